@@ -0,0 +1,445 @@
+//! Batch-Konvertierung ganzer Verzeichnisse.
+//!
+//! Die Dateien werden (optional rekursiv, unter Beachtung von `.gitignore`) gesammelt,
+//! parallel konvertiert und in gespiegelter Verzeichnisstruktur ins Zielverzeichnis
+//! geschrieben. Fehler einzelner Dateien brechen den Lauf nicht ab.
+//!
+//! Ergeben mehrere Eingaben dieselbe Ausgabedatei (z.b. `a.json` und `a.toml` → `a.yaml`),
+//! wird keine davon geschrieben; alle landen mit einem `PathError` in `BatchReport::failed`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use convrs_core::{ConvertOptions, FileFormat, FormatError};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+
+use crate::convert_io;
+
+/// Optionen für `convert_dir`.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Eingabeformat für alle Dateien, sonst anhand der Dateiendung.
+    pub from: Option<FileFormat>,
+    pub to: FileFormat,
+    /// Globs relativ zum Eingabeverzeichnis, z.b. `**/*.json`. Leer: alle lesbaren Dateien.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub recursive: bool,
+    /// `.gitignore`, `.ignore` und versteckte Dateien beachten.
+    pub respect_ignore: bool,
+}
+
+/// Ergebnis einer Batch-Konvertierung, sortiert nach Eingabepfad.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub converted: Vec<(PathBuf, PathBuf)>,
+    pub failed: Vec<(PathBuf, FormatError)>,
+}
+
+/// Konvertiert alle passenden Dateien aus `input_dir` nach `output_dir`.
+///
+/// Ist `options.sql.table` leer, wird wie bei `convert_io` pro Datei der Dateiname verwendet.
+/// Ein `Err` gibt es nur, wenn das Verzeichnis nicht gelesen werden kann oder ein Glob
+/// ungültig ist; Fehler einzelner Dateien landen in `BatchReport::failed`.
+pub fn convert_dir(
+    input_dir: &str,
+    output_dir: &str,
+    batch: &BatchOptions,
+    options: &ConvertOptions,
+) -> Result<BatchReport, FormatError> {
+    convert_selected(input_dir, output_dir, None, batch, options)
+}
+
+/// Wie `convert_dir`, aber nur für die geänderten Dateien (absolute Pfade, z.b. aus
+/// `watch`). Dateien, die `convert_dir` nicht konvertieren würde, werden übergangen.
+pub fn convert_changed(
+    input_dir: &str,
+    output_dir: &str,
+    changed: &[PathBuf],
+    batch: &BatchOptions,
+    options: &ConvertOptions,
+) -> Result<BatchReport, FormatError> {
+    let changed: HashSet<PathBuf> = changed
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+    convert_selected(input_dir, output_dir, Some(&changed), batch, options)
+}
+
+// private helper funktionen
+
+fn convert_selected(
+    input_dir: &str,
+    output_dir: &str,
+    only: Option<&HashSet<PathBuf>>,
+    batch: &BatchOptions,
+    options: &ConvertOptions,
+) -> Result<BatchReport, FormatError> {
+    let input_root = Path::new(input_dir);
+    let output_root = Path::new(output_dir);
+    if !input_root.is_dir() {
+        return Err(FormatError::IoError(format!(
+            "Input directory {} does not exist",
+            input_dir
+        )));
+    }
+
+    let mut files = collect_files(input_root, output_root, batch)?;
+    // kollisionen über alle dateien prüfen, sonst überschreibt watch die ausgabe einer anderen eingabe
+    let mut failed = output_collisions(input_root, output_root, &mut files, batch.to);
+    if let Some(only) = only {
+        files.retain(|relative| {
+            input_root
+                .join(relative)
+                .canonicalize()
+                .is_ok_and(|path| only.contains(&path))
+        });
+        failed.retain(|(input, _)| input.canonicalize().is_ok_and(|path| only.contains(&path)));
+    }
+
+    let mut results: Vec<(PathBuf, Result<PathBuf, FormatError>)> = files
+        .into_par_iter()
+        .map(|relative| {
+            let input = input_root.join(&relative);
+            let output = output_path(output_root, &relative, batch.to);
+            let result = convert_one(&input, &output, batch, options).map(|_| output);
+            (input, result)
+        })
+        .collect();
+    results.extend(failed.into_iter().map(|(input, e)| (input, Err(e))));
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut report = BatchReport::default();
+    for (input, result) in results {
+        match result {
+            Ok(output) => report.converted.push((input, output)),
+            Err(e) => report.failed.push((input, e)),
+        }
+    }
+    Ok(report)
+}
+
+fn output_path(output_root: &Path, relative: &Path, to: FileFormat) -> PathBuf {
+    output_root.join(relative.with_extension(to.extension()))
+}
+
+/// Entfernt alle Dateien aus `files`, deren Ausgabepfad auch von einer anderen Datei
+/// geschrieben würde, und gibt sie mit Fehler (als Eingabepfad) zurück.
+fn output_collisions(
+    input_root: &Path,
+    output_root: &Path,
+    files: &mut Vec<PathBuf>,
+    to: FileFormat,
+) -> Vec<(PathBuf, FormatError)> {
+    let mut by_output: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for relative in files.iter() {
+        by_output
+            .entry(output_path(output_root, relative, to))
+            .or_default()
+            .push(relative.clone());
+    }
+
+    let mut failed = Vec::new();
+    for (output, mut inputs) in by_output {
+        if inputs.len() < 2 {
+            continue;
+        }
+        inputs.sort();
+        let names: Vec<String> = inputs.iter().map(|p| p.display().to_string()).collect();
+        for relative in &inputs {
+            failed.push((
+                input_root.join(relative),
+                FormatError::PathError(format!(
+                    "Output {} would be written by several inputs: {}",
+                    output.display(),
+                    names.join(", ")
+                )),
+            ));
+        }
+        files.retain(|relative| !inputs.contains(relative));
+    }
+    failed
+}
+
+/// Sammelt die zu konvertierenden Dateien als Pfade relativ zu `input_root`.
+fn collect_files(
+    input_root: &Path,
+    output_root: &Path,
+    batch: &BatchOptions,
+) -> Result<Vec<PathBuf>, FormatError> {
+    let include = build_globs(&batch.include)?;
+    let exclude = build_globs(&batch.exclude)?;
+    // liegt das ziel im eingabeverzeichnis, dürfen frühere ergebnisse nicht wieder eingelesen werden
+    let output_root = output_root.canonicalize().ok();
+
+    let walker = WalkBuilder::new(input_root)
+        .max_depth(if batch.recursive { None } else { Some(1) })
+        .standard_filters(batch.respect_ignore)
+        .require_git(false)
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry =
+            entry.map_err(|e| FormatError::IoError(format!("Error reading directory: {}", e)))?;
+        let path = entry.path();
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if let Some(output_root) = &output_root
+            && path
+                .canonicalize()
+                .is_ok_and(|p| p.starts_with(output_root))
+        {
+            continue;
+        }
+
+        let relative = path.strip_prefix(input_root).unwrap_or(path).to_path_buf();
+        let selected = match &include {
+            Some(include) => include.is_match(&relative),
+            None => batch.from.is_some() || is_readable(&relative),
+        };
+        if selected && !exclude.as_ref().is_some_and(|e| e.is_match(&relative)) {
+            files.push(relative);
+        }
+    }
+    Ok(files)
+}
+
+fn build_globs(patterns: &[String]) -> Result<Option<GlobSet>, FormatError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| FormatError::ParseError(format!("Invalid glob '{}': {}", pattern, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| FormatError::ParseError(format!("Invalid glob: {}", e)))
+}
+
+/// Dateien mit der Endung eines lesbaren Formats (SQL ist nur Zielformat).
+///
+/// `.conf` zählt nicht dazu: die Endung haben auch nginx-, systemd- und andere Dateien,
+/// die kein HOCON sind. Sie werden nur mit `--include` oder `--from hocon` konvertiert.
+fn is_readable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| !ext.eq_ignore_ascii_case("conf"))
+        .and_then(|ext| FileFormat::from_str(ext).ok())
+        .is_some_and(|format| format.can_read())
+}
+
+fn convert_one(
+    input: &Path,
+    output: &Path,
+    batch: &BatchOptions,
+    options: &ConvertOptions,
+) -> Result<(), FormatError> {
+    let input = path_str(input)?;
+    let output_str = path_str(output)?;
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            FormatError::IoError(format!("Error creating {}: {}", parent.display(), e))
+        })?;
+    }
+
+    convert_io(input, output_str, batch.from, Some(batch.to), None, options).map(|_| ())
+}
+
+fn path_str(path: &Path) -> Result<&str, FormatError> {
+    path.to_str().ok_or_else(|| {
+        FormatError::PathError(format!("Path is not valid UTF-8: {}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(to: FileFormat) -> BatchOptions {
+        BatchOptions {
+            from: None,
+            to,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            recursive: true,
+            respect_ignore: true,
+        }
+    }
+
+    #[test]
+    fn test_convert_dir_mirrors_layout_and_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        fs::create_dir_all(input.join("nested/deep")).unwrap();
+        fs::write(input.join("a.json"), r#"{"a": 1}"#).unwrap();
+        fs::write(input.join("nested/deep/b.toml"), "b = 2\n").unwrap();
+        fs::write(input.join("nested/broken.json"), "{").unwrap();
+        fs::write(input.join("notes.txt"), "ignored").unwrap();
+        let output = dir.path().join("out");
+
+        let report = convert_dir(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            &batch(FileFormat::Yaml),
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.converted.len(), 2);
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].0.ends_with("nested/broken.json"));
+        assert_eq!(fs::read_to_string(output.join("a.yaml")).unwrap(), "a: 1\n");
+        assert_eq!(
+            fs::read_to_string(output.join("nested/deep/b.yaml")).unwrap(),
+            "b: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_dir_reports_output_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        fs::create_dir_all(&input).unwrap();
+        fs::write(input.join("a.json"), r#"{"a": 1}"#).unwrap();
+        fs::write(input.join("a.toml"), "a = 2\n").unwrap();
+        fs::write(input.join("b.json"), r#"{"b": 3}"#).unwrap();
+        let output = dir.path().join("out");
+
+        let report = convert_dir(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            &batch(FileFormat::Yaml),
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.converted.len(), 1);
+        assert!(report.converted[0].0.ends_with("b.json"));
+        assert_eq!(report.failed.len(), 2);
+        assert!(report.failed[0].0.ends_with("a.json"));
+        assert!(report.failed[1].0.ends_with("a.toml"));
+        for (_, err) in &report.failed {
+            assert!(matches!(err, FormatError::PathError(_)));
+            assert!(err.to_string().contains("a.json, a.toml"));
+        }
+        assert!(!output.join("a.yaml").exists());
+
+        // auch im watch-modus wird eine kollidierende datei nicht geschrieben
+        let report = convert_changed(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            &[input.join("a.toml")],
+            &batch(FileFormat::Yaml),
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+        assert!(report.converted.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert!(!output.join("a.yaml").exists());
+    }
+
+    #[test]
+    fn test_convert_dir_globs_gitignore_and_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path();
+        fs::create_dir_all(input.join("sub")).unwrap();
+        fs::write(input.join(".gitignore"), "secret.json\n").unwrap();
+        fs::write(input.join("keep.json"), "{}").unwrap();
+        fs::write(input.join("skip.test.json"), "{}").unwrap();
+        fs::write(input.join("secret.json"), "{}").unwrap();
+        fs::write(input.join("sub/inner.json"), "{}").unwrap();
+
+        // ausgabe im eingabeverzeichnis wird beim sammeln übersprungen
+        let output = input.join("out");
+        fs::create_dir_all(output.join("old")).unwrap();
+        fs::write(output.join("old/previous.json"), "{}").unwrap();
+
+        let mut options = batch(FileFormat::Toml);
+        options.include = vec!["**/*.json".to_string()];
+        options.exclude = vec!["*.test.json".to_string()];
+        let files = collect_files(input, &output, &options).unwrap();
+        let mut files: Vec<String> = files
+            .iter()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["keep.json", "sub/inner.json"]);
+
+        options.recursive = false;
+        options.respect_ignore = false;
+        let files = collect_files(input, &output, &options).unwrap();
+        let mut files: Vec<String> = files
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["keep.json", "secret.json"]);
+    }
+
+    #[test]
+    fn test_convert_dir_skips_conf_unless_included() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        fs::create_dir_all(&input).unwrap();
+        fs::write(input.join("app.json"), "{}").unwrap();
+        fs::write(input.join("nginx.conf"), "server { listen 80; }").unwrap();
+        let output = dir.path().join("out");
+
+        let mut options = batch(FileFormat::Json);
+        let files = collect_files(&input, &output, &options).unwrap();
+        assert_eq!(files, vec![PathBuf::from("app.json")]);
+
+        options.include = vec!["*.conf".to_string()];
+        let files = collect_files(&input, &output, &options).unwrap();
+        assert_eq!(files, vec![PathBuf::from("nginx.conf")]);
+    }
+
+    #[test]
+    fn test_convert_dir_missing_input() {
+        let err = convert_dir(
+            "does-not-exist",
+            "out",
+            &batch(FileFormat::Json),
+            &ConvertOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_convert_changed_only_rewrites_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        fs::create_dir_all(&input).unwrap();
+        fs::write(input.join("a.json"), r#"{"a": 1}"#).unwrap();
+        fs::write(input.join("b.json"), r#"{"b": 2}"#).unwrap();
+        fs::write(input.join("notes.txt"), "ignored").unwrap();
+        let output = dir.path().join("out");
+
+        let changed = vec![input.join("b.json"), input.join("notes.txt")];
+        let report = convert_changed(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            &changed,
+            &batch(FileFormat::Yaml),
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.converted.len(), 1);
+        assert!(report.failed.is_empty());
+        assert!(output.join("b.yaml").exists());
+        assert!(!output.join("a.yaml").exists());
+    }
+}
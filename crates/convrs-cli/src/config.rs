@@ -0,0 +1,589 @@
+//! Projektkonfiguration aus `.convrs.toml`.
+//!
+//! Die Datei wird vom Arbeitsverzeichnis aus aufwärts gesucht und liefert Standardwerte
+//! für `convert` (Optionen und Verzeichnis-Zuordnungen für den Batch-Modus) und `fmt`
+//! (Stil pro Format). Ein Profil unter `[profile.<name>]` wird über die restliche Datei
+//! gemergt, Flags auf der Kommandozeile haben immer Vorrang.
+//!
+//! ```toml
+//! [convert]
+//! dialect = "postgres"
+//! batch-size = 500
+//!
+//! [style.json]
+//! indent = 4
+//!
+//! [[mapping]]
+//! input-dir = "data"
+//! output-dir = "build"
+//! to = "json"
+//!
+//! [profile.ci.convert]
+//! dialect = "mysql"
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use convrs_core::fmt::{self, FmtOptions};
+use convrs_core::formats::sql::SqlDialect;
+use convrs_core::merge::{MergeOptions, merge_values};
+use convrs_core::{ConvertOptions, FileFormat, FormatError};
+use serde_json::{Map, Value as JsonValue, json};
+
+use crate::batch::BatchOptions;
+
+/// Name der Konfigurationsdatei.
+pub const CONFIG_FILE_NAME: &str = ".convrs.toml";
+
+/// Formate, für die `[style.<format>]` erlaubt ist (die von `fmt` unterstützten).
+const STYLE_FORMATS: [FileFormat; 4] = [
+    FileFormat::Json,
+    FileFormat::Jsonl,
+    FileFormat::Yaml,
+    FileFormat::Toml,
+];
+
+/// Die effektive Konfiguration eines Projekts (Datei mit Profil und Standardwerten).
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+    /// Gelesene Datei, `None` wenn es keine gibt.
+    pub path: Option<PathBuf>,
+    pub profile: Option<String>,
+    /// Standardoptionen für `convert`. Ein leerer Tabellenname bedeutet wie auf der
+    /// Kommandozeile: Name der Ausgabedatei.
+    pub convert: ConvertOptions,
+    /// Stil für `fmt` pro Format, fehlende Formate nutzen `FmtOptions::default()`.
+    pub style: Vec<(FileFormat, FmtOptions)>,
+    pub mappings: Vec<Mapping>,
+}
+
+/// Eine Verzeichnis-Zuordnung für den Batch-Modus (`[[mapping]]`).
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    /// Relativ zur Konfigurationsdatei aufgelöst.
+    pub input_dir: String,
+    pub output_dir: String,
+    pub batch: BatchOptions,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        let mut convert = ConvertOptions::default();
+        convert.sql.table = String::new();
+        ProjectConfig {
+            path: None,
+            profile: None,
+            convert,
+            style: Vec::new(),
+            mappings: Vec::new(),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Sucht `.convrs.toml` in `start` und allen übergeordneten Verzeichnissen.
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// Lädt `path` oder, ohne Angabe, die vom Arbeitsverzeichnis aus gefundene Datei.
+    /// Ohne Datei gibt es die Standardwerte; ein Profil ist dann ein Fehler.
+    pub fn load(path: Option<&str>, profile: Option<&str>) -> Result<Self, FormatError> {
+        let path = match path {
+            Some(path) => Some(PathBuf::from(path)),
+            None => std::env::current_dir()
+                .ok()
+                .and_then(|dir| Self::discover(&dir)),
+        };
+        match (path, profile) {
+            (Some(path), profile) => Self::from_file(&path, profile),
+            (None, Some(profile)) => Err(FormatError::InvalidFormat(format!(
+                "Profile '{}' given, but no {} found",
+                profile, CONFIG_FILE_NAME
+            ))),
+            (None, None) => Ok(ProjectConfig::default()),
+        }
+    }
+
+    /// Liest eine Konfigurationsdatei, optional mit Profil.
+    pub fn from_file(path: &Path, profile: Option<&str>) -> Result<Self, FormatError> {
+        let content = std::fs::read(path).map_err(|e| {
+            FormatError::IoError(format!("Error reading from {}: {}", path.display(), e))
+        })?;
+        let value = FileFormat::Toml
+            .parse_bytes(&content, &ConvertOptions::default())
+            .map_err(|e| in_file(path, e))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+
+        let mut config = parse_config(value, base_dir, profile).map_err(|e| in_file(path, e))?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Stil für `fmt` für ein Format.
+    pub fn fmt_options(&self, format: FileFormat) -> FmtOptions {
+        self.style
+            .iter()
+            .find(|(f, _)| *f == format)
+            .map(|(_, options)| options.clone())
+            .unwrap_or_default()
+    }
+
+    /// Die Zuordnung, deren Eingabeverzeichnis `input_dir` ist.
+    pub fn mapping_for(&self, input_dir: &str) -> Option<&Mapping> {
+        let wanted = Path::new(input_dir).canonicalize().ok()?;
+        self.mappings.iter().find(|mapping| {
+            Path::new(&mapping.input_dir)
+                .canonicalize()
+                .is_ok_and(|dir| dir == wanted)
+        })
+    }
+
+    /// Die effektive Konfiguration im Aufbau von `.convrs.toml` (ohne Profile), mit
+    /// allen Standardwerten.
+    pub fn to_value(&self) -> JsonValue {
+        let mut convert = json!({
+            "dialect": self.convert.sql.dialect.to_string(),
+            "batch-size": self.convert.sql.batch_size,
+            "all-sheets": self.convert.xlsx.all_sheets,
+            "binary-plist": self.convert.plist.binary,
+            "plist-dates": self.convert.plist.dates,
+        });
+        if !self.convert.sql.table.is_empty() {
+            convert["table"] = json!(self.convert.sql.table);
+        }
+        if let Some(sheet) = &self.convert.xlsx.sheet {
+            convert["sheet"] = json!(sheet);
+        }
+
+        let style: Map<String, JsonValue> = STYLE_FORMATS
+            .iter()
+            .map(|format| {
+                let options = self.fmt_options(*format);
+                (
+                    format.to_string(),
+                    json!({ "indent": options.indent, "sort-keys": options.sort_keys }),
+                )
+            })
+            .collect();
+
+        let mut value = json!({ "convert": convert, "style": style });
+        if !self.mappings.is_empty() {
+            let mappings: Vec<JsonValue> = self.mappings.iter().map(mapping_to_value).collect();
+            value["mapping"] = JsonValue::Array(mappings);
+        }
+        value
+    }
+}
+
+// private helper funktionen
+
+fn parse_config(
+    value: JsonValue,
+    base_dir: &Path,
+    profile: Option<&str>,
+) -> Result<ProjectConfig, FormatError> {
+    let JsonValue::Object(mut root) = value else {
+        return Err(invalid("the file must be a table"));
+    };
+
+    // das profil wird wie bei `merge` über die restliche datei gelegt, arrays ersetzen
+    let profiles = root.remove("profile");
+    let mut root = JsonValue::Object(root);
+    if let Some(name) = profile {
+        let available: Vec<&str> = match &profiles {
+            Some(JsonValue::Object(profiles)) => profiles.keys().map(String::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let overlay = profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(name))
+            .ok_or_else(|| {
+                invalid(&format!(
+                    "unknown profile '{}' (available: {})",
+                    name,
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ))
+            })?;
+        merge_values(&mut root, overlay, &MergeOptions::default());
+    }
+
+    let root = Section::new(&root, "")?;
+    root.check_keys(&["convert", "style", "mapping"])?;
+
+    let mut config = ProjectConfig {
+        profile: profile.map(|p| p.to_string()),
+        ..ProjectConfig::default()
+    };
+
+    if let Some(convert) = root.section("convert")? {
+        convert.check_keys(&[
+            "dialect",
+            "table",
+            "batch-size",
+            "sheet",
+            "all-sheets",
+            "binary-plist",
+            "plist-dates",
+        ])?;
+        let options = &mut config.convert;
+        if let Some(dialect) = convert.string("dialect")? {
+            options.sql.dialect = SqlDialect::from_str(&dialect)?;
+        }
+        if let Some(table) = convert.string("table")? {
+            options.sql.table = table;
+        }
+        if let Some(batch_size) = convert.positive("batch-size")? {
+            options.sql.batch_size = batch_size;
+        }
+        options.xlsx.sheet = convert.string("sheet")?;
+        options.xlsx.all_sheets = convert.bool("all-sheets")?.unwrap_or(false);
+        options.plist.binary = convert.bool("binary-plist")?.unwrap_or(false);
+        options.plist.dates = convert.bool("plist-dates")?.unwrap_or(false);
+    }
+
+    if let Some(style) = root.section("style")? {
+        for (name, value) in style.map {
+            let format = FileFormat::from_str(name)?;
+            if !fmt::supports(format) {
+                return Err(invalid(&format!(
+                    "style.{}: fmt supports json, jsonl, yaml and toml",
+                    name
+                )));
+            }
+            let section = Section::new(value, &style.key(name))?;
+            section.check_keys(&["indent", "sort-keys"])?;
+            let defaults = FmtOptions::default();
+            config.style.push((
+                format,
+                FmtOptions {
+                    indent: section.usize("indent")?.unwrap_or(defaults.indent),
+                    sort_keys: section.bool("sort-keys")?.unwrap_or(defaults.sort_keys),
+                },
+            ));
+        }
+    }
+
+    if let Some(mappings) = root.map.get("mapping") {
+        let JsonValue::Array(mappings) = mappings else {
+            return Err(invalid("mapping must be an array of tables ([[mapping]])"));
+        };
+        for (i, mapping) in mappings.iter().enumerate() {
+            let section = Section::new(mapping, &format!("mapping[{}]", i))?;
+            config.mappings.push(parse_mapping(&section, base_dir)?);
+        }
+    }
+
+    Ok(config)
+}
+
+fn parse_mapping(section: &Section, base_dir: &Path) -> Result<Mapping, FormatError> {
+    section.check_keys(&[
+        "input-dir",
+        "output-dir",
+        "from",
+        "to",
+        "include",
+        "exclude",
+        "recursive",
+        "respect-ignore",
+    ])?;
+    let dir = |key: &str| -> Result<String, FormatError> {
+        let dir = section.required_string(key)?;
+        Ok(base_dir.join(dir).to_string_lossy().into_owned())
+    };
+
+    Ok(Mapping {
+        input_dir: dir("input-dir")?,
+        output_dir: dir("output-dir")?,
+        batch: BatchOptions {
+            from: section
+                .string("from")?
+                .map(|from| FileFormat::from_str(&from))
+                .transpose()?,
+            to: FileFormat::from_str(&section.required_string("to")?)?,
+            include: section.strings("include")?,
+            exclude: section.strings("exclude")?,
+            recursive: section.bool("recursive")?.unwrap_or(false),
+            respect_ignore: section.bool("respect-ignore")?.unwrap_or(true),
+        },
+    })
+}
+
+fn mapping_to_value(mapping: &Mapping) -> JsonValue {
+    let batch = &mapping.batch;
+    let mut value = json!({
+        "input-dir": mapping.input_dir,
+        "output-dir": mapping.output_dir,
+        "to": batch.to.to_string(),
+        "include": batch.include,
+        "exclude": batch.exclude,
+        "recursive": batch.recursive,
+        "respect-ignore": batch.respect_ignore,
+    });
+    if let Some(from) = batch.from {
+        value["from"] = json!(from.to_string());
+    }
+    value
+}
+
+fn invalid(message: &str) -> FormatError {
+    FormatError::ParseError(message.to_string())
+}
+
+/// Stellt einer Fehlermeldung den Pfad der Konfigurationsdatei voran.
+fn in_file(path: &Path, error: FormatError) -> FormatError {
+    let prefix = |message: String| format!("{}: {}", path.display(), message);
+    match error {
+        FormatError::ParseError(message) => FormatError::ParseError(prefix(message)),
+        FormatError::InvalidFormat(message) => FormatError::InvalidFormat(prefix(message)),
+        other => other,
+    }
+}
+
+/// Eine Tabelle der Konfiguration mit ihrem Namen für Fehlermeldungen.
+struct Section<'a> {
+    map: &'a Map<String, JsonValue>,
+    name: String,
+}
+
+impl<'a> Section<'a> {
+    fn new(value: &'a JsonValue, name: &str) -> Result<Self, FormatError> {
+        match value {
+            JsonValue::Object(map) => Ok(Section {
+                map,
+                name: name.to_string(),
+            }),
+            _ => Err(invalid(&format!("{} must be a table", name))),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        if self.name.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.name, key)
+        }
+    }
+
+    fn check_keys(&self, allowed: &[&str]) -> Result<(), FormatError> {
+        match self.map.keys().find(|key| !allowed.contains(&key.as_str())) {
+            Some(key) => Err(invalid(&format!(
+                "unknown key '{}' (allowed: {})",
+                self.key(key),
+                allowed.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn section(&self, key: &str) -> Result<Option<Section<'a>>, FormatError> {
+        self.map
+            .get(key)
+            .map(|value| Section::new(value, &self.key(key)))
+            .transpose()
+    }
+
+    fn typed<T>(
+        &self,
+        key: &str,
+        expected: &str,
+        convert: impl Fn(&JsonValue) -> Option<T>,
+    ) -> Result<Option<T>, FormatError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(value) => convert(value)
+                .map(Some)
+                .ok_or_else(|| invalid(&format!("{} must be {}", self.key(key), expected))),
+        }
+    }
+
+    fn string(&self, key: &str) -> Result<Option<String>, FormatError> {
+        self.typed(key, "a string", |v| v.as_str().map(|s| s.to_string()))
+    }
+
+    fn required_string(&self, key: &str) -> Result<String, FormatError> {
+        self.string(key)?
+            .ok_or_else(|| invalid(&format!("{} is missing", self.key(key))))
+    }
+
+    fn bool(&self, key: &str) -> Result<Option<bool>, FormatError> {
+        self.typed(key, "true or false", |v| v.as_bool())
+    }
+
+    fn usize(&self, key: &str) -> Result<Option<usize>, FormatError> {
+        self.typed(key, "a non-negative integer", |v| {
+            v.as_u64().and_then(|n| usize::try_from(n).ok())
+        })
+    }
+
+    fn positive(&self, key: &str) -> Result<Option<usize>, FormatError> {
+        self.typed(key, "a positive integer", |v| {
+            v.as_u64()
+                .filter(|n| *n > 0)
+                .and_then(|n| usize::try_from(n).ok())
+        })
+    }
+
+    fn strings(&self, key: &str) -> Result<Vec<String>, FormatError> {
+        let strings = self.typed(key, "an array of strings", |v| {
+            v.as_array()?
+                .iter()
+                .map(|item| item.as_str().map(|s| s.to_string()))
+                .collect::<Option<Vec<_>>>()
+        })?;
+        Ok(strings.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const CONFIG: &str = r#"
+[convert]
+dialect = "postgres"
+batch-size = 500
+
+[style.json]
+indent = 4
+
+[style.yaml]
+sort-keys = true
+
+[[mapping]]
+input-dir = "data"
+output-dir = "build"
+to = "json"
+include = ["**/*.csv"]
+
+[profile.ci.convert]
+dialect = "mysql"
+table = "records"
+
+[profile.ci.style.json]
+sort-keys = true
+"#;
+
+    fn parse(content: &str, profile: Option<&str>) -> Result<ProjectConfig, FormatError> {
+        let value = FileFormat::Toml
+            .parse_bytes(content.as_bytes(), &ConvertOptions::default())
+            .unwrap();
+        parse_config(value, Path::new("project"), profile)
+    }
+
+    #[test]
+    fn test_parse_config_and_profile() {
+        let config = parse(CONFIG, None).unwrap();
+        assert_eq!(config.convert.sql.dialect, SqlDialect::Postgres);
+        assert_eq!(config.convert.sql.batch_size, 500);
+        assert_eq!(config.convert.sql.table, "");
+        assert_eq!(
+            config.fmt_options(FileFormat::Json),
+            FmtOptions {
+                indent: 4,
+                sort_keys: false
+            }
+        );
+        assert!(config.fmt_options(FileFormat::Yaml).sort_keys);
+        assert_eq!(config.fmt_options(FileFormat::Toml), FmtOptions::default());
+
+        let mapping = &config.mappings[0];
+        assert_eq!(Path::new(&mapping.input_dir), Path::new("project/data"));
+        assert_eq!(mapping.batch.to, FileFormat::Json);
+        assert_eq!(mapping.batch.include, vec!["**/*.csv"]);
+        assert!(mapping.batch.respect_ignore);
+
+        // das profil überschreibt einzelne werte, der rest bleibt
+        let config = parse(CONFIG, Some("ci")).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("ci"));
+        assert_eq!(config.convert.sql.dialect, SqlDialect::Mysql);
+        assert_eq!(config.convert.sql.table, "records");
+        assert_eq!(config.convert.sql.batch_size, 500);
+        assert_eq!(
+            config.fmt_options(FileFormat::Json),
+            FmtOptions {
+                indent: 4,
+                sort_keys: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_config_errors() {
+        let err = parse(CONFIG, Some("prod")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown profile 'prod' (available: ci)")
+        );
+
+        let err = parse("[convert]\nbatchsize = 1\n", None).unwrap_err();
+        assert!(err.to_string().contains("unknown key 'convert.batchsize'"));
+
+        let err = parse("[convert]\nbatch-size = 0\n", None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("convert.batch-size must be a positive integer")
+        );
+
+        let err = parse("[style.csv]\nindent = 2\n", None).unwrap_err();
+        assert!(err.to_string().contains("style.csv"));
+
+        let err = parse("[[mapping]]\ninput-dir = \"a\"\nto = \"json\"\n", None).unwrap_err();
+        assert!(err.to_string().contains("mapping[0].output-dir is missing"));
+    }
+
+    #[test]
+    fn test_discover_and_effective_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(ProjectConfig::discover(&nested), None);
+
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, CONFIG).unwrap();
+        assert_eq!(ProjectConfig::discover(&nested), Some(path.clone()));
+
+        let config = ProjectConfig::from_file(&path, Some("ci")).unwrap();
+        fs::create_dir_all(dir.path().join("data")).unwrap();
+        assert!(
+            config
+                .mapping_for(dir.path().join("data").to_str().unwrap())
+                .is_some()
+        );
+        assert!(config.mapping_for(nested.to_str().unwrap()).is_none());
+
+        let value = config.to_value();
+        assert_eq!(value["convert"]["dialect"], "mysql");
+        assert_eq!(value["convert"]["table"], "records");
+        assert_eq!(
+            value["style"]["json"],
+            json!({"indent": 4, "sort-keys": true})
+        );
+        assert_eq!(
+            value["style"]["toml"],
+            json!({"indent": 2, "sort-keys": false})
+        );
+        assert_eq!(value["mapping"][0]["to"], "json");
+        assert!(value.get("profile").is_none());
+
+        // die ausgabe ist selbst eine gültige konfiguration
+        let rendered = FileFormat::Toml
+            .serialize_value(&value, &ConvertOptions::default())
+            .unwrap();
+        let reparsed = FileFormat::Toml
+            .parse_bytes(&rendered, &ConvertOptions::default())
+            .unwrap();
+        let again = parse_config(reparsed, Path::new(""), None).unwrap();
+        assert_eq!(again.to_value(), value);
+    }
+}
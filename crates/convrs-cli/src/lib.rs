@@ -3,42 +3,533 @@
 //! Stellt die Konvertierungslogik (File-I/O + Format-Erkennung) bereit,
 //! die vom Binary genutzt wird.
 
-use convrs_core::{FileFormat, FormatError};
+pub mod batch;
+pub mod config;
+pub mod report;
+pub mod watch;
+
+use convrs_core::codegen::{CodegenOptions, generate_types};
+use convrs_core::diff::{Change, DiffOptions, diff_values};
+use convrs_core::edit;
+use convrs_core::fmt::{self, FmtOptions};
+use convrs_core::formats::utils::json_value_to_string;
+use convrs_core::infer::{InferOptions, infer_schema, sample_from_bytes};
+use convrs_core::lint::{LintIssue, lint_bytes};
+use convrs_core::merge::{MergeOptions, merge_all};
+use convrs_core::patch::{PatchKind, apply_patch};
+use convrs_core::repair::{Repair, repair_bytes};
+use convrs_core::validate::{Schema, Violation, validate_bytes};
+use convrs_core::{ConvertOptions, FileFormat, FormatError, KeyPath};
+use serde_json::Value as JsonValue;
 use std::fs;
+use std::io::{self, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 
+/// Pfad, der bei Ein- und Ausgabe für stdin bzw. stdout steht.
+pub const STDIO_PATH: &str = "-";
+
 /// Konvertiert eine Datei vom Input- in das Output-Format.
 /// Formate werden anhand der Dateiendungen erkannt.
 pub fn convert_file(input_path: &str, output_path: &str) -> Result<(), FormatError> {
-    // 1. Extensions parsen
-    let input_ext = Path::new(input_path)
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .ok_or_else(|| FormatError::ParseError("No input file extension found".to_string()))?;
+    convert_file_with_options(input_path, output_path, &ConvertOptions::default())
+}
+
+/// Wie `convert_file`, aber mit format-spezifischen Optionen (SQL-Dialekt, XLSX-Sheet, ...).
+pub fn convert_file_with_options(
+    input_path: &str,
+    output_path: &str,
+    options: &ConvertOptions,
+) -> Result<(), FormatError> {
+    convert_io(input_path, output_path, None, None, None, options).map(|_| ())
+}
+
+/// Konvertierung für Pipelines: `-` steht für stdin bzw. stdout.
+///
+/// Explizite Formate haben Vorrang vor der Dateiendung. Für stdin ohne `input_format`
+/// wird das Format am Inhalt erkannt, für stdout muss `output_format` gesetzt sein.
+/// Mit `schema` wird die Eingabe vorher validiert; gibt es Verletzungen, wird nichts
+/// geschrieben und die Verletzungen werden zurückgegeben.
+/// Ist `options.sql.table` leer, wird der Name der Ausgabedatei als Tabellenname verwendet.
+pub fn convert_io(
+    input_path: &str,
+    output_path: &str,
+    input_format: Option<FileFormat>,
+    output_format: Option<FileFormat>,
+    schema: Option<&Schema>,
+    options: &ConvertOptions,
+) -> Result<Vec<Violation>, FormatError> {
+    // 1. Ausgabeformat vor dem lesen bestimmen, damit fehler früh auffallen
+    let output_format = resolve_output_format(output_path, output_format)?;
+
+    // 2. Eingabe lesen, als bytes damit auch binärformate (xlsx) funktionieren
+    let input = Input::read(input_path, input_format)?;
+
+    // 3. Optional gegen das schema validieren
+    if let Some(schema) = schema {
+        let violations = input.validate(schema, options)?;
+        if !violations.is_empty() {
+            return Ok(violations);
+        }
+    }
+
+    // 4. Konvertierung vom input-format in das output-format
+    let target = OutputTarget {
+        path: output_path.to_string(),
+        format: Some(output_format),
+        options: options.clone(),
+    };
+    let result = input.render(&target)?;
+
+    // 5. Ergebnis schreiben
+    write_file(output_path, &result)?;
+    Ok(Vec::new())
+}
+
+/// Eine gelesene Eingabe (Datei oder stdin) mit aufgelöstem Format.
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub path: String,
+    pub format: FileFormat,
+    pub content: Vec<u8>,
+}
+
+impl Input {
+    /// Liest `path` (`-` für stdin). Das Format kommt aus `format`, sonst aus der
+    /// Dateiendung, bei stdin aus dem Inhalt.
+    pub fn read(path: &str, format: Option<FileFormat>) -> Result<Self, FormatError> {
+        let format = match format {
+            Some(format) => Some(format),
+            None if path == STDIO_PATH => None,
+            None => Some(detect_format(path, "input")?),
+        };
+        let content = read_file(path)?;
+        let format = match format {
+            Some(format) => format,
+            None => FileFormat::sniff(&content).ok_or_else(|| {
+                FormatError::InvalidFormat(
+                    "Could not detect the input format from stdin, use --from".to_string(),
+                )
+            })?,
+        };
+
+        Ok(Input {
+            path: path.to_string(),
+            format,
+            content,
+        })
+    }
+
+    /// Repariert häufige Syntaxfehler (siehe `convrs_core::repair`), wenn die Eingabe
+    /// nicht gelesen werden kann. Liefert die angewendeten Reparaturen.
+    pub fn repair(&mut self, options: &ConvertOptions) -> Result<Vec<Repair>, FormatError> {
+        let repaired = repair_bytes(self.format, &self.content, &self.options(options))?;
+        self.content = repaired.content;
+        Ok(repaired.repairs)
+    }
+
+    /// Validiert die Eingabe gegen ein Schema, mit Positionen wo möglich.
+    pub fn validate(
+        &self,
+        schema: &Schema,
+        options: &ConvertOptions,
+    ) -> Result<Vec<Violation>, FormatError> {
+        validate_bytes(schema, self.format, &self.content, &self.options(options))
+    }
+
+    /// Konvertiert in ein einzelnes Ziel, ohne zu schreiben (Bytes wie bei `convert_io`).
+    pub fn render(&self, target: &OutputTarget) -> Result<Vec<u8>, FormatError> {
+        let format = resolve_output_format(&target.path, target.format)?;
+        let options = with_table_name(&target.path, &self.options(&target.options));
+        self.format.convert_bytes(&self.content, format, &options)
+    }
+
+    /// Konvertiert in mehrere Ziele, ohne zu schreiben. Die Eingabe wird einmal geparst
+    /// (inklusive `select`), jedes Ziel wird aus demselben Wert mit seinen eigenen
+    /// Optionen serialisiert.
+    ///
+    /// `Err` nur, wenn die Eingabe nicht geparst werden kann, sonst ein Ergebnis pro Ziel.
+    pub fn render_all(
+        &self,
+        targets: &[OutputTarget],
+        options: &ConvertOptions,
+    ) -> Result<Vec<Result<Vec<u8>, FormatError>>, FormatError> {
+        let options = self.options(options);
+        let mut value = self.format.parse_bytes(&self.content, &options)?;
+        if let Some(query) = &options.select {
+            value = query.select(&value)?;
+        }
+
+        Ok(targets
+            .iter()
+            .map(|target| {
+                let format = resolve_output_format(&target.path, target.format)?;
+                let options = with_table_name(&target.path, &target.options);
+                format.serialize_value(&value, &options)
+            })
+            .collect())
+    }
+
+    /// Wie `render_all`, schreibt aber jedes erfolgreich konvertierte Ziel.
+    pub fn convert_to(
+        &self,
+        targets: &[OutputTarget],
+        options: &ConvertOptions,
+    ) -> Result<Vec<Result<(), FormatError>>, FormatError> {
+        Ok(self
+            .render_all(targets, options)?
+            .into_iter()
+            .zip(targets)
+            .map(|(result, target)| write_file(&target.path, &result?))
+            .collect())
+    }
+
+    /// Optionen für das Parsen: HOCON-Includes relativ zur Eingabedatei.
+    fn options(&self, options: &ConvertOptions) -> ConvertOptions {
+        with_include_dir(&self.path, options)
+    }
+}
+
+/// Ein Ausgabeziel für `Input::convert_to`: Pfad (oder `-`), optional Format und eigene Optionen.
+#[derive(Debug, Clone)]
+pub struct OutputTarget {
+    pub path: String,
+    pub format: Option<FileFormat>,
+    pub options: ConvertOptions,
+}
+
+impl OutputTarget {
+    /// Liest eine Angabe `pfad[:key=value,...]`, Optionen ohne Angabe kommen aus `defaults`.
+    ///
+    /// Erlaubte Keys: `to`, `dialect`, `table`, `batch-size`, `binary-plist` und `plist-dates`
+    /// (die letzten beiden auch ohne Wert).
+    pub fn parse(
+        spec: &str,
+        format: Option<FileFormat>,
+        defaults: &ConvertOptions,
+    ) -> Result<Self, FormatError> {
+        let mut target = OutputTarget {
+            path: spec.to_string(),
+            format,
+            options: defaults.clone(),
+        };
+
+        // nur ein suffix aus key=value paaren zählt als optionen, z.b. nicht `C:\daten`
+        let Some((path, settings)) = spec.rsplit_once(':').filter(|(_, settings)| {
+            settings
+                .split(',')
+                .all(|item| item.contains('=') || item == "binary-plist" || item == "plist-dates")
+        }) else {
+            return Ok(target);
+        };
+        target.path = path.to_string();
+
+        for item in settings.split(',') {
+            let (key, value) = item.split_once('=').unwrap_or((item, "true"));
+            let invalid =
+                |e: String| FormatError::InvalidFormat(format!("Invalid value for '{}': {}", key, e));
+            match key {
+                "to" => target.format = Some(FileFormat::from_str(value)?),
+                "dialect" => target.options.sql.dialect = value.parse()?,
+                "table" => target.options.sql.table = value.to_string(),
+                "batch-size" => {
+                    target.options.sql.batch_size =
+                        value.parse().map_err(|e: std::num::ParseIntError| invalid(e.to_string()))?
+                }
+                "binary-plist" => {
+                    target.options.plist.binary =
+                        value.parse().map_err(|e: std::str::ParseBoolError| invalid(e.to_string()))?
+                }
+                "plist-dates" => {
+                    target.options.plist.dates =
+                        value.parse().map_err(|e: std::str::ParseBoolError| invalid(e.to_string()))?
+                }
+                _ => {
+                    return Err(FormatError::InvalidFormat(format!(
+                        "Unknown output option '{}' in '{}'",
+                        key, spec
+                    )));
+                }
+            }
+        }
+        Ok(target)
+    }
+}
+
+/// Ergebnis von `check_output`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    UpToDate,
+    Missing,
+    /// Gleicher Inhalt, nur anders formatiert (z.b. Einrückung oder Key-Reihenfolge).
+    Reformatted,
+    /// Inhaltlich verschieden, vom bestehenden zum erwarteten Inhalt. Leer, wenn das
+    /// Zielformat nicht gelesen werden kann (z.b. SQL).
+    Changed(Vec<Change>),
+}
+
+/// Vergleicht die erwarteten Bytes eines Ziels mit der bestehenden Datei, zuerst
+/// byteweise und bei Unterschieden semantisch. Schreibt nichts.
+pub fn check_output(target: &OutputTarget, expected: &[u8]) -> Result<CheckStatus, FormatError> {
+    let format = resolve_output_format(&target.path, target.format)?;
+    if target.path == STDIO_PATH || !Path::new(&target.path).exists() {
+        return Ok(CheckStatus::Missing);
+    }
+    let existing = read_file(&target.path)?;
+    if existing == expected {
+        return Ok(CheckStatus::UpToDate);
+    }
+
+    let parse = |content: &[u8]| format.parse_bytes(content, &target.options).ok();
+    match (parse(&existing), parse(expected)) {
+        (Some(old), Some(new)) => {
+            let changes = diff_values(&old, &new, &DiffOptions::default());
+            if changes.is_empty() {
+                Ok(CheckStatus::Reformatted)
+            } else {
+                Ok(CheckStatus::Changed(changes))
+            }
+        }
+        _ => Ok(CheckStatus::Changed(Vec::new())),
+    }
+}
+
+/// Liest den Wert unter `path` aus einer Datei.
+pub fn get_file_value(file_path: &str, path: &KeyPath) -> Result<JsonValue, FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let options = with_include_dir(file_path, &ConvertOptions::default());
+    edit::get_value(format, &content, path, &options)
+}
+
+/// Setzt den Wert unter `path` und schreibt die Datei im selben Format zurück.
+pub fn set_file_value(file_path: &str, path: &KeyPath, value: &JsonValue) -> Result<(), FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let result = edit::set_value(format, &content, path, value, &ConvertOptions::default())?;
+    write_file(file_path, &result)
+}
+
+/// Löscht den Wert unter `path` und schreibt die Datei im selben Format zurück.
+pub fn delete_file_value(file_path: &str, path: &KeyPath) -> Result<(), FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let result = edit::delete_value(format, &content, path, &ConvertOptions::default())?;
+    write_file(file_path, &result)
+}
+
+/// Mergt mehrere Dateien (beliebige Formate) der Reihe nach und schreibt das Ergebnis.
+/// Spätere Dateien überschreiben frühere.
+pub fn merge_files(
+    input_paths: &[String],
+    output_path: &str,
+    options: &MergeOptions,
+) -> Result<(), FormatError> {
+    // ausgabeformat vor dem lesen prüfen, damit fehler früh auffallen
+    detect_format(output_path, "output")?;
+
+    let values = input_paths
+        .iter()
+        .map(|input_path| read_value(input_path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    write_value_file(output_path, &merge_all(values, options))
+}
+
+/// Vergleicht zwei Dateien (beliebige Formate) semantisch.
+pub fn diff_files(
+    left_path: &str,
+    right_path: &str,
+    options: &DiffOptions,
+) -> Result<Vec<Change>, FormatError> {
+    let left = read_value(left_path)?;
+    let right = read_value(right_path)?;
+    Ok(diff_values(&left, &right, options))
+}
+
+/// Wendet eine Patch-Datei (JSON Patch oder Merge Patch, beliebiges Format) auf eine Datei an.
+/// Ohne `output_path` wird die Zieldatei überschrieben.
+pub fn patch_file(
+    target_path: &str,
+    patch_path: &str,
+    output_path: Option<&str>,
+    kind: Option<PatchKind>,
+) -> Result<(), FormatError> {
+    let output_path = output_path.unwrap_or(target_path);
+    detect_format(output_path, "output")?;
+
+    let mut document = read_value(target_path)?;
+    let patch = read_value(patch_path)?;
+    apply_patch(&mut document, &patch, kind)?;
+
+    write_value_file(output_path, &document)
+}
+
+/// Lädt ein JSON Schema. Das Schema darf in jedem lesbaren Format vorliegen (z.b. YAML).
+pub fn load_schema(schema_path: &str) -> Result<Schema, FormatError> {
+    Schema::new(&read_value(schema_path)?)
+}
 
-    let output_ext = Path::new(output_path)
+/// Validiert eine Datei gegen ein Schema und gibt alle Verletzungen zurück.
+pub fn validate_file(file_path: &str, schema: &Schema) -> Result<Vec<Violation>, FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let options = with_include_dir(file_path, &ConvertOptions::default());
+    validate_bytes(schema, format, &content, &options)
+}
+
+/// Formatiert eine Datei kanonisch im selben Format. Gibt zurück, ob sich der Inhalt
+/// ändert; mit `check` wird die Datei nicht geschrieben.
+pub fn fmt_file(file_path: &str, options: &FmtOptions, check: bool) -> Result<bool, FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let formatted = fmt::format_bytes(format, &content, options)?;
+    if formatted == content {
+        return Ok(false);
+    }
+    if !check {
+        write_file(file_path, &formatted)?;
+    }
+    Ok(true)
+}
+
+/// Prüft eine Datei auf die Lint-Regeln aus `convrs_core::lint`.
+pub fn lint_file(file_path: &str) -> Result<Vec<LintIssue>, FormatError> {
+    let format = detect_format(file_path, "input")?;
+    let content = read_file(file_path)?;
+    let options = with_include_dir(file_path, &ConvertOptions::default());
+    Ok(lint_bytes(format, &content, &options))
+}
+
+/// Leitet ein JSON Schema aus einer oder mehreren Beispieldateien (beliebige Formate) ab.
+pub fn infer_schema_files(
+    sample_paths: &[String],
+    options: &InferOptions,
+) -> Result<JsonValue, FormatError> {
+    Ok(infer_schema(&read_samples(sample_paths)?, options))
+}
+
+/// Erzeugt Typdefinitionen (Rust, TypeScript, Go) aus Beispieldateien (beliebige Formate).
+pub fn codegen_files(sample_paths: &[String], options: &CodegenOptions) -> Result<String, FormatError> {
+    Ok(generate_types(&read_samples(sample_paths)?, options))
+}
+
+/// Schreibt einen Wert im Format, das anhand der Dateiendung erkannt wird.
+pub fn write_value_file(output_path: &str, value: &JsonValue) -> Result<(), FormatError> {
+    let output_format = detect_format(output_path, "output")?;
+    let result = output_format.serialize_value(value, &ConvertOptions::default())?;
+    write_file(output_path, &result)
+}
+
+/// Formatiert einen Wert für die Ausgabe von `get`: Skalare als reiner Text,
+/// Objekte und Arrays im Format der Datei (JSON, falls das Format nicht schreibbar ist).
+pub fn render_value(value: &JsonValue, format: FileFormat) -> String {
+    match value {
+        JsonValue::Object(_) | JsonValue::Array(_) => {
+            let rendered = if format.is_binary() {
+                None
+            } else {
+                format
+                    .serialize_value(value, &ConvertOptions::default())
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+            };
+            rendered
+                .unwrap_or_else(|| serde_json::to_string_pretty(value).unwrap_or_default())
+                .trim_end()
+                .to_string()
+        }
+        other => json_value_to_string(other),
+    }
+}
+
+/// Erkennt das format anhand der dateiendung. `role` ist "input" oder "output" für die fehlermeldung.
+pub fn detect_format(path: &str, role: &str) -> Result<FileFormat, FormatError> {
+    let ext = Path::new(path)
         .extension()
         .and_then(|ext| ext.to_str())
-        .ok_or_else(|| FormatError::ParseError("No output file extension found".to_string()))?;
+        .ok_or_else(|| FormatError::InvalidFormat(format!("No {} file extension found", role)))?;
 
-        // hier wird das format aus der dateiendung erkannt und in das FileFormat enum umgewandelt
-        // dadurch wird das format bestimmt
-    let input_format = FileFormat::from_str(input_ext)?;
-    let output_format = FileFormat::from_str(output_ext)?;
+    // hier wird das format aus der dateiendung erkannt und in das FileFormat enum umgewandelt
+    FileFormat::from_str(ext)
+}
+
+// private helper funktionen
+
+fn read_file(path: &str) -> Result<Vec<u8>, FormatError> {
+    if path == STDIO_PATH {
+        let mut content = Vec::new();
+        return io::stdin()
+            .read_to_end(&mut content)
+            .map(|_| content)
+            .map_err(|e| FormatError::IoError(format!("Error reading from stdin: {}", e)));
+    }
+    fs::read(path).map_err(|e| FormatError::IoError(format!("Error reading from {}: {}", path, e)))
+}
+
+/// Liest eine Datei und parst sie anhand der Dateiendung ins gemeinsame Datenmodell.
+fn read_value(path: &str) -> Result<JsonValue, FormatError> {
+    let format = detect_format(path, "input")?;
+    let content = read_file(path)?;
+    format.parse_bytes(&content, &with_include_dir(path, &ConvertOptions::default()))
+}
+
+/// Liest Beispieldateien für `schema infer` und `codegen`.
+fn read_samples(paths: &[String]) -> Result<Vec<JsonValue>, FormatError> {
+    paths
+        .iter()
+        .map(|path| {
+            let format = detect_format(path, "input")?;
+            let content = read_file(path)?;
+            sample_from_bytes(format, &content, &with_include_dir(path, &ConvertOptions::default()))
+        })
+        .collect()
+}
 
-    // 2. Datei lesen und rohtext holen
-    let content = fs::read_to_string(input_path)
-        .map_err(|e| FormatError::IoError(format!("Error reading from {}: {}", input_path, e)))?;
+fn write_file(path: &str, content: &[u8]) -> Result<(), FormatError> {
+    if path == STDIO_PATH {
+        let mut stdout = io::stdout().lock();
+        return stdout
+            .write_all(content)
+            .and_then(|_| stdout.flush())
+            .map_err(|e| FormatError::IoError(format!("Error writing to stdout: {}", e)));
+    }
+    fs::write(path, content)
+        .map_err(|e| FormatError::IoError(format!("Error writing to {}: {}", path, e)))
+}
 
-    // 3. Konvertierung vom input-format in das output-format
-    let result = input_format.convert(&content, output_format)?;
+/// Explizites format, sonst dateiendung; stdout braucht immer ein explizites format.
+fn resolve_output_format(path: &str, format: Option<FileFormat>) -> Result<FileFormat, FormatError> {
+    match format {
+        Some(format) => Ok(format),
+        None if path == STDIO_PATH => Err(FormatError::InvalidFormat(
+            "No output format for stdout, use --to".to_string(),
+        )),
+        None => detect_format(path, "output"),
+    }
+}
 
-    // 4. Ergebnis schreiben
-    fs::write(output_path, result)
-        .map_err(|e| FormatError::IoError(format!("Error writing to {}: {}", output_path, e)))?;
+/// Ohne tabellennamen wird der name der ausgabedatei verwendet (bei stdout "data").
+fn with_table_name(output_path: &str, options: &ConvertOptions) -> ConvertOptions {
+    let mut options = options.clone();
+    if options.sql.table.is_empty() {
+        options.sql.table = Path::new(output_path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|_| output_path != STDIO_PATH)
+            .unwrap_or("data")
+            .to_string();
+    }
+    options
+}
 
-    Ok(())
+/// HOCON-includes werden ohne explizites verzeichnis relativ zur eingabedatei gesucht.
+fn with_include_dir(input_path: &str, options: &ConvertOptions) -> ConvertOptions {
+    let mut options = options.clone();
+    if options.hocon.include_dir.is_none() && input_path != STDIO_PATH {
+        options.hocon.include_dir = Path::new(input_path).parent().map(Path::to_path_buf);
+    }
+    options
 }
 
 #[cfg(test)]
@@ -47,16 +538,30 @@ mod tests {
 
     #[test]
     fn test_convert_file_missing_extension() {
-        let result = convert_file("noext", "output.json");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("extension"));
+        let err = convert_file("noext", "output.json").unwrap_err();
+        assert!(matches!(err, FormatError::InvalidFormat(_)));
+        assert!(err.to_string().contains("extension"));
     }
 
     #[test]
     fn test_convert_file_unknown_format() {
-        let result = convert_file("input.xml", "output.json");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Unknown format"));
+        let err = convert_file("input.xml", "output.json").unwrap_err();
+        assert!(matches!(err, FormatError::InvalidFormat(_)));
+        assert!(err.to_string().contains("Unknown format"));
+    }
+
+    #[test]
+    fn test_render_value() {
+        assert_eq!(render_value(&serde_json::json!("text"), FileFormat::Yaml), "text");
+        assert_eq!(render_value(&serde_json::json!(42), FileFormat::Toml), "42");
+        assert_eq!(
+            render_value(&serde_json::json!({"a": 1}), FileFormat::Yaml),
+            "a: 1"
+        );
+        assert_eq!(
+            render_value(&serde_json::json!([1]), FileFormat::Xlsx),
+            "[\n  1\n]"
+        );
     }
 
     #[test]
@@ -65,4 +570,104 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Error reading"));
     }
+
+    #[test]
+    fn test_output_target_parse() {
+        let defaults = ConvertOptions::default();
+
+        let target = OutputTarget::parse("out/users.sql", None, &defaults).unwrap();
+        assert_eq!(target.path, "out/users.sql");
+        assert_eq!(target.options, defaults);
+
+        let target = OutputTarget::parse(
+            "users.txt:to=sql,dialect=postgres,table=people,batch-size=10",
+            Some(FileFormat::Json),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(target.path, "users.txt");
+        assert_eq!(target.format, Some(FileFormat::Sql));
+        assert_eq!(target.options.sql.table, "people");
+        assert_eq!(target.options.sql.batch_size, 10);
+
+        let target = OutputTarget::parse("Info.plist:binary-plist", None, &defaults).unwrap();
+        assert!(target.options.plist.binary);
+
+        let target = OutputTarget::parse("Info.plist:plist-dates", None, &defaults).unwrap();
+        assert!(target.options.plist.dates);
+
+        // ein doppelpunkt ohne key=value gehört zum pfad
+        let target = OutputTarget::parse("C:\\data\\out.json", None, &defaults).unwrap();
+        assert_eq!(target.path, "C:\\data\\out.json");
+
+        let err = OutputTarget::parse("out.json:indent=2", None, &defaults).unwrap_err();
+        assert!(err.to_string().contains("Unknown output option 'indent'"));
+    }
+
+    #[test]
+    fn test_input_convert_to_reports_each_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("users.csv");
+        fs::write(&input_path, "id,name\n1,ann\n").unwrap();
+        let json_path = dir.path().join("users.json").to_string_lossy().into_owned();
+        let fwf_path = dir.path().join("users.fwf").to_string_lossy().into_owned();
+
+        let input = Input::read(input_path.to_str().unwrap(), None).unwrap();
+        let defaults = ConvertOptions::default();
+        let targets = [
+            OutputTarget::parse(&json_path, None, &defaults).unwrap(),
+            OutputTarget::parse(&fwf_path, None, &defaults).unwrap(),
+        ];
+        let results = input.convert_to(&targets, &defaults).unwrap();
+
+        assert!(results[0].is_ok());
+        assert!(results[1].as_ref().unwrap_err().to_string().contains("only supported as input"));
+        let written: JsonValue = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!([{"id": 1, "name": "ann"}]));
+    }
+
+    #[test]
+    fn test_check_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json").to_string_lossy().into_owned();
+        let target = OutputTarget::parse(&path, None, &ConvertOptions::default()).unwrap();
+        let expected = b"{\n  \"a\": 1,\n  \"b\": 2\n}";
+
+        assert_eq!(check_output(&target, expected).unwrap(), CheckStatus::Missing);
+
+        fs::write(&path, expected).unwrap();
+        assert_eq!(check_output(&target, expected).unwrap(), CheckStatus::UpToDate);
+
+        fs::write(&path, r#"{"b": 2, "a": 1}"#).unwrap();
+        assert_eq!(check_output(&target, expected).unwrap(), CheckStatus::Reformatted);
+
+        fs::write(&path, r#"{"a": 1, "b": 3}"#).unwrap();
+        match check_output(&target, expected).unwrap() {
+            CheckStatus::Changed(changes) => {
+                assert_eq!(changes.len(), 1);
+                assert_eq!(changes[0].path().to_string(), ".b");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fmt_file_check_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let path_str = path.to_str().unwrap();
+        fs::write(&path, r#"{"b":1,"a":[1,"x"]}"#).unwrap();
+
+        let options = FmtOptions::default();
+        assert!(fmt_file(path_str, &options, true).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"b":1,"a":[1,"x"]}"#);
+
+        assert!(fmt_file(path_str, &options, false).unwrap());
+        assert!(!fmt_file(path_str, &options, true).unwrap());
+
+        let issues = lint_file(path_str).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "mixed-type-array");
+        assert!(fmt_file("data.csv", &options, true).is_err());
+    }
 }
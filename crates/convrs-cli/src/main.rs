@@ -1,43 +1,1238 @@
 //! Nutzt convrs-core für die Konvertierungslogik und ergänzt
 //! Argument-Parsing via Clap. Die Konvertierungslogik liegt in lib.rs, damit sie auch in anderen Projekten verwendet werden kann.
 
-use clap::{Parser, Subcommand};
+use std::ffi::OsStr;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-use convrs_cli::convert_file;
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::{Arg, ArgGroup, Args, Command, CommandFactory, Parser, Subcommand, ValueHint};
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+use clap_complete::{CompleteEnv, Shell};
+
+use convrs_cli::batch::{BatchOptions, convert_changed, convert_dir};
+use convrs_cli::config::{CONFIG_FILE_NAME, ProjectConfig};
+use convrs_cli::report::{EXIT_FAILED, Message, MessageFormat, Reporter};
+use convrs_cli::watch::{WatchTarget, watch};
+use convrs_cli::{
+    CheckStatus, Input, OutputTarget, STDIO_PATH, check_output, codegen_files, convert_io,
+    delete_file_value, detect_format, diff_files, fmt_file, get_file_value, infer_schema_files,
+    lint_file, load_schema, merge_files, patch_file, render_value, set_file_value, validate_file,
+    write_value_file,
+};
+use convrs_core::codegen::{CodegenOptions, Language};
+use convrs_core::diff::{DiffFormat, DiffOptions, render_diff};
+use convrs_core::edit::value_from_arg;
+use convrs_core::fmt::FmtOptions;
+use convrs_core::formats::fixed_width::{FixedWidthColumn, FixedWidthOptions};
+use convrs_core::formats::hocon::HoconOptions;
+use convrs_core::formats::plist::PlistOptions;
+use convrs_core::formats::sql::{SqlDialect, SqlOptions};
+use convrs_core::formats::xlsx::XlsxOptions;
+use convrs_core::infer::InferOptions;
+use convrs_core::merge::{ArrayStrategy, MergeOptions, NullStrategy};
+use convrs_core::patch::PatchKind;
+use convrs_core::validate::{Schema, Violation};
+use convrs_core::{ConvertOptions, FileFormat, FormatError, KeyPath, Query};
+use serde_json::Value as JsonValue;
 
 // hier befindet sich der CLI-Parser
 #[derive(Parser)]
 #[command(name = "convrs")]
-#[command(about = "Format-Converter for JSON, YAML, TOML, CSV")]
+#[command(about = "Format-Converter for JSON, YAML, TOML, CSV, SQL, XLSX, plist, logfmt, JSON Lines, HCL, HOCON")]
 #[command(version = "0.1.0")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Konfigurationsdatei statt der ersten .convrs.toml im Arbeitsverzeichnis oder darüber
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<String>,
+
+    /// Profil aus der Konfigurationsdatei ([profile.<name>]), überschreibt deren Werte
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Meldungen als human (Text) oder json (ein JSON-Objekt pro Zeile, auf stderr)
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "human")]
+    message_format: MessageFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    Convert {
-        /// Eingabedatei wird anhand der Dateiendung erkannt.
+    Convert(Box<ConvertArgs>),
+    /// Validiert Dateien (beliebige Formate) gegen ein JSON Schema
+    Validate {
+        /// JSON Schema, darf auch als YAML oder TOML vorliegen
+        #[arg(short, long)]
+        schema: String,
+
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<String>,
+    },
+    /// Arbeiten mit JSON Schemas
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+    /// Erzeugt Typdefinitionen (Rust, TypeScript, Go) aus Beispieldateien
+    Codegen {
+        /// Beispieldateien in beliebigen Formaten, Keys die nicht überall vorkommen werden optional
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<String>,
+
+        /// Zielsprache: rust, typescript oder go
+        #[arg(long)]
+        lang: Language,
+
+        /// Name des Wurzeltyps (Standard: Name der ersten Datei, z.b. app-config.yaml → AppConfig)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Code in eine Datei schreiben statt auf stdout
         #[arg(short, long)]
-        input: String,
+        output: Option<String>,
+    },
+    /// Mergt mehrere Dateien (auch verschiedener Formate), spätere überschreiben frühere
+    Merge {
+        /// Eingabedateien in Reihenfolge, z.b. base.yaml env.toml local.json
+        #[arg(required = true, num_args = 1..)]
+        inputs: Vec<String>,
 
-        /// dasselbe für die ausgabedatei
+        /// Ausgabedatei, das Format wird anhand der Dateiendung erkannt
         #[arg(short, long)]
         output: String,
+
+        /// Strategie für Arrays: replace, append oder key:<feld> (Objekte mit gleichem Feld mergen)
+        #[arg(long, default_value = "replace")]
+        arrays: ArrayStrategy,
+
+        /// `null` in späteren Dateien löscht den Key, statt ihn auf null zu setzen
+        #[arg(long)]
+        delete_nulls: bool,
+    },
+    /// Vergleicht zwei Dateien semantisch (auch verschiedener Formate).
+    /// Exit-Code 0: gleich, 1: unterschiedlich, ab 3: Fehler
+    Diff {
+        left: String,
+
+        right: String,
+
+        /// Reihenfolge in Arrays ignorieren
+        #[arg(long)]
+        ignore_array_order: bool,
+
+        /// Ausgabe als text, json oder patch (JSON Patch, RFC 6902)
+        #[arg(long, default_value = "text")]
+        format: DiffFormat,
+    },
+    /// Wendet einen JSON Patch (RFC 6902) oder Merge Patch (RFC 7386) auf eine Datei an
+    Patch {
+        /// Zieldatei, wird ohne --output überschrieben
+        target: String,
+
+        /// Patch-Datei (JSON, YAML, ...): ein Array ist ein JSON Patch, ein Objekt ein Merge Patch
+        patch: String,
+
+        /// Ergebnis in eine andere Datei schreiben
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Patch-Art erzwingen: json-patch oder merge-patch
+        #[arg(long)]
+        kind: Option<PatchKind>,
+    },
+    /// Formatiert JSON-, JSON-Lines-, YAML- und TOML-Dateien kanonisch im selben Format
+    Fmt {
+        /// Dateien, die an Ort und Stelle formatiert werden
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<String>,
+
+        /// Nichts schreiben, Exit-Code 1 wenn eine Datei anders formatiert wäre
+        #[arg(long)]
+        check: bool,
+
+        /// Zusätzlich Lint-Regeln prüfen (doppelte Keys, Tabs in YAML, mehrdeutige
+        /// YAML-Booleans, Arrays mit gemischten Typen)
+        #[arg(long)]
+        lint: bool,
+
+        /// Einrückung in Leerzeichen für JSON (Standard: 2 oder [style.json] in .convrs.toml)
+        #[arg(long)]
+        indent: Option<usize>,
+
+        /// Keys in Objekten alphabetisch sortieren
+        #[arg(long)]
+        sort_keys: bool,
+    },
+    /// Zeigt die effektive Projektkonfiguration (.convrs.toml mit Profil und Standardwerten)
+    Config,
+    /// Gibt ein Completion-Skript aus, z.b. `convrs completions zsh > ~/.zfunc/_convrs`
+    Completions {
+        /// bash, zsh, fish, powershell oder elvish
+        shell: Shell,
+    },
+    /// Erzeugt Man-Pages aus der CLI-Definition, ohne --out-dir nur convrs(1) auf stdout
+    Man {
+        /// Verzeichnis für convrs.1 und eine Seite pro Subcommand (convrs-convert.1, ...)
+        #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+        out_dir: Option<String>,
+    },
+    /// Gibt den Wert unter einem Pfad aus, z.b. `convrs get config.toml server.port`
+    Get {
+        /// Datei, das Format wird anhand der Dateiendung erkannt
+        file: String,
+
+        /// Pfad wie `.server.port` oder `servers[0].name`
+        path: KeyPath,
+    },
+    /// Setzt den Wert unter einem Pfad und schreibt die Datei im selben Format zurück
+    Set {
+        file: String,
+
+        path: KeyPath,
+
+        /// Neuer Wert, wird als JSON gelesen (`42`, `true`, `[1,2]`), sonst als String
+        value: String,
+    },
+    /// Löscht den Wert unter einem Pfad und schreibt die Datei im selben Format zurück
+    Delete {
+        file: String,
+
+        path: KeyPath,
+    },
+}
+
+// ja/nein-flags sind `Option<bool>`: `--flag` bzw. `--flag=false` überschreiben
+// .convrs.toml in beide richtungen, ohne flag gilt der wert aus der datei
+#[derive(Args)]
+#[command(group(ArgGroup::new("batch_input").args(["input_dir", "mappings"])))]
+struct ConvertArgs {
+    /// Eingabedatei wird anhand der Dateiendung erkannt, `-` liest von stdin.
+    #[arg(short, long, default_value = "-")]
+    input: String,
+
+    /// dasselbe für die ausgabedatei, `-` schreibt auf stdout (dann ist --to nötig).
+    /// Mehrfach möglich, eigene Optionen als pfad:key=value,... (to, dialect, table,
+    /// batch-size, binary-plist, plist-dates)
+    #[arg(short, long, value_name = "OUTPUT")]
+    output: Vec<String>,
+
+    /// Eingabeformat statt Dateiendung bzw. Inhaltserkennung bei stdin
+    #[arg(long, value_name = "FORMAT", value_parser = FormatParser::readable(), add = ArgValueCompleter::new(complete_readable))]
+    from: Option<FileFormat>,
+
+    /// Ausgabeformat statt Dateiendung, kommagetrennt für mehrere Ausgaben
+    /// (eines pro -o, oder mehrere für ein einzelnes -o, dessen Endung ersetzt wird)
+    #[arg(long, value_name = "FORMAT", value_delimiter = ',', value_parser = FormatParser::writable(), add = ArgValueCompleter::new(complete_writable))]
+    to: Vec<FileFormat>,
+
+    /// Konvertiert alle lesbaren Dateien eines Verzeichnisses (braucht --to und --output-dir,
+    /// ausser das Verzeichnis hat ein [[mapping]] in .convrs.toml)
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath, conflicts_with_all = ["input", "output", "schema"])]
+    input_dir: Option<String>,
+
+    /// Führt alle [[mapping]]-Einträge aus .convrs.toml aus
+    #[arg(long, conflicts_with_all = ["input", "output", "schema", "input_dir", "watch", "check"])]
+    mappings: bool,
+
+    /// Zielverzeichnis für --input-dir, die Verzeichnisstruktur wird gespiegelt
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath, requires = "input_dir")]
+    output_dir: Option<String>,
+
+    /// Nur Dateien, die auf einen Glob passen (relativ zu --input-dir, mehrfach möglich)
+    #[arg(long, value_name = "GLOB", requires = "batch_input")]
+    include: Vec<String>,
+
+    /// Dateien, die auf einen Glob passen, auslassen (mehrfach möglich)
+    #[arg(long, value_name = "GLOB", requires = "batch_input")]
+    exclude: Vec<String>,
+
+    /// Unterverzeichnisse von --input-dir einbeziehen (--recursive=false schaltet es ab)
+    #[arg(short, long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", requires = "batch_input")]
+    recursive: Option<bool>,
+
+    /// .gitignore, .ignore und versteckte Dateien nicht beachten
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", requires = "batch_input")]
+    no_ignore: Option<bool>,
+
+    /// Beobachtet die Eingabe (Datei oder --input-dir) und konvertiert bei jeder Änderung neu
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Konvertiert nur im Speicher und vergleicht mit den bestehenden Ausgabedateien.
+    /// Exit-Code 1 und Diff auf stdout, wenn sie nicht aktuell sind. Schreibt nichts.
+    #[arg(long, conflicts_with_all = ["watch", "input_dir"])]
+    check: bool,
+
+    /// Repariert häufige Syntaxfehler der Eingabe vor dem Konvertieren (Trailing Commas,
+    /// einfache oder typografische Anführungszeichen, Keys ohne Anführungszeichen, Tabs in
+    /// YAML) und meldet jede Reparatur. Die Eingabedatei selbst bleibt unverändert.
+    #[arg(long, conflicts_with_all = ["input_dir", "mappings"])]
+    repair: bool,
+
+    /// Wartezeit in Millisekunden, in der Änderungen für --watch gebündelt werden
+    #[arg(long, default_value_t = 200, requires = "watch")]
+    debounce: u64,
+
+    /// Query-Ausdruck (jq-Subset), der vor der Ausgabe angewendet wird, z.b. '.services[].name'
+    #[arg(long, value_name = "EXPR")]
+    select: Option<Query>,
+
+    /// Validiert die Eingabe vor der Konvertierung gegen ein JSON Schema
+    #[arg(long, value_name = "SCHEMA")]
+    schema: Option<String>,
+
+    /// SQL-Dialekt für .sql-Ausgabe (sqlite, postgres, mysql; Standard: sqlite)
+    #[arg(long)]
+    dialect: Option<SqlDialect>,
+
+    /// Tabellenname für .sql-Ausgabe (Standard: Name der Ausgabedatei)
+    #[arg(long)]
+    table: Option<String>,
+
+    /// Anzahl Zeilen pro INSERT-Statement (Standard: 100)
+    #[arg(long)]
+    batch_size: Option<usize>,
+
+    /// Sheet, das aus einer .xlsx-Eingabe gelesen wird (Standard: erstes Sheet)
+    #[arg(long, conflicts_with = "all_sheets")]
+    sheet: Option<String>,
+
+    /// Liest alle Sheets einer .xlsx-Eingabe in ein Objekt mit dem Sheet-Namen als Key
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    all_sheets: Option<bool>,
+
+    /// Schreibt .plist-Ausgabe im Binärformat statt als XML
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    binary_plist: Option<bool>,
+
+    /// Schreibt RFC-3339-Strings in .plist-Ausgabe als <date> statt als <string>
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    plist_dates: Option<bool>,
+
+    /// Spalten für .fwf-Eingabe als name:start:width[:type], kommagetrennt
+    /// (Start 1-basiert). Ohne Angabe werden die Spalten automatisch erkannt.
+    #[arg(long, value_delimiter = ',')]
+    columns: Vec<FixedWidthColumn>,
+
+    /// Überspringt bei --columns die erste Zeile der .fwf-Eingabe (Kopfzeile)
+    #[arg(long, requires = "columns")]
+    skip_header: bool,
+}
+
+/// Parser für `--from`/`--to`: akzeptiert alles, was `FileFormat::from_str` kennt (auch
+/// Aliase wie `yml`). Die möglichen Werte für `--help` und die statischen Skripte von
+/// `convrs completions` kommen aus `FileFormat::ALL`.
+#[derive(Clone)]
+struct FormatParser {
+    filter: fn(&FileFormat) -> bool,
+}
+
+impl FormatParser {
+    fn readable() -> Self {
+        FormatParser {
+            filter: FileFormat::can_read,
+        }
+    }
+
+    fn writable() -> Self {
+        FormatParser {
+            filter: FileFormat::can_write,
+        }
+    }
+}
+
+impl TypedValueParser for FormatParser {
+    type Value = FileFormat;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<FileFormat, clap::Error> {
+        // fehlermeldung wie bei einem normalen FromStr-parser
+        FileFormat::from_str.parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            FileFormat::ALL
+                .into_iter()
+                .filter(|format| (self.filter)(format))
+                .map(|format| PossibleValue::new(format.as_str())),
+        ))
+    }
+}
+
+#[derive(Subcommand)]
+enum SchemaCommand {
+    /// Leitet ein JSON Schema (Draft 2020-12) aus einer oder mehreren Beispieldateien ab
+    Infer {
+        /// Beispieldateien in beliebigen Formaten, alle müssen das Schema erfüllen
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<String>,
+
+        /// Schema in eine Datei schreiben (Format anhand der Endung), sonst JSON auf stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Maximale Anzahl verschiedener Strings, für die ein enum erzeugt wird (0: nie)
+        #[arg(long, default_value_t = 10)]
+        max_enum: usize,
     },
 }
 
+/// Laufzeit-Completion für `--from` (über `COMPLETE=<shell> convrs`, siehe `CompleteEnv`).
+fn complete_readable(current: &OsStr) -> Vec<CompletionCandidate> {
+    format_candidates(current, FileFormat::can_read)
+}
+
+/// Laufzeit-Completion für `--to`, nach einem Komma wird das nächste Format ergänzt.
+fn complete_writable(current: &OsStr) -> Vec<CompletionCandidate> {
+    format_candidates(current, FileFormat::can_write)
+}
+
+/// Fragt das laufende Binary nach den Formaten, die Skripte veralten also nicht mit
+/// neuen Formaten. Die Hilfe zeigt, ob ein Format nur lesbar oder nur schreibbar ist.
+fn format_candidates(current: &OsStr, filter: fn(&FileFormat) -> bool) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy().to_lowercase();
+    FileFormat::ALL
+        .into_iter()
+        .filter(|format| filter(format) && format.as_str().starts_with(&current))
+        .map(|format| {
+            let help = match (format.can_read(), format.can_write()) {
+                (true, false) => Some("input only"),
+                (false, true) => Some("output only"),
+                _ => None,
+            };
+            CompletionCandidate::new(format.as_str()).help(help.map(Into::into))
+        })
+        .collect()
+}
+
 fn main() {
+    // COMPLETE=<shell> convrs gibt das registrierungs-skript bzw. die kandidaten aus
+    CompleteEnv::with_factory(Cli::command).complete();
+
     let cli = Cli::parse();
+    // farben nur im terminal, NO_COLOR (https://no-color.org) schaltet sie ab
+    let color = std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let reporter = Reporter::new(cli.message_format).with_color(color);
+    let project_config = || {
+        ProjectConfig::load(cli.config.as_deref(), cli.profile.as_deref())
+            .unwrap_or_else(|e| reporter.fail(e))
+    };
 
     match cli.command {
-        Commands::Convert { input, output } => match convert_file(&input, &output) {
-            Ok(_) => println!("✓ Conversion successful: {} -> {}", input, output),
-            Err(e) => {
-                eprintln!("✗ Error: {}", e);
-                std::process::exit(1);
+        Commands::Convert(args) => run_convert(&reporter, *args, &project_config()),
+        Commands::Validate { schema, files } => {
+            let schema = load_schema(&schema).unwrap_or_else(|e| reporter.fail(e));
+
+            for file in &files {
+                match validate_file(file, &schema) {
+                    Ok(violations) if violations.is_empty() => {
+                        reporter.emit(Message::ok("valid", format!("{} is valid", file)).file(file))
+                    }
+                    Ok(violations) => report_violations(&reporter, file, &violations),
+                    Err(e) => reporter.emit(Message::error(&e).file(file)),
+                }
+            }
+            reporter.exit_on_failure();
+        }
+        Commands::Schema {
+            command:
+                SchemaCommand::Infer {
+                    files,
+                    output,
+                    max_enum,
+                },
+        } => {
+            let options = InferOptions {
+                max_enum_values: max_enum,
+            };
+            let schema = infer_schema_files(&files, &options).unwrap_or_else(|e| reporter.fail(e));
+
+            match output {
+                Some(output) => match write_value_file(&output, &schema) {
+                    Ok(_) => reporter.emit(
+                        Message::ok(
+                            "schema_inferred",
+                            format!("Schema inferred: {} -> {}", files.join(" + "), output),
+                        )
+                        .file(&output),
+                    ),
+                    Err(e) => reporter.fail(e),
+                },
+                None => println!("{}", serde_json::to_string_pretty(&schema).unwrap_or_default()),
+            }
+        }
+        Commands::Codegen {
+            files,
+            lang,
+            name,
+            output,
+        } => {
+            let root_name = name.unwrap_or_else(|| {
+                Path::new(&files[0])
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("Root")
+                    .to_string()
+            });
+            let options = CodegenOptions {
+                language: lang,
+                root_name,
+            };
+            let code = codegen_files(&files, &options).unwrap_or_else(|e| reporter.fail(e));
+
+            match output {
+                Some(output) => match std::fs::write(&output, &code) {
+                    Ok(_) => reporter.emit(
+                        Message::ok(
+                            "types_generated",
+                            format!(
+                                "Types generated ({}): {} -> {}",
+                                lang,
+                                files.join(" + "),
+                                output
+                            ),
+                        )
+                        .file(&output),
+                    ),
+                    Err(e) => reporter.fail(FormatError::IoError(format!(
+                        "Error writing to {}: {}",
+                        output, e
+                    ))),
+                },
+                None => print!("{}", code),
+            }
+        }
+        Commands::Merge {
+            inputs,
+            output,
+            arrays,
+            delete_nulls,
+        } => {
+            let options = MergeOptions {
+                arrays,
+                nulls: if delete_nulls {
+                    NullStrategy::Delete
+                } else {
+                    NullStrategy::Set
+                },
+            };
+
+            match merge_files(&inputs, &output, &options) {
+                Ok(_) => reporter.emit(
+                    Message::ok(
+                        "merged",
+                        format!("Merge successful: {} -> {}", inputs.join(" + "), output),
+                    )
+                    .file(&output),
+                ),
+                Err(e) => reporter.fail(e),
+            }
+        }
+        Commands::Diff {
+            left,
+            right,
+            ignore_array_order,
+            format,
+        } => {
+            let options = DiffOptions { ignore_array_order };
+            // wie bei diff(1): 1 heisst "unterschiedlich", fehler haben höhere codes
+            let changes = diff_files(&left, &right, &options).unwrap_or_else(|e| reporter.fail(e));
+
+            if changes.is_empty() && format == DiffFormat::Text {
+                reporter.emit(Message::ok(
+                    "no_differences",
+                    format!("No differences: {} == {}", left, right),
+                ));
+            } else {
+                println!("{}", render_diff(&changes, format));
             }
+            if !changes.is_empty() {
+                std::process::exit(EXIT_FAILED);
+            }
+        }
+        Commands::Patch {
+            target,
+            patch,
+            output,
+            kind,
+        } => match patch_file(&target, &patch, output.as_deref(), kind) {
+            Ok(_) => {
+                let output = output.as_deref().unwrap_or(&target);
+                reporter.emit(
+                    Message::ok("patched", format!("Patch applied: {} -> {}", patch, output))
+                        .file(output),
+                )
+            }
+            Err(e) => reporter.fail(e),
+        },
+        Commands::Fmt {
+            files,
+            check,
+            lint,
+            indent,
+            sort_keys,
+        } => {
+            let config = project_config();
+            // flags haben vorrang vor [style.<format>] in .convrs.toml
+            let options_for = |format: FileFormat| {
+                let mut options = config.fmt_options(format);
+                options.indent = indent.unwrap_or(options.indent);
+                options.sort_keys |= sort_keys;
+                options
+            };
+            run_fmt(&reporter, &files, options_for, check, lint);
+            reporter.exit_on_failure();
+        }
+        Commands::Completions { shell } => {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Cli::command(), "convrs", &mut script);
+            write_stdout(&reporter, &script);
+        }
+        Commands::Man { out_dir: None } => {
+            let mut page = Vec::new();
+            match clap_mangen::Man::new(Cli::command()).render(&mut page) {
+                Ok(_) => write_stdout(&reporter, &page),
+                Err(e) => reporter.fail(FormatError::IoError(format!(
+                    "Error rendering man page: {}",
+                    e
+                ))),
+            }
+        }
+        Commands::Man { out_dir: Some(dir) } => {
+            let result = std::fs::create_dir_all(&dir)
+                .and_then(|_| clap_mangen::generate_to(Cli::command(), &dir));
+            match result {
+                Ok(_) => reporter.emit(
+                    Message::ok("man_pages", format!("Man pages written to {}", dir))
+                        .with("dir", dir.as_str()),
+                ),
+                Err(e) => reporter.fail(FormatError::IoError(format!(
+                    "Error writing man pages to {}: {}",
+                    dir, e
+                ))),
+            }
+        }
+        Commands::Config => {
+            let config = project_config();
+            let message = match (&config.path, &config.profile) {
+                (Some(path), Some(profile)) => Message::ok(
+                    "config",
+                    format!("Using {} (profile {})", path.display(), profile),
+                )
+                .file(path.display().to_string())
+                .with("profile", profile.as_str()),
+                (Some(path), None) => Message::ok("config", format!("Using {}", path.display()))
+                    .file(path.display().to_string()),
+                (None, _) => Message::info(
+                    "config",
+                    format!("No {} found, using defaults", CONFIG_FILE_NAME),
+                ),
+            };
+            reporter.emit(message);
+            match FileFormat::Toml.serialize_value(&config.to_value(), &ConvertOptions::default()) {
+                Ok(rendered) => print!("{}", String::from_utf8_lossy(&rendered)),
+                Err(e) => reporter.fail(e),
+            }
+        }
+        Commands::Get { file, path } => {
+            let result = detect_format(&file, "input")
+                .and_then(|format| Ok((format, get_file_value(&file, &path)?)));
+
+            match result {
+                Ok((format, value)) => println!("{}", render_value(&value, format)),
+                Err(e) => reporter.abort(Message::error(&e).file(&file).path(&path)),
+            }
+        }
+        Commands::Set { file, path, value } => {
+            let parsed = value_from_arg(&value);
+            match set_file_value(&file, &path, &parsed) {
+                Ok(_) => reporter.emit(
+                    Message::ok("set", format!("Updated {}: {} = {}", file, path, value))
+                        .file(&file)
+                        .path(&path)
+                        .with("value", parsed),
+                ),
+                Err(e) => reporter.abort(Message::error(&e).file(&file).path(&path)),
+            }
+        }
+        Commands::Delete { file, path } => match delete_file_value(&file, &path) {
+            Ok(_) => reporter.emit(
+                Message::ok("deleted", format!("Updated {}: deleted {}", file, path))
+                    .file(&file)
+                    .path(&path),
+            ),
+            Err(e) => reporter.abort(Message::error(&e).file(&file).path(&path)),
+        },
+    }
+}
+
+/// Konvertiert eine Datei, stdin/stdout oder (mit --input-dir) ein ganzes Verzeichnis.
+fn run_convert(reporter: &Reporter, args: ConvertArgs, config: &ProjectConfig) {
+    let ConvertArgs {
+        input,
+        output,
+        from,
+        to,
+        input_dir,
+        mappings,
+        output_dir,
+        include,
+        exclude,
+        recursive,
+        no_ignore,
+        watch: watch_mode,
+        check,
+        repair,
+        debounce,
+        select,
+        schema,
+        dialect,
+        table,
+        batch_size,
+        sheet,
+        all_sheets,
+        binary_plist,
+        plist_dates,
+        columns,
+        skip_header,
+    } = args;
+
+    // flags haben vorrang vor .convrs.toml. ohne --table (und ohne table in der datei)
+    // wird der name der jeweiligen ausgabedatei als tabellenname verwendet
+    let defaults = &config.convert;
+    let options = ConvertOptions {
+        sql: SqlOptions {
+            dialect: dialect.unwrap_or(defaults.sql.dialect),
+            table: table.unwrap_or_else(|| defaults.sql.table.clone()),
+            batch_size: batch_size.unwrap_or(defaults.sql.batch_size),
         },
+        xlsx: match (sheet, all_sheets) {
+            (Some(sheet), _) => XlsxOptions {
+                sheet: Some(sheet),
+                all_sheets: false,
+            },
+            (None, Some(true)) => XlsxOptions {
+                sheet: None,
+                all_sheets: true,
+            },
+            (None, Some(false)) => XlsxOptions {
+                all_sheets: false,
+                ..defaults.xlsx.clone()
+            },
+            (None, None) => defaults.xlsx.clone(),
+        },
+        plist: PlistOptions {
+            binary: binary_plist.unwrap_or(defaults.plist.binary),
+            dates: plist_dates.unwrap_or(defaults.plist.dates),
+        },
+        fixed_width: FixedWidthOptions {
+            columns,
+            skip_header,
+        },
+        // includes werden in convert_file_with_options relativ zur eingabedatei aufgelöst
+        hocon: HoconOptions::default(),
+        select,
+    };
+
+    let debounce = Duration::from_millis(debounce);
+    let batch_flags = BatchFlags {
+        from,
+        include,
+        exclude,
+        recursive,
+        no_ignore,
+    };
+
+    if mappings {
+        if config.mappings.is_empty() {
+            reporter.fail(FormatError::InvalidFormat(format!(
+                "No [[mapping]] entries in {}",
+                CONFIG_FILE_NAME
+            )));
+        }
+        for mapping in &config.mappings {
+            let batch = batch_flags.apply(Some(&mapping.batch), mapping.batch.to);
+            run_batch(
+                reporter,
+                &mapping.input_dir,
+                &mapping.output_dir,
+                &batch,
+                &options,
+                None,
+            );
+        }
+        reporter.exit_on_failure();
+        return;
+    }
+
+    if let Some(input_dir) = input_dir {
+        // fehlende angaben kommen aus dem [[mapping]] für dieses verzeichnis
+        let mapping = config.mapping_for(&input_dir);
+        let Some(output_dir) = output_dir.or_else(|| mapping.map(|m| m.output_dir.clone())) else {
+            reporter.fail(FormatError::InvalidFormat(format!(
+                "--input-dir needs --output-dir (or a [[mapping]] in {})",
+                CONFIG_FILE_NAME
+            )));
+        };
+        let to = match (&to[..], mapping) {
+            ([to], _) => *to,
+            ([], Some(mapping)) => mapping.batch.to,
+            _ => reporter.fail(FormatError::InvalidFormat(
+                "--input-dir needs exactly one --to format".to_string(),
+            )),
+        };
+        let batch = batch_flags.apply(mapping.map(|m| &m.batch), to);
+        run_batch(reporter, &input_dir, &output_dir, &batch, &options, None);
+        if watch_mode {
+            // nur die geänderten dateien neu schreiben
+            watch_and_run(reporter, &input_dir, batch.recursive, debounce, |changed| {
+                run_batch(
+                    reporter,
+                    &input_dir,
+                    &output_dir,
+                    &batch,
+                    &options,
+                    Some(changed),
+                );
+            });
+        }
+        reporter.exit_on_failure();
+        return;
+    }
+
+    if watch_mode && input == STDIO_PATH {
+        reporter.fail(FormatError::InvalidFormat(
+            "--watch needs an input file or --input-dir".to_string(),
+        ));
+    }
+    let targets = output_targets(&output, &to, &options).unwrap_or_else(|e| reporter.fail(e));
+    let schema = schema.map(|schema| load_schema(&schema).unwrap_or_else(|e| reporter.fail(e)));
+    let schema = schema.as_ref();
+
+    if check {
+        check_targets(reporter, &input, from, &targets, schema, &options, repair);
+        reporter.exit_on_failure();
+        return;
+    }
+
+    convert_targets(reporter, &input, from, &targets, schema, &options, repair);
+    if watch_mode {
+        watch_and_run(reporter, &input, false, debounce, |_| {
+            convert_targets(reporter, &input, from, &targets, schema, &options, repair);
+        });
+    }
+    reporter.exit_on_failure();
+}
+
+/// Batch-Flags von `convert`, die über `--input-dir` oder einem `[[mapping]]` liegen.
+struct BatchFlags {
+    from: Option<FileFormat>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    recursive: Option<bool>,
+    no_ignore: Option<bool>,
+}
+
+impl BatchFlags {
+    /// Flags haben Vorrang, fehlende Angaben kommen aus `base` (dem Mapping) oder den
+    /// Standardwerten (nicht rekursiv, `.gitignore` beachten).
+    fn apply(&self, base: Option<&BatchOptions>, to: FileFormat) -> BatchOptions {
+        let pick = |flag: &[String], base: Option<&Vec<String>>| match base {
+            Some(base) if flag.is_empty() => base.clone(),
+            _ => flag.to_vec(),
+        };
+        BatchOptions {
+            from: self.from.or(base.and_then(|b| b.from)),
+            to,
+            include: pick(&self.include, base.map(|b| &b.include)),
+            exclude: pick(&self.exclude, base.map(|b| &b.exclude)),
+            recursive: self
+                .recursive
+                .unwrap_or(base.is_some_and(|b| b.recursive)),
+            respect_ignore: self
+                .no_ignore
+                .map(|no_ignore| !no_ignore)
+                .unwrap_or(base.is_none_or(|b| b.respect_ignore)),
+        }
+    }
+}
+
+/// Konvertiert die Eingabe in alle Ziele und meldet jedes Ziel einzeln.
+fn convert_targets(
+    reporter: &Reporter,
+    input: &str,
+    from: Option<FileFormat>,
+    targets: &[OutputTarget],
+    schema: Option<&Schema>,
+    options: &ConvertOptions,
+    repair: bool,
+) {
+    if let [target] = targets
+        && !repair
+    {
+        let result = convert_io(input, &target.path, from, target.format, schema, &target.options);
+        match result {
+            Ok(violations) if violations.is_empty() => reporter.emit(converted(input, target)),
+            Ok(violations) => report_violations(reporter, input, &violations),
+            Err(e) => reporter.emit(Message::error(&e).file(input)),
+        }
+        return;
+    }
+
+    // mehrere ziele (oder reparieren): einmal lesen und parsen, jedes ziel einzeln melden
+    let Some(source) = read_source(reporter, input, from, schema, options, repair) else {
+        return;
+    };
+    let results = match source.convert_to(targets, options) {
+        Ok(results) => results,
+        Err(e) => return reporter.emit(Message::error(&e).file(input)),
+    };
+
+    for (target, result) in targets.iter().zip(results) {
+        match result {
+            Ok(()) => reporter.emit(converted(input, target)),
+            Err(e) => reporter.emit(Message::file_error(&target.path, &e)),
+        }
+    }
+}
+
+/// Erfolgsmeldung für ein geschriebenes Ziel.
+fn converted(input: &str, target: &OutputTarget) -> Message {
+    Message::ok(
+        "converted",
+        format!("Conversion successful: {} -> {}", input, target.path),
+    )
+    .file(input)
+    .with("output", target.path.as_str())
+}
+
+/// Konvertiert im Speicher und vergleicht jedes Ziel mit der bestehenden Datei (`--check`).
+/// Diffs gehen auf stdout (bei `--message-format json` in die Meldung), Meldungen auf stderr.
+fn check_targets(
+    reporter: &Reporter,
+    input: &str,
+    from: Option<FileFormat>,
+    targets: &[OutputTarget],
+    schema: Option<&Schema>,
+    options: &ConvertOptions,
+    repair: bool,
+) {
+    let Some(source) = read_source(reporter, input, from, schema, options, repair) else {
+        return;
+    };
+    // ein einzelnes ziel wird wie bei convert_io direkt konvertiert
+    let rendered = match targets {
+        [target] => Ok(vec![source.render(target)]),
+        _ => source.render_all(targets, options),
+    };
+    let rendered = match rendered {
+        Ok(rendered) => rendered,
+        Err(e) => return reporter.emit(Message::error(&e).file(input)),
+    };
+
+    for (target, expected) in targets.iter().zip(rendered) {
+        let status = expected.and_then(|expected| check_output(target, &expected));
+        let path = &target.path;
+        let message = match status {
+            Ok(CheckStatus::UpToDate) => {
+                Message::ok("up_to_date", format!("{} is up to date", path))
+            }
+            Ok(CheckStatus::Missing) => Message::failed("missing", format!("{} is missing", path)),
+            Ok(CheckStatus::Reformatted) => Message::failed(
+                "out_of_date",
+                format!("{} is out of date (formatting only)", path),
+            )
+            .with("formatting_only", true),
+            Ok(CheckStatus::Changed(changes)) => {
+                let message = Message::failed("out_of_date", format!("{} is out of date", path));
+                match reporter.format() {
+                    MessageFormat::Json => {
+                        let diff = render_diff(&changes, DiffFormat::Json);
+                        let diff = serde_json::from_str(&diff).unwrap_or(JsonValue::String(diff));
+                        message.with("changes", diff)
+                    }
+                    MessageFormat::Human => {
+                        if !changes.is_empty() {
+                            println!("{}", render_diff(&changes, DiffFormat::Text));
+                        }
+                        message
+                    }
+                }
+            }
+            Err(e) => Message::file_error(path, &e),
+        };
+        reporter.emit(message.file(path.as_str()));
+    }
+}
+
+/// Liest die Eingabe, repariert und validiert sie optional. Meldet Fehler und Reparaturen
+/// selbst, `None` bei Fehlern.
+fn read_source(
+    reporter: &Reporter,
+    input: &str,
+    from: Option<FileFormat>,
+    schema: Option<&Schema>,
+    options: &ConvertOptions,
+    repair: bool,
+) -> Option<Input> {
+    let mut source = match Input::read(input, from) {
+        Ok(source) => source,
+        Err(e) => {
+            reporter.emit(Message::error(&e).file(input));
+            return None;
+        }
+    };
+    if repair {
+        match source.repair(options) {
+            Ok(repairs) => {
+                for repair in repairs {
+                    reporter.emit(
+                        Message::info(
+                            "repaired",
+                            format!("Repaired {}:{}: {}", input, repair.location, repair),
+                        )
+                        .file(input)
+                        .location(Some(repair.location))
+                        .with("rule", repair.rule)
+                        .with("repair", repair.message),
+                    );
+                }
+            }
+            Err(e) => {
+                reporter.emit(Message::error(&e).file(input));
+                return None;
+            }
+        }
+    }
+    if let Some(schema) = schema {
+        match source.validate(schema, options) {
+            Ok(violations) if violations.is_empty() => {}
+            Ok(violations) => {
+                report_violations(reporter, input, &violations);
+                return None;
+            }
+            Err(e) => {
+                reporter.emit(Message::error(&e).file(input));
+                return None;
+            }
+        }
+    }
+    Some(source)
+}
+
+/// Beobachtet `path` und ruft `on_change` bei jeder Änderung auf. Fehler beim Konvertieren
+/// beenden den Watcher nicht, nur Fehler des Watchers selbst.
+fn watch_and_run<F>(
+    reporter: &Reporter,
+    path: &str,
+    recursive: bool,
+    debounce: Duration,
+    mut on_change: F,
+) -> !
+where
+    F: FnMut(&[PathBuf]),
+{
+    let target = WatchTarget::new(path, recursive).unwrap_or_else(|e| reporter.fail(e));
+    reporter.emit(
+        Message::info(
+            "watching",
+            format!("Watching {} for changes (Ctrl+C to stop)", path),
+        )
+        .file(path),
+    );
+
+    let result = watch(&target, debounce, |changed| {
+        for path in changed {
+            reporter.emit(
+                Message::info("changed", format!("↻ {} changed", path.display()))
+                    .file(path.display().to_string()),
+            );
+        }
+        on_change(changed);
+    });
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(e) => reporter.fail(e),
+    }
+}
+
+/// Baut die Ausgabeziele aus `-o` und `--to`: ein Format für alle Ausgaben, eines pro
+/// Ausgabe, oder mehrere Formate für ein einzelnes `-o`, dessen Endung dann ersetzt wird.
+fn output_targets(
+    outputs: &[String],
+    to: &[FileFormat],
+    options: &ConvertOptions,
+) -> Result<Vec<OutputTarget>, FormatError> {
+    let stdout = [STDIO_PATH.to_string()];
+    let outputs = if outputs.is_empty() { &stdout[..] } else { outputs };
+
+    match (outputs, to) {
+        ([output], [_, _, ..]) if output != STDIO_PATH => {
+            let base = OutputTarget::parse(output, None, options)?;
+            Ok(to
+                .iter()
+                .map(|format| OutputTarget {
+                    path: Path::new(&base.path)
+                        .with_extension(format.extension())
+                        .to_string_lossy()
+                        .into_owned(),
+                    format: Some(*format),
+                    options: base.options.clone(),
+                })
+                .collect())
+        }
+        (_, [] | [_]) => outputs
+            .iter()
+            .map(|output| OutputTarget::parse(output, to.first().copied(), options))
+            .collect(),
+        _ if outputs.len() == to.len() => outputs
+            .iter()
+            .zip(to)
+            .map(|(output, format)| OutputTarget::parse(output, Some(*format), options))
+            .collect(),
+        _ => Err(FormatError::InvalidFormat(
+            "--to needs a single format, one format per -o, or a single -o as base name".to_string(),
+        )),
+    }
+}
+
+/// Führt eine Batch-Konvertierung aus (mit `changed` nur für diese Dateien) und listet
+/// Erfolge und Fehler auf.
+fn run_batch(
+    reporter: &Reporter,
+    input_dir: &str,
+    output_dir: &str,
+    batch: &BatchOptions,
+    options: &ConvertOptions,
+    changed: Option<&[PathBuf]>,
+) {
+    let report = match changed {
+        Some(changed) => convert_changed(input_dir, output_dir, changed, batch, options),
+        None => convert_dir(input_dir, output_dir, batch, options),
+    };
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => return reporter.emit(Message::error(&e).file(input_dir)),
+    };
+
+    for (input, output) in &report.converted {
+        reporter.emit(
+            Message::ok(
+                "converted",
+                format!("{} -> {}", input.display(), output.display()),
+            )
+            .file(input.display().to_string())
+            .with("output", output.display().to_string()),
+        );
+    }
+    for (input, error) in &report.failed {
+        reporter.emit(Message::file_error(&input.display().to_string(), error));
+    }
+    reporter.emit(
+        Message::info(
+            "summary",
+            format!(
+                "{} converted, {} failed",
+                report.converted.len(),
+                report.failed.len()
+            ),
+        )
+        .with("converted", report.converted.len())
+        .with("failed", report.failed.len()),
+    );
+}
+
+/// Formatiert (oder prüft mit `check`) und lintet Dateien. Nicht kanonisch formatierte
+/// Dateien und Lint-Funde gelten als Fehlschlag.
+fn run_fmt(
+    reporter: &Reporter,
+    files: &[String],
+    options_for: impl Fn(FileFormat) -> FmtOptions,
+    check: bool,
+    lint: bool,
+) {
+    for file in files {
+        let options = detect_format(file, "input")
+            .map(&options_for)
+            .unwrap_or_default();
+        match fmt_file(file, &options, check) {
+            Ok(true) if check => reporter.emit(
+                Message::failed("unformatted", format!("{} would be reformatted", file)).file(file),
+            ),
+            Ok(true) => {
+                reporter.emit(Message::ok("formatted", format!("Formatted {}", file)).file(file))
+            }
+            Ok(false) => {}
+            Err(e) => {
+                reporter.emit(Message::file_error(file, &e));
+                continue;
+            }
+        }
+
+        if lint {
+            match lint_file(file) {
+                Ok(issues) => {
+                    for issue in &issues {
+                        let text = match issue.location {
+                            Some(location) => format!("{}:{}: {}", file, location, issue),
+                            None => format!("{}: {}", file, issue),
+                        };
+                        reporter.emit(
+                            Message::failed("lint", text)
+                                .file(file)
+                                .location(issue.location)
+                                .with("rule", issue.rule)
+                                .with("issue", issue.message.as_str()),
+                        );
+                    }
+                }
+                Err(e) => reporter.emit(Message::file_error(file, &e)),
+            }
+        }
+    }
+}
+
+/// Gibt Schema-Verletzungen im Stil `datei:zeile:spalte: pfad: meldung` aus.
+fn report_violations(reporter: &Reporter, file: &str, violations: &[Violation]) {
+    for violation in violations {
+        let text = match violation.location {
+            Some(location) => format!("{}:{}: {}", file, location, violation),
+            None => format!("{}: {}", file, violation),
+        };
+        reporter.emit(
+            Message::failed("violation", text)
+                .file(file)
+                .path(&violation.path)
+                .location(violation.location)
+                .with("violation", violation.message.as_str()),
+        );
+    }
+    reporter.emit(
+        Message::failed(
+            "violations",
+            format!("{} violation(s) in {}", violations.len(), file),
+        )
+        .file(file)
+        .with("count", violations.len()),
+    );
+}
+
+/// Schreibt erzeugten Text (Completion-Skript, Man-Page) auf stdout.
+fn write_stdout(reporter: &Reporter, content: &[u8]) {
+    let mut stdout = std::io::stdout().lock();
+    if let Err(e) = stdout.write_all(content).and_then(|_| stdout.flush()) {
+        reporter.fail(FormatError::IoError(format!(
+            "Error writing to stdout: {}",
+            e
+        )));
     }
 }
@@ -0,0 +1,469 @@
+//! Ausgabe von Ergebnissen und Fehlern der CLI und Exit-Codes.
+//!
+//! Jede Meldung geht nach stderr, entweder als Zeile für Menschen (`✓ ...`, `✗ ...`)
+//! oder mit `--message-format json` als ein JSON-Objekt pro Zeile. stdout bleibt für
+//! Daten (konvertierte Dateien, `get`, Diffs) reserviert.
+//!
+//! Parse-Fehler mit Position werden für Menschen mit Ausschnitt der Datei angezeigt
+//! (siehe `convrs_core::diagnostic`), in JSON erhalten sie `location` und `help`. Lässt
+//! sich die Datei mit `convert --repair` reparieren, steht das im Hinweis.
+//!
+//! Exit-Codes:
+//!
+//! | Code | Bedeutung                                                        |
+//! |------|------------------------------------------------------------------|
+//! | 0    | Erfolg                                                           |
+//! | 1    | Prüfung fehlgeschlagen (Unterschiede, Schema-Verletzungen, Lint) |
+//! | 2    | Ungültige Argumente                                              |
+//! | 3    | `IoError`: Datei fehlt oder kann nicht geschrieben werden        |
+//! | 4    | `ParseError`: Eingabe ist syntaktisch ungültig                   |
+//! | 5    | `SerializationError`: Zielformat kann den Wert nicht darstellen  |
+//! | 6    | `InvalidFormat`: unbekanntes oder nicht unterstütztes Format     |
+//! | 7    | `PathError`: Pfad im Dokument existiert nicht                    |
+//! | 8    | `UnknownError`                                                   |
+
+use std::cell::Cell;
+use std::fmt;
+use std::str::FromStr;
+
+use convrs_core::diagnostic::{Diagnostic, diagnose};
+use convrs_core::location::Location;
+use convrs_core::repair::repair_bytes;
+use convrs_core::{ConvertOptions, FormatError};
+use serde_json::{Map, Value as JsonValue, json};
+
+use crate::{STDIO_PATH, detect_format};
+
+/// Exit-Code, wenn eine Prüfung fehlschlägt, aber kein Fehler aufgetreten ist.
+pub const EXIT_FAILED: i32 = 1;
+
+/// Exit-Code für einen Fehler, je nach Variante (siehe Tabelle oben).
+pub fn exit_code(error: &FormatError) -> i32 {
+    match error {
+        FormatError::IoError(_) => 3,
+        FormatError::ParseError(_) => 4,
+        FormatError::SerializationError(_) => 5,
+        FormatError::InvalidFormat(_) => 6,
+        FormatError::PathError(_) => 7,
+        FormatError::UnknownError(_) => 8,
+    }
+}
+
+/// Ausgabeformat für Meldungen (`--message-format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for MessageFormat {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown message format: {} (expected human or json)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for MessageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageFormat::Human => write!(f, "human"),
+            MessageFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Ob eine Meldung einen Erfolg, einen Fehlschlag oder nur einen Hinweis darstellt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Failed,
+    Info,
+}
+
+/// Eine Meldung der CLI. `text` ist die Zeile für Menschen (ohne `✓`/`✗`), die übrigen
+/// Felder erscheinen nur in der JSON-Ausgabe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub status: Status,
+    /// Art der Meldung, z.b. `converted`, `violation` oder `error`.
+    pub kind: &'static str,
+    pub text: String,
+    /// Betroffene Datei.
+    pub file: Option<String>,
+    /// Pfad im Dokument, z.b. `.server.port`.
+    pub path: Option<String>,
+    pub location: Option<Location>,
+    pub error: Option<ErrorInfo>,
+    /// Weitere Felder für die JSON-Ausgabe, z.b. `output` oder `rule`.
+    pub data: Map<String, JsonValue>,
+}
+
+/// Variante, Meldung und Exit-Code eines Fehlers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorInfo {
+    pub kind: &'static str,
+    /// Meldung ohne Präfix der Variante.
+    pub message: String,
+    pub exit_code: i32,
+}
+
+impl Message {
+    fn new(status: Status, kind: &'static str, text: impl Into<String>) -> Self {
+        Message {
+            status,
+            kind,
+            text: text.into(),
+            file: None,
+            path: None,
+            location: None,
+            error: None,
+            data: Map::new(),
+        }
+    }
+
+    pub fn ok(kind: &'static str, text: impl Into<String>) -> Self {
+        Message::new(Status::Ok, kind, text)
+    }
+
+    pub fn failed(kind: &'static str, text: impl Into<String>) -> Self {
+        Message::new(Status::Failed, kind, text)
+    }
+
+    pub fn info(kind: &'static str, text: impl Into<String>) -> Self {
+        Message::new(Status::Info, kind, text)
+    }
+
+    /// Ein Fehler, für Menschen als `Error: <fehler>`.
+    pub fn error(error: &FormatError) -> Self {
+        let mut message = Message::failed("error", format!("Error: {}", error));
+        message.error = Some(ErrorInfo {
+            kind: error.kind(),
+            message: error.message().to_string(),
+            exit_code: exit_code(error),
+        });
+        message
+    }
+
+    /// Ein Fehler bei einer bestimmten Datei, für Menschen als `<datei>: <fehler>`.
+    pub fn file_error(file: &str, error: &FormatError) -> Self {
+        let mut message = Message::error(error).file(file);
+        message.text = format!("{}: {}", file, error);
+        message
+    }
+
+    pub fn file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn path(mut self, path: impl fmt::Display) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    pub fn location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<JsonValue>) -> Self {
+        self.data.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Die Zeile für Menschen.
+    pub fn human(&self) -> String {
+        match self.status {
+            Status::Ok => format!("✓ {}", self.text),
+            Status::Failed => format!("✗ {}", self.text),
+            Status::Info => self.text.clone(),
+        }
+    }
+
+    /// Das JSON-Objekt, Felder ohne Wert werden weggelassen.
+    pub fn to_json(&self) -> JsonValue {
+        let status = match self.status {
+            Status::Ok => "ok",
+            Status::Failed => "failed",
+            Status::Info => "info",
+        };
+        let mut object = Map::new();
+        object.insert("type".to_string(), json!(self.kind));
+        object.insert("status".to_string(), json!(status));
+        object.insert("message".to_string(), json!(self.text));
+        if let Some(file) = &self.file {
+            object.insert("file".to_string(), json!(file));
+        }
+        if let Some(path) = &self.path {
+            object.insert("path".to_string(), json!(path));
+        }
+        if let Some(location) = self.location {
+            object.insert(
+                "location".to_string(),
+                json!({ "line": location.line, "column": location.column }),
+            );
+        }
+        if let Some(error) = &self.error {
+            object.insert(
+                "error".to_string(),
+                json!({ "kind": error.kind, "exit_code": error.exit_code }),
+            );
+            object.insert("error_message".to_string(), json!(error.message));
+        }
+        for (key, value) in &self.data {
+            object.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        JsonValue::Object(object)
+    }
+}
+
+/// Gibt Meldungen aus und merkt sich den Exit-Code: der erste Fehler bestimmt ihn,
+/// ohne Fehler ergibt ein Fehlschlag `EXIT_FAILED`.
+#[derive(Debug, Default)]
+pub struct Reporter {
+    format: MessageFormat,
+    color: bool,
+    exit_code: Cell<i32>,
+}
+
+impl Reporter {
+    pub fn new(format: MessageFormat) -> Self {
+        Reporter {
+            format,
+            color: false,
+            exit_code: Cell::new(0),
+        }
+    }
+
+    /// Ausschnitte bei Parse-Fehlern farbig ausgeben (ANSI).
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn format(&self) -> MessageFormat {
+        self.format
+    }
+
+    pub fn emit(&self, mut message: Message) {
+        self.record(&message);
+        let diagnostic = source_diagnostic(&message);
+        match self.format {
+            MessageFormat::Human => match (&message.file, diagnostic) {
+                (Some(file), Some((diagnostic, source))) => {
+                    // die position steht im ausschnitt, nicht mehr in der meldung
+                    if let Some(error) = &message.error {
+                        message.text = message.text.replace(&error.message, &diagnostic.message);
+                    }
+                    let heading = if self.color {
+                        format!("\x1b[1;31m{}\x1b[0m", message.human())
+                    } else {
+                        message.human()
+                    };
+                    eprint!(
+                        "{}\n{}",
+                        heading,
+                        diagnostic.render_frame(file, &source, self.color)
+                    )
+                }
+                _ => eprintln!("{}", message.human()),
+            },
+            MessageFormat::Json => {
+                if let Some((diagnostic, _)) = diagnostic {
+                    message.location = message.location.or(Some(diagnostic.location));
+                    if let Some(help) = diagnostic.help {
+                        message.data.insert("help".to_string(), json!(help));
+                    }
+                }
+                eprintln!("{}", message.to_json())
+            }
+        }
+    }
+
+    /// Gibt einen Fehler aus und beendet das Programm mit seinem Exit-Code.
+    pub fn fail(&self, error: FormatError) -> ! {
+        self.abort(Message::error(&error))
+    }
+
+    /// Gibt eine Fehlermeldung mit Kontext (Datei, Pfad) aus und beendet das Programm.
+    pub fn abort(&self, message: Message) -> ! {
+        self.emit(message);
+        self.exit()
+    }
+
+    /// Bisheriger Exit-Code, 0 wenn nichts fehlgeschlagen ist.
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code.get()
+    }
+
+    /// Beendet das Programm mit dem bisherigen Exit-Code.
+    pub fn exit(&self) -> ! {
+        std::process::exit(self.exit_code())
+    }
+
+    /// Beendet das Programm, wenn etwas fehlgeschlagen ist.
+    pub fn exit_on_failure(&self) {
+        if self.exit_code() != 0 {
+            self.exit();
+        }
+    }
+
+    fn record(&self, message: &Message) {
+        let current = self.exit_code.get();
+        match (&message.error, message.status) {
+            (Some(error), _) if current == 0 || current == EXIT_FAILED => {
+                self.exit_code.set(error.exit_code)
+            }
+            (None, Status::Failed) if current == 0 => self.exit_code.set(EXIT_FAILED),
+            _ => {}
+        }
+    }
+}
+
+// private helper funktionen
+
+/// Diagnose mit Quelltext für Parse-Fehler in einer lesbaren Datei, deren Format sich
+/// an der Endung erkennen lässt.
+fn source_diagnostic(message: &Message) -> Option<(Diagnostic, String)> {
+    let error = message
+        .error
+        .as_ref()
+        .filter(|error| error.kind == "parse")?;
+    let file = message.file.as_deref().filter(|file| *file != STDIO_PATH)?;
+    let format = detect_format(file, "input").ok()?;
+    let source = std::fs::read_to_string(file).ok()?;
+    let mut diagnostic = diagnose(format, &source, &error.message)?;
+
+    // nur vorschlagen, wenn die reparatur das dokument tatsächlich lesbar macht
+    if let Ok(repaired) = repair_bytes(format, source.as_bytes(), &ConvertOptions::default())
+        && !repaired.repairs.is_empty()
+    {
+        let fix = format!(
+            "convert --repair can fix {} problem(s) in this file",
+            repaired.repairs.len()
+        );
+        diagnostic.help = Some(match diagnostic.help {
+            Some(help) => format!("{}; {}", help, fix),
+            None => fix,
+        });
+    }
+    Some((diagnostic, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use convrs_core::KeyPath;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            FormatError::IoError(String::new()),
+            FormatError::ParseError(String::new()),
+            FormatError::SerializationError(String::new()),
+            FormatError::InvalidFormat(String::new()),
+            FormatError::PathError(String::new()),
+            FormatError::UnknownError(String::new()),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(exit_code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes, vec![3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_message_human_and_json() {
+        let message = Message::failed("violation", "config.yaml:3:7: .port: must be a number")
+            .file("config.yaml")
+            .path(".port")
+            .location(Some(Location { line: 3, column: 7 }))
+            .with("rule", "type");
+        assert_eq!(
+            message.human(),
+            "✗ config.yaml:3:7: .port: must be a number"
+        );
+        assert_eq!(
+            message.to_json(),
+            json!({
+                "type": "violation",
+                "status": "failed",
+                "message": "config.yaml:3:7: .port: must be a number",
+                "file": "config.yaml",
+                "path": ".port",
+                "location": {"line": 3, "column": 7},
+                "rule": "type",
+            })
+        );
+
+        let error = FormatError::ParseError("Invalid JSON".to_string());
+        let message = Message::file_error("a.json", &error);
+        assert_eq!(message.human(), "✗ a.json: Parse Error: Invalid JSON");
+        let value = message.to_json();
+        assert_eq!(value["error"], json!({"kind": "parse", "exit_code": 4}));
+        assert_eq!(value["error_message"], "Invalid JSON");
+        assert_eq!(value["file"], "a.json");
+    }
+
+    #[test]
+    fn test_reporter_exit_code() {
+        let reporter = Reporter::new(MessageFormat::Json);
+        reporter.record(&Message::ok("converted", "a -> b"));
+        assert_eq!(reporter.exit_code(), 0);
+
+        reporter.record(&Message::failed("violation", "x"));
+        assert_eq!(reporter.exit_code(), EXIT_FAILED);
+
+        // fehler haben vorrang vor fehlschlägen, der erste fehler gewinnt
+        reporter.record(&Message::error(&FormatError::PathError(String::new())));
+        reporter.record(&Message::error(&FormatError::IoError(String::new())));
+        assert_eq!(reporter.exit_code(), 7);
+    }
+
+    #[test]
+    fn test_source_diagnostic_for_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, "{\n  \"port\": 80,\n}\n").unwrap();
+        let file = path.to_str().unwrap();
+
+        let error = crate::get_file_value(file, &KeyPath::from_segments(Vec::new())).unwrap_err();
+        let (diagnostic, source) = source_diagnostic(&Message::file_error(file, &error)).unwrap();
+        assert_eq!(
+            diagnostic.location,
+            Location {
+                line: 2,
+                column: 13
+            }
+        );
+        assert_eq!(
+            diagnostic.help.as_deref(),
+            Some(
+                "trailing comma not allowed in JSON; convert --repair can fix 1 problem(s) in this file"
+            )
+        );
+        assert!(source.starts_with('{'));
+
+        // ohne datei oder bei anderen fehlern gibt es keinen ausschnitt
+        assert!(source_diagnostic(&Message::error(&error)).is_none());
+        let error = FormatError::IoError("at line 1 column 1".to_string());
+        assert!(source_diagnostic(&Message::file_error(file, &error)).is_none());
+    }
+
+    #[test]
+    fn test_message_format_from_str() {
+        assert_eq!(
+            "JSON".parse::<MessageFormat>().unwrap(),
+            MessageFormat::Json
+        );
+        assert!("xml".parse::<MessageFormat>().is_err());
+    }
+}
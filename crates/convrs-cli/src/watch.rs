@@ -0,0 +1,157 @@
+//! Beobachtet Eingabedateien und -verzeichnisse für `convert --watch`.
+//!
+//! Änderungen werden gebündelt (debounced) gemeldet. Einzelne Dateien werden über ihr
+//! Verzeichnis beobachtet, weil viele Editoren beim Speichern die Datei ersetzen.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+use convrs_core::FormatError;
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+
+/// Ein beobachteter Pfad: eine Datei oder ein Verzeichnis.
+#[derive(Debug, Clone)]
+pub struct WatchTarget {
+    path: PathBuf,
+    is_dir: bool,
+    recursive: bool,
+}
+
+impl WatchTarget {
+    /// `recursive` gilt nur für Verzeichnisse.
+    pub fn new(path: &str, recursive: bool) -> Result<Self, FormatError> {
+        let path = Path::new(path)
+            .canonicalize()
+            .map_err(|e| FormatError::IoError(format!("Cannot watch {}: {}", path, e)))?;
+        Ok(WatchTarget {
+            is_dir: path.is_dir(),
+            path,
+            recursive,
+        })
+    }
+
+    /// Pfad, der beim Dateisystem angemeldet wird.
+    fn watch_path(&self) -> &Path {
+        if self.is_dir {
+            &self.path
+        } else {
+            self.path.parent().unwrap_or(&self.path)
+        }
+    }
+
+    fn mode(&self) -> RecursiveMode {
+        if self.is_dir && self.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        }
+    }
+
+    /// Gehört eine Änderung an `path` zu diesem Ziel?
+    fn matches(&self, path: &Path) -> bool {
+        if self.is_dir {
+            path.starts_with(&self.path)
+        } else {
+            path == self.path
+        }
+    }
+}
+
+/// Ruft `on_change` mit den geänderten (noch existierenden) Dateien auf, sortiert und
+/// ohne Duplikate. Kehrt nur zurück, wenn der Watcher nicht mehr weiterarbeiten kann.
+pub fn watch<F>(
+    target: &WatchTarget,
+    debounce: Duration,
+    mut on_change: F,
+) -> Result<(), FormatError>
+where
+    F: FnMut(&[PathBuf]),
+{
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(debounce, tx).map_err(watch_error)?;
+    debouncer
+        .watcher()
+        .watch(target.watch_path(), target.mode())
+        .map_err(watch_error)?;
+
+    // auch lesezugriffe (z.b. durch die konvertierung selbst) erzeugen events,
+    // deshalb zählt nur eine geänderte modification time
+    let mut modified: HashMap<PathBuf, SystemTime> = HashMap::new();
+
+    for result in rx {
+        let events = result.map_err(watch_error)?;
+        let changed: BTreeSet<PathBuf> = events
+            .into_iter()
+            .map(|event| event.path)
+            .filter(|path| target.matches(path))
+            .filter(|path| match fs::metadata(path).and_then(|m| m.modified()) {
+                Ok(time) if path.is_file() => modified.insert(path.clone(), time) != Some(time),
+                _ => false,
+            })
+            .collect();
+        if !changed.is_empty() {
+            on_change(&changed.into_iter().collect::<Vec<_>>());
+        }
+    }
+
+    Err(FormatError::IoError("File watcher stopped".to_string()))
+}
+
+// private helper funktionen
+
+fn watch_error(error: notify_debouncer_mini::notify::Error) -> FormatError {
+    FormatError::IoError(format!("File watcher error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_watch_target_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.yaml");
+        fs::write(&file, "a: 1\n").unwrap();
+
+        let target = WatchTarget::new(file.to_str().unwrap(), false).unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        assert_eq!(target.watch_path(), root);
+        assert!(target.matches(&root.join("config.yaml")));
+        assert!(!target.matches(&root.join("other.yaml")));
+
+        let target = WatchTarget::new(dir.path().to_str().unwrap(), true).unwrap();
+        assert!(target.matches(&root.join("sub/other.yaml")));
+        assert!(WatchTarget::new("does-not-exist.yaml", false).is_err());
+    }
+
+    #[test]
+    fn test_watch_reports_changed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.yaml");
+        fs::write(&file, "a: 1\n").unwrap();
+        let target = WatchTarget::new(file.to_str().unwrap(), false).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = watch(&target, Duration::from_millis(50), |changed| {
+                let _ = tx.send(changed.to_vec());
+            });
+        });
+
+        // dem watcher zeit zum anmelden geben, dann schreiben bis eine meldung kommt
+        for _ in 0..50 {
+            fs::write(dir.path().join("ignored.yaml"), "b: 2\n").unwrap();
+            fs::write(&file, "a: 2\n").unwrap();
+            if let Ok(changed) = rx.recv_timeout(Duration::from_millis(200)) {
+                assert_eq!(changed, vec![file.canonicalize().unwrap()]);
+                return;
+            }
+        }
+        panic!("no change reported");
+    }
+}
@@ -118,3 +118,1133 @@ fn cli_error_on_nonexistent_file() {
 
     assert!(!output.status.success());
 }
+
+#[test]
+fn cli_convert_csv_to_sql_with_dialect() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("users.csv");
+    let output_path = dir.path().join("users.sql");
+
+    fs::write(&input_path, "name,age\nAlice,30\nBob,25").unwrap();
+
+    let output = convrs_bin()
+        .arg("convert")
+        .arg("-i")
+        .arg(input_path.to_str().unwrap())
+        .arg("-o")
+        .arg(output_path.to_str().unwrap())
+        .arg("--dialect")
+        .arg("postgres")
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result = fs::read_to_string(&output_path).unwrap();
+    assert!(result.contains("CREATE TABLE \"users\""));
+    assert!(result.contains("\"age\" BIGINT"));
+    assert!(result.contains("(25, 'Bob')"));
+}
+
+#[test]
+fn cli_convert_json_to_xlsx_and_back() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("data.json");
+    let xlsx_path = dir.path().join("data.xlsx");
+    let output_path = dir.path().join("back.json");
+
+    fs::write(&input_path, r#"[{"name":"Alice","age":30}]"#).unwrap();
+
+    let to_xlsx = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", xlsx_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(to_xlsx.status.success());
+
+    let from_xlsx = convrs_bin()
+        .args(["convert", "-i", xlsx_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .args(["--sheet", "Sheet1"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(from_xlsx.status.success());
+
+    let result = fs::read_to_string(&output_path).unwrap();
+    assert!(result.contains("\"age\": 30"));
+    assert!(result.contains("Alice"));
+}
+
+#[test]
+fn cli_convert_yaml_to_binary_plist() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("Info.yaml");
+    let output_path = dir.path().join("Info.plist");
+
+    fs::write(&input_path, "CFBundleName: Demo\nBuild: 42").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .arg("--binary-plist")
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result = fs::read(&output_path).unwrap();
+    assert!(result.starts_with(b"bplist00"));
+}
+
+#[test]
+fn cli_convert_logfmt_to_jsonl() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("app.logfmt");
+    let output_path = dir.path().join("app.jsonl");
+
+    fs::write(&input_path, "level=info msg=\"started server\" port=8080\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(result, "{\"level\":\"info\",\"msg\":\"started server\",\"port\":8080}\n");
+}
+
+#[test]
+fn cli_convert_fixed_width_with_columns() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("export.fwf");
+    let output_path = dir.path().join("export.json");
+
+    fs::write(&input_path, "00042ALICE     0001050\n00043BOB       0000020\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .args(["--columns", "id:1:5:integer,name:6:10,cents:16:7:integer"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result = fs::read_to_string(&output_path).unwrap();
+    assert!(result.contains("\"id\": 42"));
+    assert!(result.contains("\"name\": \"ALICE\""));
+    assert!(result.contains("\"cents\": 1050"));
+}
+
+#[test]
+fn cli_convert_hocon_with_include_to_hcl() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("application.conf");
+    let output_path = dir.path().join("application.tfvars");
+
+    // includes werden relativ zur eingabedatei aufgelöst
+    fs::write(dir.path().join("defaults.conf"), "server { host = localhost, port = 80 }\n").unwrap();
+    fs::write(
+        &input_path,
+        "include \"defaults.conf\"\nserver.port = 8080\nurl = \"http://\"${server.host}\n",
+    )
+    .unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result = fs::read_to_string(&output_path).unwrap();
+    assert!(result.contains("server {"));
+    assert!(result.contains("port = 8080"));
+    assert!(result.contains("url = \"http://localhost\""));
+}
+
+#[test]
+fn cli_convert_with_select() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("stack.yaml");
+    let output_path = dir.path().join("names.json");
+
+    fs::write(
+        &input_path,
+        "services:\n  - name: web\n    port: 8080\n  - name: db\n    port: 5432\n",
+    )
+    .unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .args(["--select", ".services[] | select(.port > 6000) | .name"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+    // select(...) ergibt immer ein array, auch bei einem treffer
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), "[\n  \"web\"\n]");
+}
+
+#[test]
+fn cli_convert_with_invalid_select_fails() {
+    let output = convrs_bin()
+        .args(["convert", "-i", "in.json", "-o", "out.yaml", "--select", "services"])
+        .output()
+        .expect("Failed to run convrs");
+
+    // syntaxfehler fängt schon clap ab
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid query"));
+
+    // ein pfad, der nicht zu den daten passt, ist ein pfad-fehler
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("in.json");
+    fs::write(&input_path, r#"{"a": {"b": 1}}"#).unwrap();
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", dir.path().join("out.yaml").to_str().unwrap()])
+        .args(["--select", ".a.b.c"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert_eq!(output.status.code(), Some(7));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Query error: cannot apply key \"c\" to number"));
+    assert!(!stderr.contains("Invalid Format"));
+}
+
+#[test]
+fn cli_get_set_delete_toml_keeps_comments() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let file = dir.path().join("config.toml");
+    let file_str = file.to_str().unwrap();
+
+    fs::write(
+        &file,
+        "# service config\n[server]\nhost = \"localhost\" # dev only\nport = 8080\ndebug = true\n",
+    )
+    .unwrap();
+
+    let output = convrs_bin()
+        .args(["get", file_str, "server.port"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "8080\n");
+
+    let output = convrs_bin()
+        .args(["set", file_str, ".server.host", "example.com"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let output = convrs_bin()
+        .args(["delete", file_str, "server.debug"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "# service config\n[server]\nhost = \"example.com\" # dev only\nport = 8080\n"
+    );
+}
+
+#[test]
+fn cli_set_json_value_types() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let file = dir.path().join("package.json");
+    let file_str = file.to_str().unwrap();
+
+    fs::write(&file, r#"{"name": "demo", "version": "1.0.0"}"#).unwrap();
+
+    let output = convrs_bin()
+        .args(["set", file_str, "scripts.ports", "[80, 443]"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let result = fs::read_to_string(&file).unwrap();
+    assert!(result.find("\"name\"").unwrap() < result.find("\"version\"").unwrap());
+    assert!(result.contains("\"scripts\": {\n    \"ports\": [\n      80,\n      443\n    ]"));
+}
+
+#[test]
+fn cli_get_missing_path_fails() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let file = dir.path().join("app.yaml");
+    fs::write(&file, "name: demo\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["get", file.to_str().unwrap(), "server.port"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'.server.port' not found"));
+}
+
+#[test]
+fn cli_merge_across_formats() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let base = dir.path().join("base.yaml");
+    let env = dir.path().join("env.toml");
+    let local = dir.path().join("local.json");
+    let output_path = dir.path().join("out.json");
+
+    fs::write(&base, "db:\n  host: localhost\n  port: 5432\nplugins: [auth]\ndebug: true\n").unwrap();
+    fs::write(&env, "plugins = [\"metrics\"]\n\n[db]\nhost = \"db.prod\"\n").unwrap();
+    fs::write(&local, r#"{"db": {"port": 6543}, "debug": null}"#).unwrap();
+
+    let output = convrs_bin()
+        .arg("merge")
+        .args([base.to_str().unwrap(), env.to_str().unwrap(), local.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .args(["--arrays", "append", "--delete-nulls"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+
+    let result: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!({
+            "db": {"host": "db.prod", "port": 6543},
+            "plugins": ["auth", "metrics"]
+        })
+    );
+}
+
+#[test]
+fn cli_diff_across_formats() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let left = dir.path().join("config.json");
+    let right = dir.path().join("config.yaml");
+
+    fs::write(&left, r#"{"name": "app", "db": {"port": 5432}, "tags": ["a", "b"]}"#).unwrap();
+    fs::write(&right, "tags: [b, a]\ndb:\n  port: 5432\nname: app\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["diff", left.to_str().unwrap(), right.to_str().unwrap()])
+        .arg("--ignore-array-order")
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(0));
+
+    fs::write(&right, "tags: [a, b]\ndb:\n  port: 6543\n").unwrap();
+    let output = convrs_bin()
+        .args(["diff", left.to_str().unwrap(), right.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "~ .db.port: 5432 -> 6543\n- .name: \"app\"\n");
+
+    let output = convrs_bin()
+        .args(["diff", left.to_str().unwrap(), right.to_str().unwrap()])
+        .args(["--format", "patch"])
+        .output()
+        .expect("Failed to run convrs");
+    let patch: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        patch,
+        serde_json::json!([
+            {"op": "replace", "path": "/db/port", "value": 6543},
+            {"op": "remove", "path": "/name"}
+        ])
+    );
+
+    let output = convrs_bin()
+        .args(["diff", left.to_str().unwrap(), "missing.yaml"])
+        .output()
+        .expect("Failed to run convrs");
+    // fehlende datei: IoError
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn cli_patch_yaml_and_toml() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let target = dir.path().join("config.toml");
+    let json_patch = dir.path().join("change.json");
+    let merge_patch = dir.path().join("override.yaml");
+    let output_path = dir.path().join("out.json");
+
+    fs::write(&target, "name = \"app\"\ntags = [\"a\"]\n\n[db]\nport = 5432\n").unwrap();
+    fs::write(
+        &json_patch,
+        r#"[{"op": "replace", "path": "/db/port", "value": 6543}, {"op": "add", "path": "/tags/-", "value": "b"}]"#,
+    )
+    .unwrap();
+    fs::write(&merge_patch, "name: null\ndb:\n  host: db.prod\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["patch", target.to_str().unwrap(), json_patch.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&target).unwrap().contains("port = 6543"));
+
+    let output = convrs_bin()
+        .args(["patch", target.to_str().unwrap(), merge_patch.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let result: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!({"db": {"host": "db.prod", "port": 6543}, "tags": ["a", "b"]})
+    );
+}
+
+#[test]
+fn cli_patch_reports_failed_operation() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let target = dir.path().join("config.yaml");
+    let patch = dir.path().join("change.json");
+
+    fs::write(&target, "a: 1\n").unwrap();
+    fs::write(&patch, r#"[{"op": "remove", "path": "/b/c"}]"#).unwrap();
+
+    let output = convrs_bin()
+        .args(["patch", target.to_str().unwrap(), patch.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("operation 1 (remove) failed at '/b/c'"));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "a: 1\n");
+}
+
+#[test]
+fn cli_validate_reports_violations_with_location() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let schema = dir.path().join("schema.yaml");
+    let valid = dir.path().join("valid.json");
+    let invalid = dir.path().join("invalid.toml");
+
+    fs::write(
+        &schema,
+        "type: object\nrequired: [name]\nproperties:\n  name: {type: string}\n  port: {type: integer}\n",
+    )
+    .unwrap();
+    fs::write(&valid, r#"{"name": "app", "port": 80}"#).unwrap();
+    fs::write(&invalid, "# config\nport = \"eighty\"\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["validate", "--schema", schema.to_str().unwrap()])
+        .arg(valid.to_str().unwrap())
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let output = convrs_bin()
+        .args(["validate", "-s", schema.to_str().unwrap()])
+        .args([valid.to_str().unwrap(), invalid.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid.toml:2:1: .port: \"eighty\" is not of type \"integer\""));
+    assert!(stderr.contains("\"name\" is a required property"));
+    assert!(stderr.contains("2 violation(s)"));
+}
+
+#[test]
+fn cli_convert_with_schema_validation() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let schema = dir.path().join("schema.json");
+    let input = dir.path().join("input.yaml");
+    let output_path = dir.path().join("output.json");
+
+    fs::write(&schema, r#"{"type": "object", "required": ["name"]}"#).unwrap();
+    fs::write(&input, "port: 80\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input.to_str().unwrap(), "-o", output_path.to_str().unwrap()])
+        .args(["--schema", schema.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("required property"));
+    assert!(!output_path.exists());
+}
+
+#[test]
+fn cli_schema_infer_round_trip() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let first = dir.path().join("a.yaml");
+    let second = dir.path().join("b.toml");
+    let schema_path = dir.path().join("schema.json");
+
+    fs::write(&first, "name: web\nport: 80\nadmin: ann@example.com\n").unwrap();
+    fs::write(&second, "name = \"db\"\nport = 5432\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["schema", "infer", first.to_str().unwrap(), second.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["required"], serde_json::json!(["name", "port"]));
+    assert_eq!(schema["properties"]["admin"]["format"], "email");
+
+    let output = convrs_bin()
+        .args(["schema", "infer", first.to_str().unwrap()])
+        .args(["-o", schema_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+
+    let output = convrs_bin()
+        .args(["validate", "--schema", schema_path.to_str().unwrap()])
+        .arg(second.to_str().unwrap())
+        .output()
+        .expect("Failed to run convrs");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("\"admin\" is a required property"));
+}
+
+#[test]
+fn cli_codegen_languages() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let first = dir.path().join("app-config.yaml");
+    let second = dir.path().join("other.json");
+    let output_path = dir.path().join("types.go");
+
+    fs::write(&first, "name: web\nserver:\n  port: 80\n").unwrap();
+    fs::write(&second, r#"{"name": "db", "server": {"port": 5432, "tls": true}}"#).unwrap();
+
+    let output = convrs_bin()
+        .args(["codegen", "--lang", "rust"])
+        .args([first.to_str().unwrap(), second.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    let code = String::from_utf8_lossy(&output.stdout);
+    assert!(code.contains("pub struct AppConfig {"));
+    assert!(code.contains("pub server: Server,"));
+    assert!(code.contains("pub tls: Option<bool>,"));
+
+    let output = convrs_bin()
+        .args(["codegen", "--lang", "ts", "--name", "Settings"])
+        .arg(first.to_str().unwrap())
+        .output()
+        .expect("Failed to run convrs");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("export interface Settings {"));
+
+    let output = convrs_bin()
+        .args(["codegen", "--lang", "go", "-o", output_path.to_str().unwrap()])
+        .arg(second.to_str().unwrap())
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&output_path).unwrap().contains("type Other struct {"));
+
+    let output = convrs_bin()
+        .args(["codegen", "--lang", "kotlin", first.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cli_convert_stdin_to_stdout() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let run = |args: &[&str], input: &str| {
+        let mut child = convrs_bin()
+            .arg("convert")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to run convrs");
+        // bei fehlern vor dem lesen schliesst convrs stdin, daher kein unwrap
+        let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+        child.wait_with_output().unwrap()
+    };
+
+    // format von stdin wird am inhalt erkannt, stdout enthält nur daten
+    let output = run(&["--to", "yaml"], r#"{"name":"Alice","age":30}"#);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "age: 30\nname: Alice\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("✓ Conversion successful"));
+
+    // --from überschreibt die erkennung, -i/-o - explizit
+    let output = run(&["-i", "-", "-o", "-", "--from", "csv", "--to", "json"], "a,b\n1,x\n");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value, serde_json::json!([{"a": 1, "b": "x"}]));
+
+    // stdout ohne --to
+    let output = run(&[], "a: 1\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("use --to"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn cli_convert_from_overrides_extension() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("settings.txt");
+    fs::write(&input_path, "[server]\nport = 80\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap(), "--from", "toml", "--to", "json"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["server"]["port"], 80);
+}
+
+#[test]
+fn cli_convert_directory() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_dir = dir.path().join("configs");
+    let output_dir = dir.path().join("out");
+    fs::create_dir_all(input_dir.join("services")).unwrap();
+    fs::write(input_dir.join("app.json"), r#"{"name": "app"}"#).unwrap();
+    fs::write(input_dir.join("services/db.toml"), "port = 5432\n").unwrap();
+    fs::write(input_dir.join("services/broken.json"), "{").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "--input-dir", input_dir.to_str().unwrap()])
+        .args(["--output-dir", output_dir.to_str().unwrap(), "--to", "yaml", "-r"])
+        .output()
+        .expect("Failed to run convrs");
+
+    // ein fehler stoppt die anderen dateien nicht, aber der exit-code meldet ihn
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("2 converted, 1 failed"));
+    assert!(stderr.contains("broken.json"));
+    assert_eq!(fs::read_to_string(output_dir.join("app.yaml")).unwrap(), "name: app\n");
+    assert_eq!(
+        fs::read_to_string(output_dir.join("services/db.yaml")).unwrap(),
+        "port: 5432\n"
+    );
+
+    let output = convrs_bin()
+        .args(["convert", "--input-dir", input_dir.to_str().unwrap()])
+        .args(["--output-dir", output_dir.to_str().unwrap(), "--to", "json"])
+        .args(["-r", "--exclude", "**/broken.json"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(output_dir.join("services/db.json").exists());
+}
+
+#[test]
+fn cli_convert_multiple_outputs() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("users.csv");
+    fs::write(&input_path, "id,name\n1,ann\n2,bob\n").unwrap();
+    let base = dir.path().join("users");
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", base.to_str().unwrap(), "--to", "json,yaml,sql"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(dir.path().join("users.json").exists());
+    assert!(dir.path().join("users.yaml").exists());
+    let sql = fs::read_to_string(dir.path().join("users.sql")).unwrap();
+    assert!(sql.contains("CREATE TABLE \"users\""));
+
+    // eigene optionen pro ziel, fehler werden pro ziel gemeldet
+    let sql_path = dir.path().join("people.sql");
+    let fwf_path = dir.path().join("people.fwf");
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", &format!("{}:dialect=mysql,table=people", sql_path.display())])
+        .args(["-o", fwf_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(6));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("✓ Conversion successful"));
+    assert!(stderr.contains("people.fwf: Invalid Format"));
+    assert!(fs::read_to_string(&sql_path).unwrap().contains("CREATE TABLE `people`"));
+}
+
+#[test]
+fn cli_watch_requires_input_file() {
+    let output = convrs_bin()
+        .args(["convert", "--watch", "--to", "json"])
+        .output()
+        .expect("Failed to run convrs");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--watch needs an input file"));
+}
+
+#[test]
+fn cli_convert_check_mode() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input_path = dir.path().join("config.yaml");
+    let output_path = dir.path().join("config.json");
+    fs::write(&input_path, "name: app\nport: 80\n").unwrap();
+
+    let check = || {
+        convrs_bin()
+            .args(["convert", "-i", input_path.to_str().unwrap()])
+            .args(["-o", output_path.to_str().unwrap(), "--check"])
+            .output()
+            .expect("Failed to run convrs")
+    };
+
+    // fehlende ausgabe, nichts wird geschrieben
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is missing"));
+    assert!(!output_path.exists());
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input_path.to_str().unwrap()])
+        .args(["-o", output_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(check().status.success());
+
+    fs::write(&output_path, r#"{"name": "app", "port": 8080}"#).unwrap();
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "~ .port: 8080 -> 80\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        r#"{"name": "app", "port": 8080}"#
+    );
+}
+
+#[test]
+fn cli_fmt_check_write_and_lint() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let json_path = dir.path().join("data.json");
+    let yaml_path = dir.path().join("ci.yaml");
+    fs::write(&json_path, r#"{"b":1,"a":2}"#).unwrap();
+    fs::write(&yaml_path, "on: push\nname: ci\n").unwrap();
+
+    let fmt = |args: &[&str]| {
+        convrs_bin()
+            .arg("fmt")
+            .args(args)
+            .output()
+            .expect("Failed to run convrs")
+    };
+
+    let output = fmt(&["--check", json_path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would be reformatted"));
+    assert_eq!(fs::read_to_string(&json_path).unwrap(), r#"{"b":1,"a":2}"#);
+
+    let output = fmt(&["--sort-keys", json_path.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&json_path).unwrap(),
+        "{\n  \"a\": 2,\n  \"b\": 1\n}\n"
+    );
+    assert!(
+        fmt(&["--check", "--sort-keys", json_path.to_str().unwrap()])
+            .status
+            .success()
+    );
+
+    // die yaml-datei ist schon kanonisch, hat aber einen lint-fund
+    let output = fmt(&["--check", "--lint", yaml_path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("ci.yaml:1:1: [yaml-ambiguous-bool]"));
+    assert!(!stderr.contains("would be reformatted"));
+}
+
+#[test]
+fn cli_fmt_refuses_yaml_with_comments() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let yaml_path = dir.path().join("config.yaml");
+    let content = "# database settings\nhost:   localhost # primary\nbase: &b {x: 1}\nother: *b\n";
+    fs::write(&yaml_path, content).unwrap();
+
+    for check in [true, false] {
+        let output = convrs_bin()
+            .arg("fmt")
+            .args(check.then_some("--check"))
+            .arg(yaml_path.to_str().unwrap())
+            .output()
+            .expect("Failed to run convrs");
+
+        assert_eq!(output.status.code(), Some(6));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("comment at line 1"));
+        assert!(!stderr.contains("Formatted"));
+        assert_eq!(fs::read_to_string(&yaml_path).unwrap(), content);
+    }
+}
+
+#[test]
+fn cli_project_config_defaults_profiles_and_mappings() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let nested = dir.path().join("sub");
+    fs::create_dir_all(dir.path().join("data")).unwrap();
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(
+        dir.path().join(".convrs.toml"),
+        "[convert]\ndialect = \"postgres\"\n\n[[mapping]]\ninput-dir = \"data\"\noutput-dir = \"build\"\nto = \"yaml\"\n\n[profile.mysql.convert]\ndialect = \"mysql\"\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("data/a.json"), r#"{"a": 1}"#).unwrap();
+    fs::write(nested.join("rows.json"), r#"[{"flag": true}]"#).unwrap();
+
+    // die datei wird vom arbeitsverzeichnis aus aufwärts gefunden
+    let convert = |args: &[&str]| {
+        convrs_bin()
+            .current_dir(&nested)
+            .args(args)
+            .args(["convert", "-i", "rows.json", "-o", "rows.sql"])
+            .output()
+            .expect("Failed to run convrs")
+    };
+    assert!(convert(&[]).status.success());
+    let sql = fs::read_to_string(nested.join("rows.sql")).unwrap();
+    assert!(sql.contains("TRUE") && sql.contains("\"rows\""));
+
+    assert!(convert(&["--profile", "mysql"]).status.success());
+    let sql = fs::read_to_string(nested.join("rows.sql")).unwrap();
+    assert!(sql.contains("`rows`"));
+
+    // flags haben vorrang vor der datei
+    let output = convrs_bin()
+        .current_dir(&nested)
+        .args(["convert", "-i", "rows.json", "-o", "rows.sql"])
+        .args(["--dialect", "sqlite"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    let sql = fs::read_to_string(nested.join("rows.sql")).unwrap();
+    assert!(sql.contains("(1);"));
+
+    let output = convrs_bin()
+        .current_dir(dir.path())
+        .args(["convert", "--mappings"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("build/a.yaml")).unwrap(),
+        "a: 1\n"
+    );
+
+    let output = convrs_bin()
+        .current_dir(&nested)
+        .args(["--profile", "mysql", "config"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("dialect = \"mysql\""));
+    assert!(stdout.contains("[style.json]"));
+
+    let output = convrs_bin()
+        .current_dir(&nested)
+        .args(["--profile", "prod", "config"])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown profile 'prod'"));
+}
+
+#[test]
+fn cli_flags_override_project_config() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    fs::create_dir_all(dir.path().join("data/sub")).unwrap();
+    fs::write(
+        dir.path().join(".convrs.toml"),
+        "[convert]\nbinary-plist = true\n\n[[mapping]]\ninput-dir = \"data\"\noutput-dir = \"build\"\nto = \"yaml\"\nrecursive = true\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("in.json"), r#"{"a": 1}"#).unwrap();
+    fs::write(dir.path().join("data/a.json"), r#"{"a": 1}"#).unwrap();
+    fs::write(dir.path().join("data/skip.json"), r#"{"s": 1}"#).unwrap();
+    fs::write(dir.path().join("data/sub/b.json"), r#"{"b": 2}"#).unwrap();
+
+    let convert = |args: &[&str]| {
+        convrs_bin()
+            .current_dir(dir.path())
+            .arg("convert")
+            .args(args)
+            .output()
+            .expect("Failed to run convrs")
+    };
+
+    assert!(convert(&["-i", "in.json", "-o", "bin.plist"]).status.success());
+    assert!(fs::read(dir.path().join("bin.plist")).unwrap().starts_with(b"bplist00"));
+
+    // --binary-plist=false schaltet den wert aus der datei wieder ab
+    let output = convert(&["-i", "in.json", "-o", "xml.plist", "--binary-plist=false"]);
+    assert!(output.status.success());
+    assert!(fs::read(dir.path().join("xml.plist")).unwrap().starts_with(b"<?xml"));
+
+    // batch-flags gelten auch für jedes [[mapping]]
+    let output = convert(&["--mappings", "--recursive=false", "--exclude", "skip.json"]);
+    assert!(output.status.success());
+    assert!(dir.path().join("build/a.yaml").exists());
+    assert!(!dir.path().join("build/skip.yaml").exists());
+    assert!(!dir.path().join("build/sub/b.yaml").exists());
+}
+
+#[test]
+fn cli_dynamic_completion_of_formats() {
+    let complete = |args: &[&str]| {
+        let output = convrs_bin()
+            .env("COMPLETE", "fish")
+            .args(["--", "convrs", "convert"])
+            .args(args)
+            .output()
+            .expect("Failed to run convrs");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let candidates = complete(&["--from", "fi"]);
+    assert_eq!(candidates.trim(), "fixed-width\tinput only");
+
+    // nach dem komma wird das nächste format ergänzt, nur schreibbare formate
+    let candidates = complete(&["--to", "json,s"]);
+    assert_eq!(candidates.trim(), "json,sql\toutput only");
+    assert!(!complete(&["--to", "h"]).contains("hocon"));
+
+    // das registrierungs-skript ruft das binary zur laufzeit auf
+    let output = convrs_bin()
+        .env("COMPLETE", "bash")
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("COMPLETE=\"bash\""));
+}
+
+#[test]
+fn cli_completions_and_man_pages() {
+    let output = convrs_bin()
+        .args(["completions", "fish"])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    let script = String::from_utf8_lossy(&output.stdout);
+    // formatnamen kommen aus FileFormat, --to bietet nur schreibbare formate an
+    let start = script
+        .find("subcommand convert\" -l to ")
+        .expect("no completion for --to");
+    let to_values = &script[start..];
+    let to_values = &to_values[..to_values.find("\ncomplete -c").unwrap_or(to_values.len())];
+    assert!(to_values.contains("jsonl") && to_values.contains("sql"));
+    assert!(!to_values.contains("fixed-width") && !to_values.contains("fwf"));
+    assert!(script.contains("fixed-width") && script.contains("hocon"));
+
+    for shell in ["bash", "zsh", "powershell"] {
+        let output = convrs_bin()
+            .args(["completions", shell])
+            .output()
+            .expect("Failed to run convrs");
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains("convrs"));
+    }
+
+    let output = convrs_bin()
+        .arg("man")
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(".TH convrs 1"));
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let out_dir = dir.path().join("man");
+    let output = convrs_bin()
+        .args(["man", "--out-dir", out_dir.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert!(out_dir.join("convrs.1").exists());
+    assert!(out_dir.join("convrs-convert.1").exists());
+}
+
+#[test]
+fn cli_exit_codes_and_json_messages() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let broken = dir.path().join("broken.json");
+    let config = dir.path().join("config.json");
+    let schema = dir.path().join("schema.json");
+    fs::write(&broken, "{\"name\": ").unwrap();
+    fs::write(&config, "{\n  \"name\": \"app\",\n  \"port\": \"80\"\n}\n").unwrap();
+    fs::write(
+        &schema,
+        r#"{"type": "object", "properties": {"port": {"type": "integer"}}}"#,
+    )
+    .unwrap();
+
+    // jede FormatError-variante hat ihren eigenen exit-code
+    for (args, code) in [
+        (vec!["convert", "-i", "missing.json", "--to", "yaml"], 3),
+        (
+            vec!["convert", "-i", broken.to_str().unwrap(), "--to", "yaml"],
+            4,
+        ),
+        (
+            vec!["convert", "-i", config.to_str().unwrap(), "--to", "fwf"],
+            6,
+        ),
+        (vec!["get", config.to_str().unwrap(), ".server.port"], 7),
+    ] {
+        let output = convrs_bin()
+            .args(&args)
+            .output()
+            .expect("Failed to run convrs");
+        assert_eq!(output.status.code(), Some(code), "{:?}", args);
+    }
+
+    let output = convrs_bin()
+        .args(["--message-format", "json", "convert", "-i"])
+        .args([broken.to_str().unwrap(), "--to", "yaml"])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(message["type"], "error");
+    assert_eq!(message["error"]["kind"], "parse");
+    assert_eq!(message["error"]["exit_code"], 4);
+    assert_eq!(message["file"], broken.to_str().unwrap());
+
+    // eine zeile pro meldung, verletzungen mit pfad und position
+    let output = convrs_bin()
+        .args(["validate", "--message-format", "json", "-s"])
+        .args([schema.to_str().unwrap(), config.to_str().unwrap()])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(1));
+    let messages: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["type"], "violation");
+    assert_eq!(messages[0]["status"], "failed");
+    assert_eq!(messages[0]["path"], ".port");
+    assert_eq!(messages[0]["location"]["line"], 3);
+    assert_eq!(messages[1]["type"], "violations");
+    assert_eq!(messages[1]["count"], 1);
+}
+
+#[test]
+fn cli_parse_error_shows_source_frame() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input = dir.path().join("config.json");
+    fs::write(&input, "{\n  \"name\": \"app\",\n  \"port\": 80,\n}\n").unwrap();
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input.to_str().unwrap(), "--to", "yaml"])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(4));
+
+    // stderr ist kein terminal, also ohne farben
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("✗ Error: Parse Error: Invalid JSON: trailing comma\n"));
+    assert!(stderr.contains("config.json:3:13\n"));
+    assert!(stderr.contains("3 |   \"port\": 80,\n  |             ^\n"));
+    assert!(stderr.contains("= help: trailing comma not allowed in JSON"));
+    assert!(!stderr.contains('\x1b'));
+}
+
+#[test]
+fn cli_convert_repair() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input = dir.path().join("config.json");
+    let original = "{\n  name: 'app',\n  “port”: 80,\n}\n";
+    fs::write(&input, original).unwrap();
+
+    // ohne --repair gibt es den fehler mit dem hinweis auf --repair
+    let output = convrs_bin()
+        .args(["convert", "-i", input.to_str().unwrap(), "--to", "yaml"])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(4));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("convert --repair can fix 4 problem(s) in this file")
+    );
+
+    let output = convrs_bin()
+        .args(["convert", "-i", input.to_str().unwrap(), "--to", "yaml"])
+        .arg("--repair")
+        .output()
+        .expect("Failed to run convrs");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "name: app\nport: 80\n"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("config.json:2:3: [unquoted-key] quoted key 'name'"));
+    assert!(stderr.contains("config.json:2:9: [single-quotes]"));
+    assert!(stderr.contains("config.json:3:3: [smart-quotes]"));
+    assert!(stderr.contains("config.json:3:13: [trailing-comma] removed trailing comma"));
+    // die eingabedatei bleibt unverändert
+    assert_eq!(fs::read_to_string(&input).unwrap(), original);
+
+    let output = convrs_bin()
+        .args(["convert", "--input-dir", dir.path().to_str().unwrap()])
+        .args(["--output-dir", "out", "--to", "yaml", "--repair"])
+        .output()
+        .expect("Failed to run convrs");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_unknown_or_missing_extension_is_invalid_format() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let xml = dir.path().join("in.xml");
+    let noext = dir.path().join("noext");
+    fs::write(&xml, "<a/>").unwrap();
+    fs::write(&noext, "{}").unwrap();
+    let output_path = dir.path().join("out.json");
+
+    // kein syntaxfehler, sondern ein nicht unterstütztes format
+    for input in [&xml, &noext] {
+        let output = convrs_bin()
+            .args(["--message-format", "json", "convert", "-i"])
+            .args([input.to_str().unwrap(), "-o", output_path.to_str().unwrap()])
+            .output()
+            .expect("Failed to run convrs");
+        assert_eq!(output.status.code(), Some(6));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+        assert_eq!(message["error"]["kind"], "invalid_format");
+        assert_eq!(message["error"]["exit_code"], 6);
+    }
+}
@@ -0,0 +1,586 @@
+//! Erzeugt Typdefinitionen (Rust, TypeScript, Go) aus Beispieldaten.
+//!
+//! Das Typmodell wird über `infer::infer_schema` abgeleitet: Keys, die nicht in allen
+//! Objekten vorkommen, werden optional, `null` macht einen Typ nullable. Verschachtelte
+//! Objekte bekommen eigene Typen, benannt nach ihrem Pfad (`server.tls` → `ServerTls`).
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::infer::{InferOptions, infer_schema};
+
+/// Zielsprache für `generate_types`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Structs mit `serde`-Derives und `#[serde(rename)]` wo nötig.
+    Rust,
+    /// `export interface` mit optionalen Properties (`key?:`).
+    TypeScript,
+    /// Structs mit `json`-Tags, optionale Felder als Pointer mit `omitempty`.
+    Go,
+}
+
+impl FromStr for Language {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rust" | "rs" => Ok(Language::Rust),
+            "typescript" | "ts" => Ok(Language::TypeScript),
+            "go" | "golang" => Ok(Language::Go),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown language '{}', expected rust, typescript or go",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::Rust => write!(f, "rust"),
+            Language::TypeScript => write!(f, "typescript"),
+            Language::Go => write!(f, "go"),
+        }
+    }
+}
+
+/// Optionen für `generate_types`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenOptions {
+    pub language: Language,
+    /// Name des Wurzeltyps, z.b. `Config`.
+    pub root_name: String,
+}
+
+/// Erzeugt Typdefinitionen, die alle `samples` abbilden.
+pub fn generate_types<'a, I>(samples: I, options: &CodegenOptions) -> String
+where
+    I: IntoIterator<Item = &'a JsonValue>,
+{
+    // enums werden nicht als eigene typen erzeugt
+    let schema = infer_schema(samples, &InferOptions { max_enum_values: 0 });
+    generate_types_from_schema(&schema, options)
+}
+
+/// Erzeugt Typdefinitionen aus einem (abgeleiteten) JSON Schema.
+pub fn generate_types_from_schema(schema: &JsonValue, options: &CodegenOptions) -> String {
+    let mut model = Model::default();
+    let root_name = pascal_case(&options.root_name);
+    let root = model.build(schema, &root_name, "");
+
+    match options.language {
+        Language::Rust => render_rust(&model, &root, &root_name),
+        Language::TypeScript => render_typescript(&model, &root, &root_name),
+        Language::Go => render_go(&model, &root, &root_name),
+    }
+}
+
+/// Macht aus einem Dateinamen oder Key einen Typnamen, z.b. `app-config` → `AppConfig`.
+pub fn pascal_case(name: &str) -> String {
+    let mut result = String::new();
+    for word in split_words(name) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    if result.is_empty() {
+        return "Root".to_string();
+    }
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, 'T');
+    }
+    result
+}
+
+// private helper funktionen
+
+#[derive(Debug, Clone, PartialEq)]
+enum TypeRef {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// beliebiger wert (gemischte typen oder nur `null`)
+    Any,
+    /// objekt ohne bekannte keys
+    Map,
+    Array(Box<TypeRef>),
+    Struct(String),
+    /// mehrere typen, nur TypeScript kann sie direkt abbilden
+    Union(Vec<TypeRef>),
+    Nullable(Box<TypeRef>),
+}
+
+struct Field {
+    key: String,
+    ty: TypeRef,
+    optional: bool,
+}
+
+struct StructDef {
+    name: String,
+    fields: Vec<Field>,
+}
+
+#[derive(Default)]
+struct Model {
+    structs: Vec<StructDef>,
+    names: HashSet<String>,
+}
+
+impl Model {
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while self.names.contains(&candidate) {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        self.names.insert(candidate.clone());
+        candidate
+    }
+
+    /// `name` ist der Typname für ein Objekt an dieser Stelle, `prefix` der Pfad-Präfix
+    /// für verschachtelte Typen (leer an der Wurzel).
+    fn build(&mut self, schema: &JsonValue, name: &str, prefix: &str) -> TypeRef {
+        let types: Vec<&str> = match schema.get("type") {
+            Some(JsonValue::String(t)) => vec![t.as_str()],
+            Some(JsonValue::Array(ts)) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        let nullable = types.contains(&"null");
+
+        let variants: Vec<TypeRef> = types
+            .iter()
+            .filter(|t| **t != "null")
+            .map(|t| match *t {
+                "string" => TypeRef::String,
+                "integer" => TypeRef::Integer,
+                "number" => TypeRef::Number,
+                "boolean" => TypeRef::Boolean,
+                "array" => {
+                    let item_name = format!("{}Item", name);
+                    let item = match schema.get("items") {
+                        Some(items) => self.build(items, &item_name, &item_name),
+                        None => TypeRef::Any,
+                    };
+                    TypeRef::Array(Box::new(item))
+                }
+                "object" => self.build_struct(schema, name, prefix),
+                _ => TypeRef::Any,
+            })
+            .collect();
+
+        let ty = match variants.len() {
+            0 => return TypeRef::Any,
+            1 => variants.into_iter().next().unwrap_or(TypeRef::Any),
+            _ => TypeRef::Union(variants),
+        };
+        if nullable {
+            TypeRef::Nullable(Box::new(ty))
+        } else {
+            ty
+        }
+    }
+
+    fn build_struct(&mut self, schema: &JsonValue, name: &str, prefix: &str) -> TypeRef {
+        let properties = match schema.get("properties") {
+            Some(JsonValue::Object(props)) if !props.is_empty() => props,
+            _ => return TypeRef::Map,
+        };
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|k| k.as_str()).collect())
+            .unwrap_or_default();
+
+        // platz reservieren, damit der übergeordnete typ vor den verschachtelten steht
+        let name = self.unique_name(name);
+        let index = self.structs.len();
+        self.structs.push(StructDef {
+            name: name.clone(),
+            fields: Vec::new(),
+        });
+
+        let fields = properties
+            .iter()
+            .map(|(key, child)| {
+                let child_name = format!("{}{}", prefix, pascal_case(key));
+                let child_prefix = child_name.clone();
+                Field {
+                    key: key.clone(),
+                    ty: self.build(child, &child_name, &child_prefix),
+                    optional: !required.contains(key.as_str()),
+                }
+            })
+            .collect();
+
+        self.structs[index].fields = fields;
+        TypeRef::Struct(name)
+    }
+}
+
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        // camelCase-grenze: `maxConn` → `max`, `Conn`
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(name: &str) -> String {
+    let result = split_words(name)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if result.is_empty() {
+        return "field".to_string();
+    }
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("_{}", result);
+    }
+    result
+}
+
+/// Gleiche Feldnamen nach der Umwandlung werden durchnummeriert.
+fn unique_field_names(fields: &[Field], convert: impl Fn(&str) -> String) -> Vec<String> {
+    let mut seen = HashSet::new();
+    fields
+        .iter()
+        .map(|field| {
+            let base = convert(&field.key);
+            let mut candidate = base.clone();
+            let mut counter = 2;
+            while !seen.insert(candidate.clone()) {
+                candidate = format!("{}{}", base, counter);
+                counter += 1;
+            }
+            candidate
+        })
+        .collect()
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+fn rust_field_name(key: &str) -> String {
+    let name = snake_case(key);
+    match name.as_str() {
+        // diese keywords sind auch als raw identifier nicht erlaubt
+        "self" | "super" | "crate" => format!("{}_", name),
+        _ if RUST_KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+fn rust_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "i64".to_string(),
+        TypeRef::Number => "f64".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Any | TypeRef::Union(_) => "serde_json::Value".to_string(),
+        TypeRef::Map => "serde_json::Map<String, serde_json::Value>".to_string(),
+        TypeRef::Array(item) => format!("Vec<{}>", rust_type(item)),
+        TypeRef::Struct(name) => name.clone(),
+        // serde_json::Value kann null schon darstellen
+        TypeRef::Nullable(inner) if matches!(**inner, TypeRef::Any | TypeRef::Union(_)) => {
+            rust_type(inner)
+        }
+        TypeRef::Nullable(inner) => format!("Option<{}>", rust_type(inner)),
+    }
+}
+
+fn render_rust(model: &Model, root: &TypeRef, root_name: &str) -> String {
+    let mut out = String::from("use serde::{Deserialize, Serialize};\n");
+
+    // wurzel ist kein objekt: alias auf den typ
+    if !matches!(root, TypeRef::Struct(name) if name == root_name) {
+        out.push_str(&format!(
+            "\npub type {} = {};\n",
+            root_name,
+            rust_type(root)
+        ));
+    }
+
+    for def in &model.structs {
+        out.push_str("\n#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        out.push_str(&format!("pub struct {} {{\n", def.name));
+        let names = unique_field_names(&def.fields, rust_field_name);
+        for (field, name) in def.fields.iter().zip(names) {
+            let mut attrs = Vec::new();
+            if name.trim_start_matches("r#") != field.key {
+                attrs.push(format!("rename = {:?}", field.key));
+            }
+            let ty = match (&field.ty, field.optional) {
+                (TypeRef::Nullable(_), true) => {
+                    attrs.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+                    rust_type(&field.ty)
+                }
+                (ty, true) => {
+                    attrs.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+                    format!("Option<{}>", rust_type(ty))
+                }
+                (ty, false) => rust_type(ty),
+            };
+            if !attrs.is_empty() {
+                out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+            }
+            out.push_str(&format!("    pub {}: {},\n", name, ty));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn ts_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "string".to_string(),
+        TypeRef::Integer | TypeRef::Number => "number".to_string(),
+        TypeRef::Boolean => "boolean".to_string(),
+        TypeRef::Any => "unknown".to_string(),
+        TypeRef::Map => "Record<string, unknown>".to_string(),
+        TypeRef::Array(item) => match **item {
+            TypeRef::Union(_) | TypeRef::Nullable(_) => format!("({})[]", ts_type(item)),
+            _ => format!("{}[]", ts_type(item)),
+        },
+        TypeRef::Struct(name) => name.clone(),
+        TypeRef::Union(variants) => {
+            let mut parts: Vec<String> = Vec::new();
+            for part in variants.iter().map(ts_type) {
+                if !parts.contains(&part) {
+                    parts.push(part);
+                }
+            }
+            parts.join(" | ")
+        }
+        TypeRef::Nullable(inner) => format!("{} | null", ts_type(inner)),
+    }
+}
+
+fn ts_property_name(key: &str) -> String {
+    let valid = key.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if valid {
+        key.to_string()
+    } else {
+        JsonValue::String(key.to_string()).to_string()
+    }
+}
+
+fn render_typescript(model: &Model, root: &TypeRef, root_name: &str) -> String {
+    let mut blocks = Vec::new();
+
+    if !matches!(root, TypeRef::Struct(name) if name == root_name) {
+        blocks.push(format!("export type {} = {};\n", root_name, ts_type(root)));
+    }
+
+    for def in &model.structs {
+        let mut block = format!("export interface {} {{\n", def.name);
+        for field in &def.fields {
+            let optional = if field.optional { "?" } else { "" };
+            block.push_str(&format!(
+                "  {}{}: {};\n",
+                ts_property_name(&field.key),
+                optional,
+                ts_type(&field.ty)
+            ));
+        }
+        block.push_str("}\n");
+        blocks.push(block);
+    }
+    blocks.join("\n")
+}
+
+fn go_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "string".to_string(),
+        TypeRef::Integer => "int64".to_string(),
+        TypeRef::Number => "float64".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Any | TypeRef::Union(_) => "any".to_string(),
+        TypeRef::Map => "map[string]any".to_string(),
+        TypeRef::Array(item) => format!("[]{}", go_type(item)),
+        TypeRef::Struct(name) => name.clone(),
+        TypeRef::Nullable(inner) => go_pointer(inner),
+    }
+}
+
+/// Slices, Maps und `any` können schon `nil` sein und brauchen keinen Pointer.
+fn go_pointer(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::Array(_) | TypeRef::Map | TypeRef::Any | TypeRef::Union(_) => go_type(ty),
+        TypeRef::Nullable(inner) => go_pointer(inner),
+        other => format!("*{}", go_type(other)),
+    }
+}
+
+fn render_go(model: &Model, root: &TypeRef, root_name: &str) -> String {
+    let mut out = String::from("// Code generated by convrs. DO NOT EDIT.\n\npackage main\n");
+
+    if !matches!(root, TypeRef::Struct(name) if name == root_name) {
+        out.push_str(&format!("\ntype {} {}\n", root_name, go_type(root)));
+    }
+
+    for def in &model.structs {
+        out.push_str(&format!("\ntype {} struct {{\n", def.name));
+        let names = unique_field_names(&def.fields, pascal_case);
+        for (field, name) in def.fields.iter().zip(names) {
+            let (ty, tag) = if field.optional {
+                (go_pointer(&field.ty), format!("{},omitempty", field.key))
+            } else {
+                (go_type(&field.ty), field.key.clone())
+            };
+            out.push_str(&format!("\t{} {} `json:\"{}\"`\n", name, ty, tag));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn generate(samples: &[JsonValue], language: Language) -> String {
+        generate_types(
+            samples,
+            &CodegenOptions {
+                language,
+                root_name: "config".to_string(),
+            },
+        )
+    }
+
+    fn samples() -> Vec<JsonValue> {
+        vec![
+            json!({
+                "name": "web",
+                "maxConnections": 100,
+                "type": "http",
+                "server": {"host": "localhost", "tls": {"cert": "a.pem"}},
+                "services": [{"name": "api", "port": 80}, {"name": "db"}],
+                "owner": null
+            }),
+            json!({
+                "name": "db",
+                "maxConnections": 5,
+                "type": "tcp",
+                "server": {"host": "db", "tls": {"cert": "b.pem"}},
+                "services": [],
+                "owner": "ann",
+                "ratio": 0.5
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_generate_rust() {
+        let code = generate(&samples(), Language::Rust);
+        assert!(code.starts_with("use serde::{Deserialize, Serialize};\n"));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct Config {\n"
+        ));
+        assert!(code.contains(
+            "    #[serde(rename = \"maxConnections\")]\n    pub max_connections: i64,\n"
+        ));
+        assert!(code.contains("    pub r#type: String,\n"));
+        assert!(code.contains("    pub owner: Option<String>,\n"));
+        assert!(code.contains(
+            "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub ratio: Option<f64>,\n"
+        ));
+        assert!(code.contains("    pub server: Server,\n"));
+        assert!(code.contains("pub struct ServerTls {\n    pub cert: String,\n}"));
+        assert!(code.contains("    pub services: Vec<ServicesItem>,\n"));
+        assert!(code.contains("    pub port: Option<i64>,\n"));
+        // wurzeltyp zuerst, verschachtelte typen danach
+        assert!(code.find("struct Config").unwrap() < code.find("struct Server").unwrap());
+    }
+
+    #[test]
+    fn test_generate_typescript() {
+        let code = generate(&samples(), Language::TypeScript);
+        assert!(code.contains("export interface Config {\n"));
+        assert!(code.contains("  maxConnections: number;\n"));
+        assert!(code.contains("  owner: string | null;\n"));
+        assert!(code.contains("  ratio?: number;\n"));
+        assert!(code.contains("  services: ServicesItem[];\n"));
+        assert!(
+            code.contains("export interface ServicesItem {\n  name: string;\n  port?: number;\n}")
+        );
+    }
+
+    #[test]
+    fn test_generate_go() {
+        let code = generate(&samples(), Language::Go);
+        assert!(code.contains("package main\n"));
+        assert!(code.contains("type Config struct {\n"));
+        assert!(code.contains("\tMaxConnections int64 `json:\"maxConnections\"`\n"));
+        assert!(code.contains("\tOwner *string `json:\"owner\"`\n"));
+        assert!(code.contains("\tRatio *float64 `json:\"ratio,omitempty\"`\n"));
+        assert!(code.contains("\tServer Server `json:\"server\"`\n"));
+        assert!(code.contains("\tTls ServerTls `json:\"tls\"`\n"));
+    }
+
+    #[test]
+    fn test_generate_root_array_and_odd_keys() {
+        let samples = [json!([{"meta data": 1, "8080": true, "mixed": 1}, {"mixed": "x"}])];
+
+        let rust = generate(&samples, Language::Rust);
+        assert!(rust.contains("pub type Config = Vec<ConfigItem>;\n"));
+        assert!(rust.contains("    #[serde(rename = \"meta data\", default, skip_serializing_if = \"Option::is_none\")]\n    pub meta_data: Option<i64>,\n"));
+        assert!(rust.contains("    pub _8080: Option<bool>,\n"));
+        assert!(rust.contains("    pub mixed: serde_json::Value,\n"));
+
+        let ts = generate(&samples, Language::TypeScript);
+        assert!(ts.contains("export type Config = ConfigItem[];\n"));
+        assert!(ts.contains("  \"meta data\"?: number;\n"));
+        assert!(ts.contains("  mixed: string | number;\n"));
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(pascal_case("app-config"), "AppConfig");
+        assert_eq!(pascal_case("maxConnections"), "MaxConnections");
+        assert_eq!(pascal_case("8080"), "T8080");
+        assert_eq!(snake_case("maxConnections"), "max_connections");
+        assert_eq!(snake_case("HTTP Port"), "http_port");
+        assert_eq!(rust_field_name("self"), "self_");
+        assert!("kotlin".parse::<Language>().is_err());
+    }
+}
@@ -0,0 +1,404 @@
+//! Diagnosen für Parse-Fehler mit Quelltext-Ausschnitt im Stil von rustc.
+//!
+//! Die Parser liefern ihre Fehler als Text, meist mit `at line 3 column 5`. Daraus wird
+//! die Position gelesen, der betroffene Ausschnitt angezeigt und, wo möglich, ein Hinweis
+//! zur Behebung ergänzt:
+//!
+//! ```text
+//! error: Invalid JSON: trailing comma
+//!  --> config.json:3:13
+//!   |
+//! 2 |   "name": "app",
+//! 3 |   "port": 80,
+//!   |             ^
+//!   |
+//!   = help: trailing comma not allowed in JSON
+//! ```
+
+use crate::format::FileFormat;
+use crate::location::{Location, offset_to_location};
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const CYAN: &str = "\x1b[1;36m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Ein Fehler mit Position im Quelltext.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Fehlermeldung ohne Positionsangabe.
+    pub message: String,
+    pub location: Location,
+    /// Text neben dem `^`, z.b. Details des Parsers.
+    pub label: Option<String>,
+    /// Hinweis zur Behebung.
+    pub help: Option<String>,
+}
+
+/// Baut eine Diagnose aus einer Fehlermeldung des Parsers. `None`, wenn die Meldung
+/// keine Position enthält.
+pub fn diagnose(format: FileFormat, source: &str, message: &str) -> Option<Diagnostic> {
+    let mut lines = message.lines();
+    let first = lines.next()?;
+    let (mut location, span) = find_position(first)?;
+
+    let mut header = format!("{}{}", &first[..span.0], &first[span.1..]);
+    header.truncate(header.trim_end().len());
+    // toml_edit hängt einen eigenen ausschnitt an, übrig bleiben die details
+    let details: Vec<&str> = lines
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_frame_line(line))
+        .collect();
+    let label = (!details.is_empty()).then(|| details.join("; "));
+
+    // JSON Lines nennt die zeile des datensatzes, die position von serde_json gilt darin
+    if let Some(record) = first
+        .strip_prefix("Invalid JSON Lines: line ")
+        .and_then(|rest| rest.split(':').next())
+        .and_then(|record| record.parse::<usize>().ok())
+    {
+        location.line += record - 1;
+    }
+
+    let offset = location_to_offset(source, location);
+    let help = hint(format, message, source, offset);
+
+    // serde_json zeigt hinter das komma, der fehler ist das komma selbst
+    if matches!(format, FileFormat::Json | FileFormat::Jsonl) && header.contains("trailing comma") {
+        let before = source[..offset].trim_end();
+        if before.ends_with(',') {
+            location = offset_to_location(source, before.len() - 1);
+        }
+    }
+
+    Some(Diagnostic {
+        message: header,
+        location,
+        label,
+        help,
+    })
+}
+
+impl Diagnostic {
+    /// Rendert die Diagnose mit Überschrift `error: <meldung>` und Ausschnitt. Mit `color`
+    /// werden ANSI-Farben verwendet.
+    pub fn render(&self, file: &str, source: &str, color: bool) -> String {
+        format!(
+            "{}{}\n{}",
+            paint(RED, "error", color),
+            paint(BOLD, &format!(": {}", self.message), color),
+            self.render_frame(file, source, color)
+        )
+    }
+
+    /// Nur der Ausschnitt ab `-->`, mit einer Zeile Kontext vor der Fehlerzeile.
+    pub fn render_frame(&self, file: &str, source: &str, color: bool) -> String {
+        let paint = |code: &str, text: &str| paint(code, text, color);
+
+        let lines: Vec<&str> = source.lines().collect();
+        // eine position hinter dem letzten zeilenumbruch zeigt auf die letzte zeile
+        let line = self.location.line.clamp(1, lines.len().max(1));
+        let width = line.to_string().len();
+        let gutter = paint(BLUE, &format!("{:width$} |", ""));
+
+        let mut out = format!(
+            "{:width$}{} {}:{}:{}\n",
+            "",
+            paint(BLUE, "-->"),
+            file,
+            line,
+            self.location.column
+        );
+        out.push_str(&format!("{}\n", gutter));
+
+        for number in line.saturating_sub(1).max(1)..=line {
+            let text = lines.get(number - 1).copied().unwrap_or("");
+            out.push_str(&format!(
+                "{} {}\n",
+                paint(BLUE, &format!("{:>width$} |", number)),
+                text
+            ));
+        }
+
+        // einrückung mit tabs bleibt erhalten, damit das ^ unter der richtigen spalte steht
+        let text = lines.get(line - 1).copied().unwrap_or("");
+        let indent: String = text
+            .chars()
+            .take(self.location.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let marker = match &self.label {
+            Some(label) => format!("^ {}", label),
+            None => "^".to_string(),
+        };
+        out.push_str(&format!("{} {}{}\n", gutter, indent, paint(RED, &marker)));
+
+        if let Some(help) = &self.help {
+            out.push_str(&format!("{}\n", gutter));
+            out.push_str(&format!(
+                "{:width$} {} {}\n",
+                "",
+                paint(BLUE, "="),
+                paint(CYAN, &format!("help: {}", help))
+            ));
+        }
+        out
+    }
+}
+
+// private helper funktionen
+
+fn paint(code: &str, text: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", code, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Findet `line N column M` (serde_json, serde_yaml) oder `line N, column M` (toml) und
+/// liefert die Position sowie den Bereich, der aus der Meldung entfernt wird.
+fn find_position(message: &str) -> Option<(Location, (usize, usize))> {
+    let mut search = 0;
+    while let Some(found) = message[search..].find("line ") {
+        let start = search + found;
+        search = start + 5;
+
+        let rest = &message[search..];
+        let line_len = rest.chars().take_while(char::is_ascii_digit).count();
+        let Ok(line) = rest[..line_len].parse() else {
+            continue;
+        };
+        let rest = rest[line_len..].trim_start_matches(',');
+        let Some(rest) = rest.strip_prefix(" column ") else {
+            continue;
+        };
+        let column_len = rest.chars().take_while(char::is_ascii_digit).count();
+        let Ok(column) = rest[..column_len].parse() else {
+            continue;
+        };
+
+        let end = message.len() - rest.len() + column_len;
+        let start = match message[..start].strip_suffix(" at ") {
+            Some(before) => before.len(),
+            None => start,
+        };
+        return Some((Location { line, column }, (start, end)));
+    }
+    None
+}
+
+/// Zeilen eines Ausschnitts wie `  |` oder `2 | b = `.
+fn is_frame_line(line: &str) -> bool {
+    let before_bar = line.split('|').next().unwrap_or("");
+    line.contains('|') && before_bar.trim().chars().all(|c| c.is_ascii_digit())
+}
+
+/// Hinweise für häufige Fehler, anhand der Meldung und des Zeichens an der Position.
+fn hint(format: FileFormat, message: &str, source: &str, offset: usize) -> Option<String> {
+    let at = source[offset..].chars().next();
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = source[line_start..].lines().next().unwrap_or("");
+
+    if at.is_some_and(|c| matches!(c, '“' | '”' | '„' | '‘' | '’')) {
+        return Some("typographic quotes are not string delimiters, use \" or '".to_string());
+    }
+
+    let help = match format {
+        FileFormat::Json | FileFormat::Jsonl => {
+            if message.contains("trailing comma") {
+                "trailing comma not allowed in JSON"
+            } else if at == Some('\'') {
+                "JSON strings and keys need double quotes"
+            } else if matches!(at, Some('/' | '#')) {
+                "JSON does not allow comments"
+            } else if message.contains("key must be a string") {
+                "object keys must be quoted in JSON"
+            } else if message.contains("EOF while parsing") {
+                "the document ends before all brackets or strings are closed"
+            } else if message.contains("expected `,`") {
+                "entries must be separated by ','"
+            } else {
+                return None;
+            }
+        }
+        FileFormat::Yaml => {
+            if line.trim_start_matches(' ').starts_with('\t') {
+                "YAML does not allow tabs for indentation, use spaces"
+            } else if message.contains("mapping values are not allowed") {
+                "check the indentation, or quote values that contain ': '"
+            } else if message.contains("did not find expected key") {
+                "check the indentation of this line"
+            } else {
+                return None;
+            }
+        }
+        FileFormat::Toml => {
+            if message.contains("duplicate key") {
+                "each key can only be defined once per table"
+            } else if message.contains("invalid string") {
+                "string values must be quoted"
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    Some(help.to_string())
+}
+
+/// Byte-Offset einer Position, Spalten zählen Zeichen. Positionen hinter dem Ende der
+/// Zeile oder des Textes werden auf das Ende begrenzt.
+fn location_to_offset(source: &str, location: Location) -> usize {
+    let mut offset = 0;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        if i + 1 == location.line {
+            let content = line.trim_end_matches(['\n', '\r']);
+            return offset
+                + content
+                    .char_indices()
+                    .nth(location.column.saturating_sub(1))
+                    .map_or(content.len(), |(i, _)| i);
+        }
+        offset += line.len();
+    }
+    source.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConvertOptions;
+
+    fn parse_error(format: FileFormat, source: &str) -> String {
+        format
+            .parse_bytes(source.as_bytes(), &ConvertOptions::default())
+            .unwrap_err()
+            .message()
+            .to_string()
+    }
+
+    #[test]
+    fn test_json_trailing_comma_points_at_comma() {
+        let source = "{\n  \"name\": \"app\",\n  \"port\": 80,\n}\n";
+        let diagnostic = diagnose(
+            FileFormat::Json,
+            source,
+            &parse_error(FileFormat::Json, source),
+        )
+        .unwrap();
+        assert_eq!(diagnostic.message, "Invalid JSON: trailing comma");
+        assert_eq!(
+            diagnostic.location,
+            Location {
+                line: 3,
+                column: 13
+            }
+        );
+        assert_eq!(
+            diagnostic.render("config.json", source, false),
+            "error: Invalid JSON: trailing comma\n \
+             --> config.json:3:13\n  \
+             |\n\
+             2 |   \"name\": \"app\",\n\
+             3 |   \"port\": 80,\n  \
+             |             ^\n  \
+             |\n  \
+             = help: trailing comma not allowed in JSON\n"
+        );
+    }
+
+    #[test]
+    fn test_json_hints() {
+        let hint_for = |source: &str| {
+            diagnose(
+                FileFormat::Json,
+                source,
+                &parse_error(FileFormat::Json, source),
+            )
+            .and_then(|diagnostic| diagnostic.help)
+        };
+        assert_eq!(
+            hint_for("{'a': 1}").as_deref(),
+            Some("JSON strings and keys need double quotes")
+        );
+        assert_eq!(
+            hint_for("{\"a\": 1 // port\n}").as_deref(),
+            Some("JSON does not allow comments")
+        );
+        assert_eq!(
+            hint_for("{“a”: 1}").as_deref(),
+            Some("typographic quotes are not string delimiters, use \" or '")
+        );
+        assert_eq!(
+            hint_for("{\"a\": [1, 2}").as_deref(),
+            Some("entries must be separated by ','")
+        );
+    }
+
+    #[test]
+    fn test_yaml_and_toml() {
+        let source = "a: 1\n b: 2\n";
+        let diagnostic = diagnose(
+            FileFormat::Yaml,
+            source,
+            &parse_error(FileFormat::Yaml, source),
+        )
+        .unwrap();
+        assert_eq!(diagnostic.location, Location { line: 2, column: 3 });
+        assert!(diagnostic.help.unwrap().contains("indentation"));
+
+        // den ausschnitt von toml_edit ersetzt der eigene, die details werden zum label
+        let source = "name = app\n";
+        let diagnostic = diagnose(
+            FileFormat::Toml,
+            source,
+            &parse_error(FileFormat::Toml, source),
+        )
+        .unwrap();
+        assert!(!diagnostic.message.contains("line"));
+        assert_eq!(diagnostic.location, Location { line: 1, column: 8 });
+        assert!(!diagnostic.label.unwrap().contains('|'));
+        assert_eq!(
+            diagnostic.help.as_deref(),
+            Some("string values must be quoted")
+        );
+    }
+
+    #[test]
+    fn test_jsonl_position_in_record() {
+        let source = "{\"a\": 1}\n\n{\"a\": 2,}\n";
+        let diagnostic = diagnose(
+            FileFormat::Jsonl,
+            source,
+            &parse_error(FileFormat::Jsonl, source),
+        )
+        .unwrap();
+        assert_eq!(diagnostic.location, Location { line: 3, column: 8 });
+        assert_eq!(
+            diagnostic.help.as_deref(),
+            Some("trailing comma not allowed in JSON")
+        );
+    }
+
+    #[test]
+    fn test_render_color_and_without_position() {
+        assert!(diagnose(FileFormat::Json, "{}", "Invalid JSON: something").is_none());
+
+        let diagnostic = Diagnostic {
+            message: "Invalid YAML: x".to_string(),
+            location: Location { line: 5, column: 2 },
+            label: Some("here".to_string()),
+            help: None,
+        };
+        // position hinter dem ende zeigt auf die letzte zeile
+        let plain = diagnostic.render("a.yaml", "a: 1\n\tb: 2\n", false);
+        assert!(plain.contains(" --> a.yaml:2:2\n"));
+        assert!(plain.ends_with("2 | \tb: 2\n  | \t^ here\n"));
+        assert!(!plain.contains("help"));
+
+        let colored = diagnostic.render("a.yaml", "a: 1\n", true);
+        assert!(colored.starts_with("\x1b[1;31merror\x1b[0m"));
+    }
+}
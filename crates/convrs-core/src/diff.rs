@@ -0,0 +1,397 @@
+//! Semantischer Vergleich zweier Dokumente auf dem gemeinsamen Datenmodell.
+//!
+//! Da beide Seiten vorher geparst werden, spielen Format, Formatierung und
+//! Key-Reihenfolge keine Rolle. Optional wird auch die Reihenfolge in Arrays ignoriert.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::FormatError;
+use crate::query::{KeyPath, PathSegment};
+
+/// Optionen für `diff_values`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Arrays gelten als gleich, wenn sie dieselben Elemente enthalten (Reihenfolge egal).
+    pub ignore_array_order: bool,
+}
+
+/// Eine Änderung von der linken zur rechten Seite.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added {
+        path: KeyPath,
+        value: JsonValue,
+    },
+    Removed {
+        path: KeyPath,
+        value: JsonValue,
+    },
+    Changed {
+        path: KeyPath,
+        old: JsonValue,
+        new: JsonValue,
+    },
+}
+
+impl Change {
+    pub fn path(&self) -> &KeyPath {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// Ausgabeformat für `render_diff`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffFormat {
+    /// Eine Zeile pro Änderung: `+`, `-` oder `~`, Pfad und Werte.
+    #[default]
+    Text,
+    /// JSON-Array mit `type`, `path` und den Werten.
+    Json,
+    /// JSON Patch (RFC 6902), der die linke in die rechte Seite überführt.
+    Patch,
+}
+
+impl FromStr for DiffFormat {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(DiffFormat::Text),
+            "json" => Ok(DiffFormat::Json),
+            "patch" | "json-patch" => Ok(DiffFormat::Patch),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown diff format '{}', expected text, json or patch",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for DiffFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffFormat::Text => write!(f, "text"),
+            DiffFormat::Json => write!(f, "json"),
+            DiffFormat::Patch => write!(f, "patch"),
+        }
+    }
+}
+
+/// Vergleicht zwei Werte und gibt die Änderungen von `left` nach `right` zurück.
+///
+/// Die Reihenfolge ist so gewählt, dass die Änderungen als JSON Patch der Reihe nach
+/// anwendbar sind (Array-Elemente werden von hinten entfernt).
+pub fn diff_values(left: &JsonValue, right: &JsonValue, options: &DiffOptions) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(left, right, &mut Vec::new(), options, &mut changes);
+    changes
+}
+
+/// Formatiert die Änderungen im gewünschten Ausgabeformat.
+pub fn render_diff(changes: &[Change], format: DiffFormat) -> String {
+    match format {
+        DiffFormat::Text => changes
+            .iter()
+            .map(|change| match change {
+                Change::Added { path, value } => format!("+ {}: {}", path, value),
+                Change::Removed { path, value } => format!("- {}: {}", path, value),
+                Change::Changed { path, old, new } => format!("~ {}: {} -> {}", path, old, new),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        DiffFormat::Json => {
+            let entries: Vec<JsonValue> = changes
+                .iter()
+                .map(|change| match change {
+                    Change::Added { path, value } => {
+                        json!({"type": "added", "path": path.to_string(), "value": value})
+                    }
+                    Change::Removed { path, value } => {
+                        json!({"type": "removed", "path": path.to_string(), "value": value})
+                    }
+                    Change::Changed { path, old, new } => {
+                        json!({"type": "changed", "path": path.to_string(), "old": old, "new": new})
+                    }
+                })
+                .collect();
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        }
+        DiffFormat::Patch => {
+            let operations: Vec<JsonValue> = changes
+                .iter()
+                .map(|change| match change {
+                    Change::Added { path, value } => {
+                        json!({"op": "add", "path": path.to_json_pointer(), "value": value})
+                    }
+                    Change::Removed { path, .. } => {
+                        json!({"op": "remove", "path": path.to_json_pointer()})
+                    }
+                    Change::Changed { path, new, .. } => {
+                        json!({"op": "replace", "path": path.to_json_pointer(), "value": new})
+                    }
+                })
+                .collect();
+            serde_json::to_string_pretty(&operations).unwrap_or_default()
+        }
+    }
+}
+
+// private helper funktionen
+
+fn diff_into(
+    left: &JsonValue,
+    right: &JsonValue,
+    path: &mut Vec<PathSegment>,
+    options: &DiffOptions,
+    changes: &mut Vec<Change>,
+) {
+    if same(left, right, options) {
+        return;
+    }
+
+    let at = |path: &Vec<PathSegment>, segment: PathSegment| {
+        let mut segments = path.clone();
+        segments.push(segment);
+        KeyPath::from_segments(segments)
+    };
+
+    match (left, right) {
+        (JsonValue::Object(left_obj), JsonValue::Object(right_obj)) => {
+            let keys: BTreeSet<&String> = left_obj.keys().chain(right_obj.keys()).collect();
+            for key in keys {
+                match (left_obj.get(key), right_obj.get(key)) {
+                    (Some(l), Some(r)) => {
+                        path.push(PathSegment::Key(key.clone()));
+                        diff_into(l, r, path, options, changes);
+                        path.pop();
+                    }
+                    (Some(l), None) => changes.push(Change::Removed {
+                        path: at(path, PathSegment::Key(key.clone())),
+                        value: l.clone(),
+                    }),
+                    (None, Some(r)) => changes.push(Change::Added {
+                        path: at(path, PathSegment::Key(key.clone())),
+                        value: r.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (JsonValue::Array(left_arr), JsonValue::Array(right_arr)) if options.ignore_array_order => {
+            // elemente ohne gegenstück werden entfernt, neue hinten angehängt
+            let mut matched = vec![false; right_arr.len()];
+            let mut removed = Vec::new();
+            for (index, item) in left_arr.iter().enumerate() {
+                let partner = (0..right_arr.len()).find(|&candidate| {
+                    !matched[candidate] && same(item, &right_arr[candidate], options)
+                });
+                match partner {
+                    Some(candidate) => matched[candidate] = true,
+                    None => removed.push(index),
+                }
+            }
+
+            for &index in removed.iter().rev() {
+                changes.push(Change::Removed {
+                    path: at(path, PathSegment::Index(index as i64)),
+                    value: left_arr[index].clone(),
+                });
+            }
+            let kept = left_arr.len() - removed.len();
+            let added = right_arr
+                .iter()
+                .zip(&matched)
+                .filter(|(_, matched)| !**matched);
+            for (offset, (item, _)) in added.enumerate() {
+                changes.push(Change::Added {
+                    path: at(path, PathSegment::Index((kept + offset) as i64)),
+                    value: item.clone(),
+                });
+            }
+        }
+        (JsonValue::Array(left_arr), JsonValue::Array(right_arr)) => {
+            for (index, (l, r)) in left_arr.iter().zip(right_arr).enumerate() {
+                path.push(PathSegment::Index(index as i64));
+                diff_into(l, r, path, options, changes);
+                path.pop();
+            }
+            for (index, value) in right_arr.iter().enumerate().skip(left_arr.len()) {
+                changes.push(Change::Added {
+                    path: at(path, PathSegment::Index(index as i64)),
+                    value: value.clone(),
+                });
+            }
+            for (index, value) in left_arr.iter().enumerate().skip(right_arr.len()).rev() {
+                changes.push(Change::Removed {
+                    path: at(path, PathSegment::Index(index as i64)),
+                    value: value.clone(),
+                });
+            }
+        }
+        _ => changes.push(Change::Changed {
+            path: KeyPath::from_segments(path.clone()),
+            old: left.clone(),
+            new: right.clone(),
+        }),
+    }
+}
+
+/// Semantische Gleichheit: `1` und `1.0` sind gleich (z.b. TOML-Integer gegen YAML-Float).
+fn same(left: &JsonValue, right: &JsonValue, options: &DiffOptions) -> bool {
+    match (left, right) {
+        (JsonValue::Number(l), JsonValue::Number(r)) => {
+            l == r || (l.is_f64() || r.is_f64()) && l.as_f64() == r.as_f64()
+        }
+        (JsonValue::Object(l), JsonValue::Object(r)) => {
+            l.len() == r.len()
+                && l.iter()
+                    .all(|(key, value)| r.get(key).is_some_and(|other| same(value, other, options)))
+        }
+        (JsonValue::Array(l), JsonValue::Array(r)) if options.ignore_array_order => {
+            let mut matched = vec![false; r.len()];
+            l.len() == r.len()
+                && l.iter().all(|item| {
+                    match (0..r.len()).find(|&i| !matched[i] && same(item, &r[i], options)) {
+                        Some(i) => {
+                            matched[i] = true;
+                            true
+                        }
+                        None => false,
+                    }
+                })
+        }
+        (JsonValue::Array(l), JsonValue::Array(r)) => {
+            l.len() == r.len() && l.iter().zip(r).all(|(a, b)| same(a, b, options))
+        }
+        _ => left == right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(changes: &[Change]) -> Vec<String> {
+        changes.iter().map(|c| c.path().to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_objects() {
+        let left = json!({"db": {"host": "localhost", "port": 5432}, "debug": true, "n": 1});
+        let right = json!({"n": 1.0, "db": {"port": 6543, "host": "localhost", "user": "admin"}});
+
+        let changes = diff_values(&left, &right, &DiffOptions::default());
+        assert_eq!(
+            changes,
+            vec![
+                Change::Changed {
+                    path: ".db.port".parse().unwrap(),
+                    old: json!(5432),
+                    new: json!(6543)
+                },
+                Change::Added {
+                    path: ".db.user".parse().unwrap(),
+                    value: json!("admin")
+                },
+                Change::Removed {
+                    path: ".debug".parse().unwrap(),
+                    value: json!(true)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_arrays_ordered_and_unordered() {
+        let left = json!({"tags": ["a", "b", "c"]});
+        let right = json!({"tags": ["c", "a", "d"]});
+
+        let ordered = diff_values(&left, &right, &DiffOptions::default());
+        assert_eq!(paths(&ordered), vec![".tags[0]", ".tags[1]", ".tags[2]"]);
+
+        let options = DiffOptions {
+            ignore_array_order: true,
+        };
+        let unordered = diff_values(&left, &right, &options);
+        assert_eq!(
+            unordered,
+            vec![
+                Change::Removed {
+                    path: ".tags[1]".parse().unwrap(),
+                    value: json!("b")
+                },
+                Change::Added {
+                    path: ".tags[2]".parse().unwrap(),
+                    value: json!("d")
+                },
+            ]
+        );
+        assert!(diff_values(&json!([1, [2, 3]]), &json!([[3, 2], 1]), &options).is_empty());
+    }
+
+    #[test]
+    fn test_diff_array_length_changes() {
+        let changes = diff_values(&json!([1, 2, 3, 4]), &json!([1]), &DiffOptions::default());
+        assert_eq!(paths(&changes), vec![".[3]", ".[2]", ".[1]"]);
+    }
+
+    #[test]
+    fn test_render_text_and_patch() {
+        let changes = diff_values(
+            &json!({"a": 1, "b": [1, 2], "c": "x"}),
+            &json!({"a": 2, "b": [1], "d": null}),
+            &DiffOptions::default(),
+        );
+
+        assert_eq!(
+            render_diff(&changes, DiffFormat::Text),
+            "~ .a: 1 -> 2\n- .b[1]: 2\n- .c: \"x\"\n+ .d: null"
+        );
+
+        let patch: JsonValue =
+            serde_json::from_str(&render_diff(&changes, DiffFormat::Patch)).unwrap();
+        assert_eq!(
+            patch,
+            json!([
+                {"op": "replace", "path": "/a", "value": 2},
+                {"op": "remove", "path": "/b/1"},
+                {"op": "remove", "path": "/c"},
+                {"op": "add", "path": "/d", "value": null}
+            ])
+        );
+
+        let report: JsonValue =
+            serde_json::from_str(&render_diff(&changes, DiffFormat::Json)).unwrap();
+        assert_eq!(
+            report[0],
+            json!({"type": "changed", "path": ".a", "old": 1, "new": 2})
+        );
+    }
+
+    #[test]
+    fn test_diff_root_type_change() {
+        let changes = diff_values(&json!([1]), &json!({"a": 1}), &DiffOptions::default());
+        assert_eq!(paths(&changes), vec!["."]);
+        assert_eq!(
+            render_diff(&changes, DiffFormat::Patch),
+            "[\n  {\n    \"op\": \"replace\",\n    \"path\": \"\",\n    \"value\": {\n      \"a\": 1\n    }\n  }\n]"
+        );
+    }
+
+    #[test]
+    fn test_diff_format_parsing() {
+        assert_eq!(
+            "json-patch".parse::<DiffFormat>().unwrap(),
+            DiffFormat::Patch
+        );
+        assert!("unified".parse::<DiffFormat>().is_err());
+    }
+}
@@ -0,0 +1,680 @@
+//! Lesen, Setzen und Löschen einzelner Werte in einem Dokument (`get`/`set`/`delete`).
+//!
+//! Das Ergebnis wird im selben Format geschrieben. TOML wird über `toml_edit` bearbeitet
+//! und behält Kommentare und Key-Reihenfolge. JSON und YAML behalten die Key-Reihenfolge,
+//! YAML-Kommentare gehen verloren. Alle anderen Formate laufen über das gemeinsame Datenmodell.
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::format::FileFormat;
+use crate::formats::utils::json_to_toml_value;
+use crate::options::ConvertOptions;
+use crate::query::{KeyPath, PathSegment, resolve_index, type_name};
+
+/// Liest den Wert unter `path` aus einem Dokument.
+pub fn get_value(
+    format: FileFormat,
+    input: &[u8],
+    path: &KeyPath,
+    options: &ConvertOptions,
+) -> Result<JsonValue, FormatError> {
+    let document = format.parse_bytes(input, options)?;
+    get_path(&document, path)
+        .cloned()
+        .ok_or_else(|| not_found(path))
+}
+
+/// Setzt den Wert unter `path` und gibt das geänderte Dokument zurück.
+/// Fehlende Objekte auf dem Weg werden angelegt, `[n]` mit n = Länge hängt an.
+pub fn set_value(
+    format: FileFormat,
+    input: &[u8],
+    path: &KeyPath,
+    value: &JsonValue,
+    options: &ConvertOptions,
+) -> Result<Vec<u8>, FormatError> {
+    match format {
+        FileFormat::Toml => {
+            let mut document = parse_toml_document(input)?;
+            set_in(document.as_item_mut(), path, json_to_toml_item(value)?)?;
+            Ok(document.to_string().into_bytes())
+        }
+        FileFormat::Json | FileFormat::Yaml => {
+            let mut document = parse_ordered(format, input)?;
+            let value = serde_yaml::to_value(value)
+                .map_err(|e| FormatError::SerializationError(format!("Invalid value: {}", e)))?;
+            set_in(&mut document, path, value)?;
+            write_ordered(format, &document)
+        }
+        _ => {
+            let mut document = format.parse_bytes(input, options)?;
+            set_in(&mut document, path, value.clone())?;
+            format.serialize_value(&document, options)
+        }
+    }
+}
+
+/// Löscht den Wert unter `path` und gibt das geänderte Dokument zurück.
+pub fn delete_value(
+    format: FileFormat,
+    input: &[u8],
+    path: &KeyPath,
+    options: &ConvertOptions,
+) -> Result<Vec<u8>, FormatError> {
+    match format {
+        FileFormat::Toml => {
+            let mut document = parse_toml_document(input)?;
+            delete_in(document.as_item_mut(), path)?;
+            Ok(document.to_string().into_bytes())
+        }
+        FileFormat::Json | FileFormat::Yaml => {
+            let mut document = parse_ordered(format, input)?;
+            delete_in(&mut document, path)?;
+            write_ordered(format, &document)
+        }
+        _ => {
+            let mut document = format.parse_bytes(input, options)?;
+            delete_in(&mut document, path)?;
+            format.serialize_value(&document, options)
+        }
+    }
+}
+
+/// Sucht den Wert unter `path` im gemeinsamen Datenmodell.
+pub fn get_path<'a>(value: &'a JsonValue, path: &KeyPath) -> Option<&'a JsonValue> {
+    path.segments()
+        .iter()
+        .try_fold(value, |current, segment| match (segment, current) {
+            (PathSegment::Key(key), JsonValue::Object(obj)) => obj.get(key),
+            (PathSegment::Index(index), JsonValue::Array(arr)) => {
+                resolve_index(*index, arr.len()).and_then(|i| arr.get(i))
+            }
+            _ => None,
+        })
+}
+
+/// Setzt den Wert unter `path` im gemeinsamen Datenmodell.
+pub fn set_path(
+    document: &mut JsonValue,
+    path: &KeyPath,
+    value: JsonValue,
+) -> Result<(), FormatError> {
+    set_in(document, path, value)
+}
+
+/// Löscht den Wert unter `path` im gemeinsamen Datenmodell.
+pub fn delete_path(document: &mut JsonValue, path: &KeyPath) -> Result<(), FormatError> {
+    delete_in(document, path)
+}
+
+/// Interpretiert ein Kommandozeilen-Argument als JSON-Wert (`42`, `true`, `[1,2]`, `"x"`),
+/// alles andere wird als String übernommen.
+pub fn value_from_arg(arg: &str) -> JsonValue {
+    serde_json::from_str(arg).unwrap_or_else(|_| JsonValue::String(arg.to_string()))
+}
+
+// private helper funktionen
+
+/// Gemeinsame Schnittstelle der bearbeitbaren Dokument-Bäume
+/// (`serde_json::Value`, `serde_yaml::Value`, `toml_edit::Item`).
+trait EditTree: Sized {
+    fn kind(&self) -> &'static str;
+
+    /// Gibt das Kind unter `segment` zurück. Mit `create` werden fehlende Objekte angelegt.
+    fn child_mut(&mut self, segment: &PathSegment, create: bool) -> Option<&mut Self>;
+
+    fn set_child(&mut self, segment: &PathSegment, value: Self) -> Result<(), String>;
+
+    /// Gibt `false` zurück, wenn es kein Kind unter `segment` gibt.
+    fn remove_child(&mut self, segment: &PathSegment) -> bool;
+}
+
+fn set_in<T: EditTree>(root: &mut T, path: &KeyPath, value: T) -> Result<(), FormatError> {
+    let (last, parents) = split_path(path)?;
+
+    let mut node = root;
+    for segment in parents {
+        let kind = node.kind();
+        node = node.child_mut(segment, true).ok_or_else(|| {
+            FormatError::PathError(format!(
+                "cannot resolve '{}': {} {} has no {}",
+                path,
+                article(kind),
+                kind,
+                describe(segment)
+            ))
+        })?;
+    }
+
+    node.set_child(last, value)
+        .map_err(|msg| FormatError::PathError(format!("cannot set '{}': {}", path, msg)))
+}
+
+fn delete_in<T: EditTree>(root: &mut T, path: &KeyPath) -> Result<(), FormatError> {
+    let (last, parents) = split_path(path)?;
+
+    let mut node = root;
+    for segment in parents {
+        node = node
+            .child_mut(segment, false)
+            .ok_or_else(|| not_found(path))?;
+    }
+
+    if node.remove_child(last) {
+        Ok(())
+    } else {
+        Err(not_found(path))
+    }
+}
+
+fn split_path(path: &KeyPath) -> Result<(&PathSegment, &[PathSegment]), FormatError> {
+    path.segments()
+        .split_last()
+        .ok_or_else(|| FormatError::PathError("path must not be empty".to_string()))
+}
+
+fn not_found(path: &KeyPath) -> FormatError {
+    FormatError::PathError(format!("'{}' not found", path))
+}
+
+fn describe(segment: &PathSegment) -> String {
+    match segment {
+        PathSegment::Key(key) => format!("key \"{}\"", key),
+        PathSegment::Index(index) => format!("index [{}]", index),
+    }
+}
+
+fn article(kind: &str) -> &'static str {
+    if kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+/// Index zum Ersetzen oder, wenn er genau der Länge entspricht, zum Anhängen.
+fn set_index(index: i64, len: usize) -> Result<usize, String> {
+    match resolve_index(index, len) {
+        Some(i) if i <= len => Ok(i),
+        _ => Err(format!(
+            "index [{}] out of range for array of length {}",
+            index, len
+        )),
+    }
+}
+
+impl EditTree for JsonValue {
+    fn kind(&self) -> &'static str {
+        type_name(self)
+    }
+
+    fn child_mut(&mut self, segment: &PathSegment, create: bool) -> Option<&mut Self> {
+        if create && self.is_null() && matches!(segment, PathSegment::Key(_)) {
+            *self = JsonValue::Object(serde_json::Map::new());
+        }
+        match (segment, self) {
+            (PathSegment::Key(key), JsonValue::Object(obj)) => {
+                if create {
+                    Some(
+                        obj.entry(key.clone())
+                            .or_insert_with(|| JsonValue::Object(serde_json::Map::new())),
+                    )
+                } else {
+                    obj.get_mut(key)
+                }
+            }
+            (PathSegment::Index(index), JsonValue::Array(arr)) => {
+                resolve_index(*index, arr.len()).and_then(|i| arr.get_mut(i))
+            }
+            _ => None,
+        }
+    }
+
+    fn set_child(&mut self, segment: &PathSegment, value: Self) -> Result<(), String> {
+        if self.is_null() && matches!(segment, PathSegment::Key(_)) {
+            *self = JsonValue::Object(serde_json::Map::new());
+        }
+        let kind = self.kind();
+        match (segment, self) {
+            (PathSegment::Key(key), JsonValue::Object(obj)) => {
+                obj.insert(key.clone(), value);
+                Ok(())
+            }
+            (PathSegment::Index(index), JsonValue::Array(arr)) => {
+                let i = set_index(*index, arr.len())?;
+                if i == arr.len() {
+                    arr.push(value);
+                } else {
+                    arr[i] = value;
+                }
+                Ok(())
+            }
+            (segment, _) => Err(format!(
+                "{} {} has no {}",
+                article(kind),
+                kind,
+                describe(segment)
+            )),
+        }
+    }
+
+    fn remove_child(&mut self, segment: &PathSegment) -> bool {
+        match (segment, self) {
+            (PathSegment::Key(key), JsonValue::Object(obj)) => obj.remove(key).is_some(),
+            (PathSegment::Index(index), JsonValue::Array(arr)) => {
+                match resolve_index(*index, arr.len()).filter(|i| *i < arr.len()) {
+                    Some(i) => {
+                        arr.remove(i);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl EditTree for serde_yaml::Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            serde_yaml::Value::Null => "null",
+            serde_yaml::Value::Bool(_) => "boolean",
+            serde_yaml::Value::Number(_) => "number",
+            serde_yaml::Value::String(_) => "string",
+            serde_yaml::Value::Sequence(_) => "array",
+            serde_yaml::Value::Mapping(_) => "object",
+            serde_yaml::Value::Tagged(_) => "tagged value",
+        }
+    }
+
+    fn child_mut(&mut self, segment: &PathSegment, create: bool) -> Option<&mut Self> {
+        if create && self.is_null() && matches!(segment, PathSegment::Key(_)) {
+            *self = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+        match (segment, self) {
+            (PathSegment::Key(key), serde_yaml::Value::Mapping(map)) => {
+                if create {
+                    Some(
+                        map.entry(serde_yaml::Value::String(key.clone()))
+                            .or_insert_with(|| {
+                                serde_yaml::Value::Mapping(serde_yaml::Mapping::new())
+                            }),
+                    )
+                } else {
+                    map.get_mut(key.as_str())
+                }
+            }
+            (PathSegment::Index(index), serde_yaml::Value::Sequence(seq)) => {
+                resolve_index(*index, seq.len()).and_then(|i| seq.get_mut(i))
+            }
+            _ => None,
+        }
+    }
+
+    fn set_child(&mut self, segment: &PathSegment, value: Self) -> Result<(), String> {
+        if self.is_null() && matches!(segment, PathSegment::Key(_)) {
+            *self = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+        let kind = self.kind();
+        match (segment, self) {
+            (PathSegment::Key(key), serde_yaml::Value::Mapping(map)) => {
+                map.insert(serde_yaml::Value::String(key.clone()), value);
+                Ok(())
+            }
+            (PathSegment::Index(index), serde_yaml::Value::Sequence(seq)) => {
+                let i = set_index(*index, seq.len())?;
+                if i == seq.len() {
+                    seq.push(value);
+                } else {
+                    seq[i] = value;
+                }
+                Ok(())
+            }
+            (segment, _) => Err(format!(
+                "{} {} has no {}",
+                article(kind),
+                kind,
+                describe(segment)
+            )),
+        }
+    }
+
+    fn remove_child(&mut self, segment: &PathSegment) -> bool {
+        match (segment, self) {
+            (PathSegment::Key(key), serde_yaml::Value::Mapping(map)) => {
+                map.shift_remove(key.as_str()).is_some()
+            }
+            (PathSegment::Index(index), serde_yaml::Value::Sequence(seq)) => {
+                match resolve_index(*index, seq.len()).filter(|i| *i < seq.len()) {
+                    Some(i) => {
+                        seq.remove(i);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl EditTree for toml_edit::Item {
+    fn kind(&self) -> &'static str {
+        match self {
+            toml_edit::Item::None => "missing value",
+            toml_edit::Item::Value(value) => value.type_name(),
+            toml_edit::Item::Table(_) => "table",
+            toml_edit::Item::ArrayOfTables(_) => "array of tables",
+        }
+    }
+
+    fn child_mut(&mut self, segment: &PathSegment, create: bool) -> Option<&mut Self> {
+        match segment {
+            PathSegment::Key(key) => {
+                let table = self.as_table_like_mut()?;
+                if create && table.get(key).is_none() {
+                    // implizit: kein leerer [a]-header, wenn nur [a.b] gesetzt wird
+                    let mut new_table = toml_edit::Table::new();
+                    new_table.set_implicit(true);
+                    table.insert(key, toml_edit::Item::Table(new_table));
+                }
+                table.get_mut(key)
+            }
+            PathSegment::Index(index) => {
+                let len = toml_len(self)?;
+                let i = resolve_index(*index, len).filter(|i| *i < len)?;
+                self.get_mut(i)
+            }
+        }
+    }
+
+    fn set_child(&mut self, segment: &PathSegment, value: Self) -> Result<(), String> {
+        let kind = self.kind();
+        let mismatch = |segment: &PathSegment| {
+            format!("{} {} has no {}", article(kind), kind, describe(segment))
+        };
+
+        match segment {
+            PathSegment::Key(key) => {
+                // objekte unter normalen tabellen als [tabelle] statt inline schreiben
+                let value = match (self.is_table(), value) {
+                    (true, toml_edit::Item::Value(toml_edit::Value::InlineTable(inline))) => {
+                        toml_edit::Item::Table(inline.into_table())
+                    }
+                    (_, value) => value,
+                };
+                let table = self.as_table_like_mut().ok_or_else(|| mismatch(segment))?;
+                let mut value = value;
+                match table.get_mut(key) {
+                    // bestehenden eintrag direkt ersetzen, damit kommentare am key bleiben
+                    Some(existing) => {
+                        if let (Some(old), toml_edit::Item::Value(new)) =
+                            (existing.as_value(), &mut value)
+                        {
+                            *new.decor_mut() = old.decor().clone();
+                        }
+                        *existing = value;
+                    }
+                    None => {
+                        table.insert(key, value);
+                    }
+                }
+                Ok(())
+            }
+            PathSegment::Index(index) => match self {
+                toml_edit::Item::Value(toml_edit::Value::Array(arr)) => {
+                    let i = set_index(*index, arr.len())?;
+                    let value = value
+                        .into_value()
+                        .map_err(|item| format!("cannot store {} in an array", item.type_name()))?;
+                    if i == arr.len() {
+                        arr.push(value);
+                    } else {
+                        arr.replace(i, value);
+                    }
+                    Ok(())
+                }
+                toml_edit::Item::ArrayOfTables(tables) => {
+                    let i = set_index(*index, tables.len())?;
+                    let table = value.into_table().map_err(|item| {
+                        format!("cannot store {} in an array of tables", item.type_name())
+                    })?;
+                    if i == tables.len() {
+                        tables.push(table);
+                    } else if let Some(existing) = tables.get_mut(i) {
+                        *existing = table;
+                    }
+                    Ok(())
+                }
+                _ => Err(mismatch(segment)),
+            },
+        }
+    }
+
+    fn remove_child(&mut self, segment: &PathSegment) -> bool {
+        match segment {
+            PathSegment::Key(key) => self
+                .as_table_like_mut()
+                .is_some_and(|table| table.remove(key).is_some()),
+            PathSegment::Index(index) => {
+                let Some(len) = toml_len(self) else {
+                    return false;
+                };
+                let Some(i) = resolve_index(*index, len).filter(|i| *i < len) else {
+                    return false;
+                };
+                match self {
+                    toml_edit::Item::Value(toml_edit::Value::Array(arr)) => {
+                        // leerraum des entfernten elements an das nachfolgende weitergeben
+                        let removed = arr.remove(i);
+                        if let Some(next) = arr.get_mut(i) {
+                            *next.decor_mut() = removed.decor().clone();
+                        }
+                    }
+                    toml_edit::Item::ArrayOfTables(tables) => tables.remove(i),
+                    _ => return false,
+                }
+                true
+            }
+        }
+    }
+}
+
+fn toml_len(item: &toml_edit::Item) -> Option<usize> {
+    match item {
+        toml_edit::Item::Value(toml_edit::Value::Array(arr)) => Some(arr.len()),
+        toml_edit::Item::ArrayOfTables(tables) => Some(tables.len()),
+        _ => None,
+    }
+}
+
+fn parse_toml_document(input: &[u8]) -> Result<toml_edit::DocumentMut, FormatError> {
+    utf8(input)?
+        .parse()
+        .map_err(|e| FormatError::ParseError(format!("Invalid TOML: {}", e)))
+}
+
+fn json_to_toml_item(value: &JsonValue) -> Result<toml_edit::Item, FormatError> {
+    // toml::Value wird als inline-wert ausgegeben und von toml_edit wieder eingelesen
+    let inline = json_to_toml_value(value)?.to_string();
+    inline
+        .parse::<toml_edit::Value>()
+        .map(toml_edit::Item::Value)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting TOML: {}", e)))
+}
+
+/// Liest JSON oder YAML in einen `serde_yaml::Value`, der die Key-Reihenfolge behält.
+fn parse_ordered(format: FileFormat, input: &[u8]) -> Result<serde_yaml::Value, FormatError> {
+    let text = utf8(input)?;
+    if format == FileFormat::Json {
+        serde_json::from_str(text)
+            .map_err(|e| FormatError::ParseError(format!("Invalid JSON: {}", e)))
+    } else {
+        serde_yaml::from_str(text)
+            .map_err(|e| FormatError::ParseError(format!("Invalid YAML: {}", e)))
+    }
+}
+
+fn write_ordered(format: FileFormat, document: &serde_yaml::Value) -> Result<Vec<u8>, FormatError> {
+    let text = if format == FileFormat::Json {
+        serde_json::to_string_pretty(document)
+            .map_err(|e| FormatError::SerializationError(format!("Error formatting JSON: {}", e)))?
+    } else {
+        serde_yaml::to_string(document)
+            .map_err(|e| FormatError::SerializationError(format!("Error formatting YAML: {}", e)))?
+    };
+    Ok(text.into_bytes())
+}
+
+fn utf8(input: &[u8]) -> Result<&str, FormatError> {
+    std::str::from_utf8(input)
+        .map_err(|e| FormatError::ParseError(format!("Input is not valid UTF-8: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(s: &str) -> KeyPath {
+        s.parse().unwrap()
+    }
+
+    fn set(format: FileFormat, input: &str, p: &str, value: JsonValue) -> String {
+        let output = set_value(
+            format,
+            input.as_bytes(),
+            &path(p),
+            &value,
+            &Default::default(),
+        );
+        String::from_utf8(output.unwrap()).unwrap()
+    }
+
+    fn delete(format: FileFormat, input: &str, p: &str) -> Result<String, FormatError> {
+        delete_value(format, input.as_bytes(), &path(p), &Default::default())
+            .map(|output| String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_get_value() {
+        let input = b"server:\n  ports: [80, 443]\n";
+        let options = ConvertOptions::default();
+        let value = get_value(
+            FileFormat::Yaml,
+            input,
+            &path(".server.ports[-1]"),
+            &options,
+        );
+        assert_eq!(value.unwrap(), 443);
+
+        let err = get_value(FileFormat::Yaml, input, &path("server.host"), &options).unwrap_err();
+        assert!(matches!(err, FormatError::PathError(_)));
+    }
+
+    #[test]
+    fn test_set_toml_keeps_comments_and_order() {
+        let input = "# app config\nname = \"demo\" # display name\nport = 80\n\n[db]\n# connection\nhost = \"localhost\"\n";
+        let result = set(FileFormat::Toml, input, ".name", serde_json::json!("prod"));
+        assert_eq!(
+            result,
+            "# app config\nname = \"prod\" # display name\nport = 80\n\n[db]\n# connection\nhost = \"localhost\"\n"
+        );
+
+        let result = set(FileFormat::Toml, input, "db.port", serde_json::json!(5432));
+        assert!(result.ends_with("# connection\nhost = \"localhost\"\nport = 5432\n"));
+    }
+
+    #[test]
+    fn test_set_toml_creates_tables_and_appends() {
+        let input = "tags = [\"a\"]\n";
+        let result = set(FileFormat::Toml, input, "tags[1]", serde_json::json!("b"));
+        assert_eq!(result, "tags = [\"a\", \"b\"]\n");
+
+        let result = set(
+            FileFormat::Toml,
+            input,
+            "cache.redis",
+            serde_json::json!({"port": 6379}),
+        );
+        assert!(result.contains("[cache.redis]\nport = 6379"));
+        assert!(!result.contains("[cache]\n"));
+    }
+
+    #[test]
+    fn test_set_json_keeps_key_order() {
+        let input = r#"{"zeta": 1, "alpha": {"b": 2, "a": 3}}"#;
+        let result = set(
+            FileFormat::Json,
+            input,
+            "alpha.b",
+            serde_json::json!([1, 2]),
+        );
+        let zeta = result.find("zeta").unwrap();
+        let alpha = result.find("alpha").unwrap();
+        assert!(zeta < alpha);
+        assert!(result.find("\"b\"").unwrap() < result.find("\"a\"").unwrap());
+        assert!(result.contains("\"b\": [\n"));
+    }
+
+    #[test]
+    fn test_set_yaml_and_csv() {
+        let result = set(
+            FileFormat::Yaml,
+            "b: 1\na: 2\n",
+            "b",
+            serde_json::json!(true),
+        );
+        assert_eq!(result, "b: true\na: 2\n");
+
+        let result = set(
+            FileFormat::Csv,
+            "name,age\nAlice,30\n",
+            "[0].age",
+            serde_json::json!(31),
+        );
+        assert_eq!(result, "age,name\n31,Alice\n");
+    }
+
+    #[test]
+    fn test_set_type_mismatch_fails() {
+        let err = set_value(
+            FileFormat::Json,
+            br#"{"name": "demo"}"#,
+            &path("name.first"),
+            &serde_json::json!("x"),
+            &Default::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("a string has no key \"first\""));
+    }
+
+    #[test]
+    fn test_delete_values() {
+        let toml = "a = 1 # keep\nb = 2\nlist = [1, 2, 3]\n";
+        assert_eq!(
+            delete(FileFormat::Toml, toml, "b").unwrap(),
+            "a = 1 # keep\nlist = [1, 2, 3]\n"
+        );
+        assert_eq!(
+            delete(FileFormat::Toml, toml, "list[0]").unwrap(),
+            "a = 1 # keep\nb = 2\nlist = [2, 3]\n"
+        );
+
+        let yaml = delete(FileFormat::Yaml, "x: 1\ny:\n  z: 2\n", "y.z").unwrap();
+        assert_eq!(yaml, "x: 1\ny: {}\n");
+
+        let err = delete(FileFormat::Json, "{}", "missing").unwrap_err();
+        assert_eq!(err.to_string(), "Path Error: '.missing' not found");
+    }
+
+    #[test]
+    fn test_value_from_arg() {
+        assert_eq!(value_from_arg("42"), 42);
+        assert_eq!(value_from_arg("true"), true);
+        assert_eq!(value_from_arg("[1,2]"), serde_json::json!([1, 2]));
+        assert_eq!(value_from_arg("hello world"), "hello world");
+        assert_eq!(value_from_arg("\"42\""), "42");
+    }
+}
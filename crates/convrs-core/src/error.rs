@@ -11,10 +11,38 @@ pub enum FormatError {
     SerializationError(String),
     /// Unbekanntes oder nicht unterstütztes Format.
     InvalidFormat(String),
+    /// Pfad im Dokument existiert nicht oder passt nicht zur Struktur (get/set/delete).
+    PathError(String),
     /// Fallback für unerwartete Fehler.
     UnknownError(String),
 }
 
+impl FormatError {
+    /// Stabiler Name der Variante für maschinenlesbare Ausgaben, z.b. `parse`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FormatError::IoError(_) => "io",
+            FormatError::ParseError(_) => "parse",
+            FormatError::SerializationError(_) => "serialization",
+            FormatError::InvalidFormat(_) => "invalid_format",
+            FormatError::PathError(_) => "path",
+            FormatError::UnknownError(_) => "unknown",
+        }
+    }
+
+    /// Die Meldung ohne den Präfix der Variante.
+    pub fn message(&self) -> &str {
+        match self {
+            FormatError::IoError(msg)
+            | FormatError::ParseError(msg)
+            | FormatError::SerializationError(msg)
+            | FormatError::InvalidFormat(msg)
+            | FormatError::PathError(msg)
+            | FormatError::UnknownError(msg) => msg,
+        }
+    }
+}
+
 impl std::fmt::Display for FormatError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -22,6 +50,7 @@ impl std::fmt::Display for FormatError {
             FormatError::ParseError(msg) => write!(f, "Parse Error: {}", msg),
             FormatError::SerializationError(msg) => write!(f, "Serialization Error: {}", msg),
             FormatError::InvalidFormat(msg) => write!(f, "Invalid Format: {}", msg),
+            FormatError::PathError(msg) => write!(f, "Path Error: {}", msg),
             FormatError::UnknownError(msg) => write!(f, "Unknown Error: {}", msg),
         }
     }
@@ -57,12 +86,26 @@ mod tests {
         assert_eq!(err.to_string(), "Invalid Format: xml");
     }
 
+    #[test]
+    fn test_display_path_error() {
+        let err = FormatError::PathError("'.a.b' not found".to_string());
+        assert_eq!(err.to_string(), "Path Error: '.a.b' not found");
+    }
+
     #[test]
     fn test_display_unknown_error() {
         let err = FormatError::UnknownError("unexpected".to_string());
         assert_eq!(err.to_string(), "Unknown Error: unexpected");
     }
 
+    #[test]
+    fn test_kind_and_message() {
+        let err = FormatError::InvalidFormat("xml".to_string());
+        assert_eq!(err.kind(), "invalid_format");
+        assert_eq!(err.message(), "xml");
+        assert_eq!(FormatError::IoError(String::new()).kind(), "io");
+    }
+
     #[test]
     fn test_error_is_debug() {
         let err = FormatError::ParseError("test".to_string());
@@ -0,0 +1,419 @@
+//! Kanonische Formatierung von Dateien im selben Format (`convrs fmt`).
+//!
+//! JSON, JSON Lines und YAML laufen über `serde_yaml::Value`, das die Key-Reihenfolge
+//! behält. Weil dabei YAML-Kommentare verloren gingen und Anker/Aliase aufgelöst würden,
+//! lehnt `fmt` YAML mit Kommentaren oder Ankern ab, statt die Datei zu verändern.
+//! TOML wird über `toml_edit` formatiert und behält Kommentare; normalisiert werden
+//! Leerzeichen um `=`, Einrückung und Leerzeilen.
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value as YamlValue};
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::error::FormatError;
+use crate::format::FileFormat;
+
+/// Stil-Optionen für `format_bytes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FmtOptions {
+    /// Einrückung in Leerzeichen für JSON (YAML rückt immer mit 2 ein).
+    pub indent: usize,
+    /// Keys in Objekten alphabetisch sortieren.
+    pub sort_keys: bool,
+}
+
+impl Default for FmtOptions {
+    fn default() -> Self {
+        FmtOptions {
+            indent: 2,
+            sort_keys: false,
+        }
+    }
+}
+
+/// Gibt an, ob `fmt` das Format unterstützt. Andere Formate würden beim Neuschreiben
+/// Informationen verlieren (z.b. CSV durch die Typerkennung).
+pub fn supports(format: FileFormat) -> bool {
+    matches!(
+        format,
+        FileFormat::Json | FileFormat::Jsonl | FileFormat::Yaml | FileFormat::Toml
+    )
+}
+
+/// Formatiert ein Dokument kanonisch. Das Ergebnis endet immer mit einem Zeilenumbruch.
+pub fn format_bytes(
+    format: FileFormat,
+    input: &[u8],
+    options: &FmtOptions,
+) -> Result<Vec<u8>, FormatError> {
+    let text = std::str::from_utf8(input)
+        .map_err(|e| FormatError::ParseError(format!("Input is not valid UTF-8: {}", e)))?;
+
+    let formatted = match format {
+        FileFormat::Json => {
+            let value = parse_json(text, options)?;
+            let mut out = to_json(&value, Some(options.indent))?;
+            out.push('\n');
+            out
+        }
+        FileFormat::Jsonl => {
+            let mut out = String::new();
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                out.push_str(&to_json(&parse_json(line, options)?, None)?);
+                out.push('\n');
+            }
+            out
+        }
+        FileFormat::Yaml => {
+            if let Some((what, line)) = yaml_lossy_construct(text) {
+                return Err(FormatError::InvalidFormat(format!(
+                    "fmt would drop the YAML {} at line {}, file left unchanged",
+                    what, line
+                )));
+            }
+            let mut value: YamlValue = serde_yaml::from_str(text)
+                .map_err(|e| FormatError::ParseError(format!("Invalid YAML: {}", e)))?;
+            if options.sort_keys {
+                sort_yaml(&mut value);
+            }
+            serde_yaml::to_string(&value).map_err(|e| {
+                FormatError::SerializationError(format!("Error formatting YAML: {}", e))
+            })?
+        }
+        FileFormat::Toml => {
+            let mut document: DocumentMut = text
+                .parse()
+                .map_err(|e| FormatError::ParseError(format!("Invalid TOML: {}", e)))?;
+            format_toml_table(document.as_table_mut(), options);
+            let trailing = document.trailing().as_str().map(normalize_prefix);
+            if let Some(trailing) = trailing {
+                document.set_trailing(trailing.trim_end_matches('\n'));
+            }
+            let out = document.to_string();
+            format!("{}\n", out.trim_start_matches('\n').trim_end())
+        }
+        other => {
+            return Err(FormatError::InvalidFormat(format!(
+                "fmt supports json, jsonl, yaml and toml, not {}",
+                other
+            )));
+        }
+    };
+
+    Ok(formatted.into_bytes())
+}
+
+// private helper funktionen
+
+/// JSON als `serde_yaml::Value`, damit die Key-Reihenfolge erhalten bleibt.
+fn parse_json(text: &str, options: &FmtOptions) -> Result<YamlValue, FormatError> {
+    let mut value: YamlValue = serde_json::from_str(text)
+        .map_err(|e| FormatError::ParseError(format!("Invalid JSON: {}", e)))?;
+    if options.sort_keys {
+        sort_yaml(&mut value);
+    }
+    Ok(value)
+}
+
+fn to_json(value: &YamlValue, indent: Option<usize>) -> Result<String, FormatError> {
+    let mut out = Vec::new();
+    let result = match indent {
+        Some(indent) => {
+            let indent = vec![b' '; indent];
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+            value.serialize(&mut serde_json::Serializer::with_formatter(
+                &mut out, formatter,
+            ))
+        }
+        None => value.serialize(&mut serde_json::Serializer::new(&mut out)),
+    };
+    result.map_err(|e| FormatError::SerializationError(format!("Error formatting JSON: {}", e)))?;
+    String::from_utf8(out)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting JSON: {}", e)))
+}
+
+/// Sucht Kommentare und Anker/Aliase, die beim Neuschreiben über `serde_yaml` verloren
+/// gingen. Gibt die Art des Fundes und die Zeile (1-basiert) zurück.
+///
+/// Ein einfacher Scanner: Quotes (auch über mehrere Zeilen) und Block-Scalars (`|`, `>`)
+/// werden übersprungen, damit `#`, `&` und `*` darin nicht zählen.
+fn yaml_lossy_construct(text: &str) -> Option<(&'static str, usize)> {
+    let mut quote: Option<char> = None;
+    // einrückung der zeile, die den block-scalar eröffnet hat
+    let mut block: Option<usize> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let indent = line.len() - line.trim_start().len();
+        if let Some(block_indent) = block {
+            if line.trim().is_empty() || indent > block_indent {
+                continue;
+            }
+            block = None;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut j = 0;
+        while j < chars.len() {
+            let c = chars[j];
+            match quote {
+                Some('\'') if c == '\'' => {
+                    // '' ist ein escapetes quote
+                    if chars.get(j + 1) == Some(&'\'') {
+                        j += 1;
+                    } else {
+                        quote = None;
+                    }
+                }
+                Some('"') if c == '\\' => j += 1,
+                Some('"') if c == '"' => quote = None,
+                Some(_) => {}
+                None => {
+                    let after_space = j == 0 || chars[j - 1].is_whitespace();
+                    // erstes zeichen eines werts: zeilenanfang oder nach `: `, `- `, `[`, `{`, `,`, `? `
+                    let token_start = chars[..j]
+                        .iter()
+                        .rev()
+                        .find(|c| !c.is_whitespace())
+                        .is_none_or(|prev| ":-[{,?".contains(*prev));
+                    let next = chars.get(j + 1).copied();
+                    match c {
+                        '#' if after_space => return Some(("comment", i + 1)),
+                        '\'' | '"' if token_start => quote = Some(c),
+                        '&' | '*'
+                            if token_start
+                                && next.is_some_and(|n| !n.is_whitespace() && !",[]{}".contains(n)) =>
+                        {
+                            let what = if c == '&' { "anchor" } else { "alias" };
+                            return Some((what, i + 1));
+                        }
+                        '|' | '>' if token_start => block = Some(indent),
+                        _ => {}
+                    }
+                }
+            }
+            j += 1;
+        }
+    }
+
+    None
+}
+
+fn sort_yaml(value: &mut YamlValue) {
+    match value {
+        YamlValue::Mapping(mapping) => {
+            let mut entries: Vec<(YamlValue, YamlValue)> =
+                std::mem::take(mapping).into_iter().collect();
+            entries.sort_by_key(|(key, _)| sort_key(key));
+            *mapping = entries
+                .into_iter()
+                .map(|(key, mut value)| {
+                    sort_yaml(&mut value);
+                    (key, value)
+                })
+                .collect::<Mapping>();
+        }
+        YamlValue::Sequence(items) => items.iter_mut().for_each(sort_yaml),
+        YamlValue::Tagged(tagged) => sort_yaml(&mut tagged.value),
+        _ => {}
+    }
+}
+
+fn sort_key(key: &YamlValue) -> String {
+    match key {
+        YamlValue::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default(),
+    }
+}
+
+fn format_toml_table(table: &mut Table, options: &FmtOptions) {
+    if options.sort_keys {
+        table.sort_values();
+    }
+    if let Some(prefix) = table.decor().prefix().and_then(|p| p.as_str()) {
+        let prefix = normalize_prefix(prefix);
+        table.decor_mut().set_prefix(prefix);
+    }
+    if let Some(suffix) = table.decor().suffix().and_then(|s| s.as_str()) {
+        let suffix = normalize_suffix(suffix);
+        table.decor_mut().set_suffix(suffix);
+    }
+
+    for (mut key, item) in table.iter_mut() {
+        if let Some(prefix) = key.leaf_decor().prefix().and_then(|p| p.as_str()) {
+            let prefix = normalize_prefix(prefix);
+            key.leaf_decor_mut().set_prefix(prefix);
+        }
+        match item {
+            Item::Value(value) => {
+                key.leaf_decor_mut().set_suffix(" ");
+                let suffix = value
+                    .decor()
+                    .suffix()
+                    .and_then(|s| s.as_str())
+                    .map(normalize_suffix);
+                let decor = value.decor_mut();
+                decor.set_prefix(" ");
+                if let Some(suffix) = suffix {
+                    decor.set_suffix(suffix);
+                }
+            }
+            Item::Table(table) => format_toml_table(table, options),
+            Item::ArrayOfTables(tables) => {
+                for table in tables.iter_mut() {
+                    format_toml_table(table, options);
+                }
+            }
+            Item::None => {}
+        }
+    }
+}
+
+/// Kommentare und Leerzeilen vor einem Eintrag: ohne Einrückung, höchstens eine Leerzeile.
+fn normalize_prefix(raw: &str) -> String {
+    let mut out = String::new();
+    let mut blank = false;
+    // das letzte stück ist die einrückung vor dem key und fällt weg
+    let lines: Vec<&str> = raw.split('\n').collect();
+    for line in &lines[..lines.len() - 1] {
+        let line = line.trim();
+        if line.is_empty() {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+        } else {
+            out.push_str(line);
+            out.push('\n');
+            blank = false;
+        }
+    }
+    out
+}
+
+/// Text nach einem Wert: ein Leerzeichen vor einem Kommentar, sonst nichts.
+fn normalize_suffix(raw: &str) -> String {
+    match raw.trim() {
+        "" => String::new(),
+        comment => format!(" {}", comment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt(format: FileFormat, input: &str, options: &FmtOptions) -> String {
+        String::from_utf8(format_bytes(format, input.as_bytes(), options).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_fmt_json_keeps_order_and_indent() {
+        let input = r#"{"b": 1, "a": {"y": [1,2], "x": null}}"#;
+        assert_eq!(
+            fmt(FileFormat::Json, input, &FmtOptions::default()),
+            "{\n  \"b\": 1,\n  \"a\": {\n    \"y\": [\n      1,\n      2\n    ],\n    \"x\": null\n  }\n}\n"
+        );
+
+        let options = FmtOptions {
+            indent: 4,
+            sort_keys: true,
+        };
+        assert_eq!(
+            fmt(FileFormat::Json, r#"{"b": 1, "a": 2}"#, &options),
+            "{\n    \"a\": 2,\n    \"b\": 1\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_fmt_jsonl_and_yaml() {
+        let options = FmtOptions {
+            sort_keys: true,
+            ..FmtOptions::default()
+        };
+        assert_eq!(
+            fmt(
+                FileFormat::Jsonl,
+                "{ \"b\": 1, \"a\": 2 }\n\n[1, 2]\n",
+                &options
+            ),
+            "{\"a\":2,\"b\":1}\n[1,2]\n"
+        );
+        assert_eq!(
+            fmt(
+                FileFormat::Yaml,
+                "b:   1\na:\n    - x\n",
+                &FmtOptions::default()
+            ),
+            "b: 1\na:\n- x\n"
+        );
+        assert_eq!(
+            fmt(FileFormat::Yaml, "b: 1\na: 2\n", &options),
+            "a: 2\nb: 1\n"
+        );
+    }
+
+    #[test]
+    fn test_fmt_toml_keeps_comments() {
+        let input = "\n# top\ntitle   =  \"x\"   # trailing\n  # before\n  port=1\n\n\n\n[server]   # tbl\n  host =  \"h\"\n";
+        assert_eq!(
+            fmt(FileFormat::Toml, input, &FmtOptions::default()),
+            "# top\ntitle = \"x\" # trailing\n# before\nport = 1\n\n[server] # tbl\nhost = \"h\"\n"
+        );
+    }
+
+    #[test]
+    fn test_fmt_yaml_refuses_comments_and_anchors() {
+        let options = FmtOptions::default();
+        for (input, expected) in [
+            ("# database settings
+host: localhost
+", "comment at line 1"),
+            ("host: localhost # primary
+", "comment at line 1"),
+            ("base: &b {x: 1}
+other: *b
+", "anchor at line 1"),
+            ("list:
+  - *b
+", "alias at line 2"),
+        ] {
+            let err = format_bytes(FileFormat::Yaml, input.as_bytes(), &options).unwrap_err();
+            assert!(matches!(err, FormatError::InvalidFormat(_)));
+            assert!(err.to_string().contains(expected), "{}: {}", input, err);
+        }
+
+        // `#`, `&` und `*` in strings und block-scalars sind kein kommentar bzw. anker
+        let input = "url: \"http://x/#top\"
+color: 'a # b'
+id: a#b
+expr: 2 * 3
+script: |
+  # not a comment
+  echo &x
+next: ok
+";
+        assert_eq!(
+            fmt(FileFormat::Yaml, input, &options),
+            "url: http://x/#top\ncolor: 'a # b'\nid: a#b\nexpr: 2 * 3\nscript: |\n  # not a comment\n  echo &x\nnext: ok\n"
+        );
+    }
+
+    #[test]
+    fn test_fmt_is_idempotent() {
+        let options = FmtOptions::default();
+        for (format, input) in [
+            (FileFormat::Json, r#"{"a": [1, {"b": true}]}"#),
+            (FileFormat::Yaml, "a:\n  - 1\n  - b: true\n"),
+            (FileFormat::Toml, "a = 1\n\n[b]\nc = [1, 2]\n"),
+        ] {
+            let once = fmt(format, input, &options);
+            assert_eq!(fmt(format, &once, &options), once);
+        }
+    }
+
+    #[test]
+    fn test_fmt_unsupported_format() {
+        let err = format_bytes(FileFormat::Csv, b"a,b\n", &FmtOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("fmt supports"));
+    }
+}
@@ -1,14 +1,28 @@
 //! FileFormat Enum: Kern der Konvertierungsarchitektur.
 //!
-//! Repräsentiert die vier unterstützten Formate und stellt eine
-//! zentrale `convert()`-Methode bereit, die alle 16 Kombinationen abdeckt.
+//! Repräsentiert die unterstützten Formate und stellt eine
+//! zentrale `convert()`-Methode bereit, die alle Kombinationen abdeckt.
 
 use std::str::FromStr;
 use crate::error::FormatError;
 use crate::formats::json::{json_to_json_string, json_to_toml_string, json_to_yaml_string, json_to_csv_string};
 use crate::formats::toml::{toml_to_json_string, toml_to_toml_string, toml_to_yaml_string, toml_to_csv_string};
 use crate::formats::yaml::{yaml_to_json_string, yaml_to_toml_string, yaml_to_yaml_string, yaml_to_csv_string};
-use crate::formats::csv::{csv_to_json_string, csv_to_toml_string, csv_to_yaml_string, csv_to_csv_string};
+use crate::formats::csv::{csv_to_json_string, csv_to_toml_string, csv_to_yaml_string, csv_to_csv_string, parse_csv_to_json_values};
+use crate::formats::csv::value_to_csv_string;
+use crate::formats::json::{parse_json, value_to_json_string};
+use crate::formats::fixed_width::parse_fixed_width;
+use crate::formats::hcl::{parse_hcl, value_to_hcl_string};
+use crate::formats::hocon::parse_hocon;
+use crate::formats::jsonl::{parse_jsonl, value_to_jsonl_string};
+use crate::formats::logfmt::{parse_logfmt, value_to_logfmt_string};
+use crate::formats::plist::{parse_plist, value_to_plist_bytes};
+use crate::formats::sql::value_to_sql_string;
+use crate::formats::toml::{parse_toml, value_to_toml_string};
+use crate::formats::xlsx::{parse_xlsx, value_to_xlsx_bytes};
+use crate::formats::yaml::{parse_yaml, value_to_yaml_string};
+use crate::options::ConvertOptions;
+use serde_json::Value as JsonValue;
 
 /// Unterstützte Datenformate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,13 +31,28 @@ pub enum FileFormat {
     Toml,
     Yaml,
     Csv,
+    /// Nur als Zielformat: `CREATE TABLE` + `INSERT`-Statements.
+    Sql,
+    /// Binärformat, nur über `convert_bytes` verfügbar.
+    Xlsx,
+    /// Apple Property List, XML oder binär (über `ConvertOptions::plist`).
+    Plist,
+    /// Zeilenorientiert: `key=value` Paare, ein Record pro Zeile.
+    Logfmt,
+    /// JSON Lines: ein JSON-Wert pro Zeile.
+    Jsonl,
+    /// Nur als Quellformat: Spalten fester Breite (über `ConvertOptions::fixed_width`).
+    FixedWidth,
+    /// HashiCorp Configuration Language (z.b. Terraform-Variablen).
+    Hcl,
+    /// Nur als Quellformat: HOCON mit Substitutionen und Includes (über `ConvertOptions::hocon`).
+    Hocon,
 }
 
 impl FileFormat {
     /// Konvertiert einen Input-String vom aktuellen Format in das Zielformat.
     ///
-    /// Alle 16 Kombinationen werden per Pattern Matching abgedeckt.
-    
+    /// Alle Kombinationen werden per Pattern Matching abgedeckt.
     pub fn convert(&self, input: &str, output_format: FileFormat) -> Result<String, FormatError> {
         match (self, output_format) {
             // für json als quelle
@@ -49,7 +78,211 @@ impl FileFormat {
             (FileFormat::Csv, FileFormat::Toml) => csv_to_toml_string(input),
             (FileFormat::Csv, FileFormat::Yaml) => csv_to_yaml_string(input),
             (FileFormat::Csv, FileFormat::Csv) => csv_to_csv_string(input),
+
+            // binärformate laufen über convert_bytes
+            (FileFormat::Xlsx, _) | (_, FileFormat::Xlsx) => Err(Self::binary_error()),
+
+            // alle weiteren formate (sql, plist, ...) über das gemeinsame datenmodell
+            _ => {
+                let value = self.parse_value(input)?;
+                let output = output_format.serialize_value(&value, &ConvertOptions::default())?;
+                String::from_utf8(output).map_err(|e| {
+                    FormatError::SerializationError(format!("Error converting to UTF-8: {}", e))
+                })
+            }
+        }
+    }
+
+    /// Konvertiert Input-Bytes vom aktuellen Format in das Zielformat.
+    ///
+    /// Unterstützt zusätzlich Binärformate (XLSX, binäre plist) und format-spezifische Optionen.
+    /// Zwischen JSON, TOML, YAML und CSV wird ohne `select` die direkte String-Konvertierung
+    /// von `convert()` genutzt.
+    pub fn convert_bytes(
+        &self,
+        input: &[u8],
+        output_format: FileFormat,
+        options: &ConvertOptions,
+    ) -> Result<Vec<u8>, FormatError> {
+        if options.select.is_none() && self.has_direct_conversion(output_format) {
+            return self
+                .convert(Self::utf8(input)?, output_format)
+                .map(String::into_bytes);
+        }
+
+        let mut value = self.parse_bytes(input, options)?;
+        if let Some(query) = &options.select {
+            value = query.select(&value)?;
+        }
+        output_format.serialize_value(&value, options)
+    }
+
+    /// Parst einen Input-String im aktuellen Format zu einem `serde_json::Value`.
+    ///
+    /// Damit arbeiten formatübergreifende Funktionen auf einem gemeinsamen Datenmodell.
+    pub fn parse_value(&self, input: &str) -> Result<JsonValue, FormatError> {
+        match self {
+            FileFormat::Json => parse_json(input),
+            FileFormat::Toml => parse_toml(input),
+            FileFormat::Yaml => parse_yaml(input),
+            FileFormat::Csv => Ok(JsonValue::Array(parse_csv_to_json_values(input)?)),
+            FileFormat::Sql => Err(Self::sql_input_error()),
+            FileFormat::Xlsx => Err(Self::binary_error()),
+            FileFormat::Plist => parse_plist(input.as_bytes()),
+            FileFormat::Logfmt => parse_logfmt(input),
+            FileFormat::Jsonl => parse_jsonl(input),
+            FileFormat::FixedWidth => parse_fixed_width(input, &Default::default()),
+            FileFormat::Hcl => parse_hcl(input),
+            FileFormat::Hocon => parse_hocon(input, &Default::default()),
+        }
+    }
+
+    /// Wie `parse_value`, aber für Input-Bytes inklusive Binärformate.
+    pub fn parse_bytes(&self, input: &[u8], options: &ConvertOptions) -> Result<JsonValue, FormatError> {
+        match self {
+            FileFormat::Xlsx => parse_xlsx(input, &options.xlsx),
+            FileFormat::Plist => parse_plist(input),
+            FileFormat::FixedWidth => parse_fixed_width(Self::utf8(input)?, &options.fixed_width),
+            FileFormat::Hocon => parse_hocon(Self::utf8(input)?, &options.hocon),
+            _ => self.parse_value(Self::utf8(input)?),
+        }
+    }
+
+    /// Serialisiert einen `serde_json::Value` in das aktuelle Format.
+    pub fn serialize_value(
+        &self,
+        value: &JsonValue,
+        options: &ConvertOptions,
+    ) -> Result<Vec<u8>, FormatError> {
+        let text = match self {
+            FileFormat::Json => value_to_json_string(value)?,
+            FileFormat::Toml => value_to_toml_string(value)?,
+            FileFormat::Yaml => value_to_yaml_string(value)?,
+            FileFormat::Csv => value_to_csv_string(value)?,
+            FileFormat::Sql => value_to_sql_string(value, &options.sql)?,
+            FileFormat::Logfmt => value_to_logfmt_string(value)?,
+            FileFormat::Jsonl => value_to_jsonl_string(value)?,
+            FileFormat::Hcl => value_to_hcl_string(value)?,
+            FileFormat::FixedWidth => return Err(Self::fixed_width_output_error()),
+            FileFormat::Hocon => return Err(Self::hocon_output_error()),
+            FileFormat::Xlsx => return value_to_xlsx_bytes(value),
+            FileFormat::Plist => return value_to_plist_bytes(value, &options.plist),
+        };
+        Ok(text.into_bytes())
+    }
+
+    /// gibt zurück, ob das format binär ist und nicht als string verarbeitet werden kann
+    pub fn is_binary(&self) -> bool {
+        matches!(self, FileFormat::Xlsx)
+    }
+
+    /// Erkennt das Format anhand des Inhalts, z.b. für stdin ohne Dateiendung.
+    ///
+    /// Binäre Signaturen (XLSX, binäre plist) werden zuerst geprüft, danach wird vom
+    /// strengsten zum tolerantesten Format geparst: JSON, JSON Lines, TOML, logfmt,
+    /// CSV und zuletzt YAML. `None`, wenn nichts davon passt (z.b. HCL oder HOCON).
+    pub fn sniff(input: &[u8]) -> Option<FileFormat> {
+        if input.starts_with(b"PK\x03\x04") {
+            return Some(FileFormat::Xlsx);
+        }
+        if input.starts_with(b"bplist") {
+            return Some(FileFormat::Plist);
+        }
+
+        let text = std::str::from_utf8(input).ok()?;
+        let text = text.trim_start_matches('\u{feff}').trim();
+        if text.is_empty() {
+            return None;
+        }
+        if text.starts_with('<') {
+            return text.contains("<plist").then_some(FileFormat::Plist);
+        }
+
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        if text.starts_with(['{', '[']) {
+            if serde_json::from_str::<JsonValue>(text).is_ok() {
+                return Some(FileFormat::Json);
+            }
+            if lines
+                .iter()
+                .all(|line| serde_json::from_str::<JsonValue>(line).is_ok())
+            {
+                return Some(FileFormat::Jsonl);
+            }
+        }
+        if parse_toml(text).is_ok() {
+            return Some(FileFormat::Toml);
         }
+        if lines.iter().all(|line| looks_like_logfmt(line)) {
+            return Some(FileFormat::Logfmt);
+        }
+        if looks_like_csv(text, lines.len()) {
+            return Some(FileFormat::Csv);
+        }
+        match parse_yaml(text) {
+            Ok(JsonValue::Object(_) | JsonValue::Array(_)) => Some(FileFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// gibt zurück, ob es eine direkte string-zu-string konvertierung gibt
+    fn has_direct_conversion(&self, output_format: FileFormat) -> bool {
+        let is_direct = |format: &FileFormat| {
+            matches!(
+                format,
+                FileFormat::Json | FileFormat::Toml | FileFormat::Yaml | FileFormat::Csv
+            )
+        };
+        is_direct(self) && is_direct(&output_format)
+    }
+
+    fn utf8(input: &[u8]) -> Result<&str, FormatError> {
+        std::str::from_utf8(input)
+            .map_err(|e| FormatError::ParseError(format!("Input is not valid UTF-8: {}", e)))
+    }
+
+    fn sql_input_error() -> FormatError {
+        FormatError::InvalidFormat("SQL is only supported as output format".to_string())
+    }
+
+    fn fixed_width_output_error() -> FormatError {
+        FormatError::InvalidFormat("Fixed-width is only supported as input format".to_string())
+    }
+
+    fn hocon_output_error() -> FormatError {
+        FormatError::InvalidFormat("HOCON is only supported as input format".to_string())
+    }
+
+    fn binary_error() -> FormatError {
+        FormatError::InvalidFormat(
+            "Binary formats (xlsx) must be converted with convert_bytes".to_string(),
+        )
+    }
+
+    /// Alle Formate, z.b. für die möglichen Werte von `--from`/`--to` in der CLI.
+    pub const ALL: [FileFormat; 12] = [
+        FileFormat::Json,
+        FileFormat::Toml,
+        FileFormat::Yaml,
+        FileFormat::Csv,
+        FileFormat::Sql,
+        FileFormat::Xlsx,
+        FileFormat::Plist,
+        FileFormat::Logfmt,
+        FileFormat::Jsonl,
+        FileFormat::FixedWidth,
+        FileFormat::Hcl,
+        FileFormat::Hocon,
+    ];
+
+    /// gibt zurück, ob das format gelesen werden kann (SQL ist nur zielformat)
+    pub fn can_read(&self) -> bool {
+        !matches!(self, FileFormat::Sql)
+    }
+
+    /// gibt zurück, ob das format geschrieben werden kann (Fixed-Width und HOCON sind nur quellformate)
+    pub fn can_write(&self) -> bool {
+        !matches!(self, FileFormat::FixedWidth | FileFormat::Hocon)
     }
 
     /// gibt den format-namen als string zurück
@@ -59,15 +292,63 @@ impl FileFormat {
             FileFormat::Toml => "toml",
             FileFormat::Yaml => "yaml",
             FileFormat::Csv => "csv",
+            FileFormat::Sql => "sql",
+            FileFormat::Xlsx => "xlsx",
+            FileFormat::Plist => "plist",
+            FileFormat::Logfmt => "logfmt",
+            FileFormat::Jsonl => "jsonl",
+            FileFormat::FixedWidth => "fixed-width",
+            FileFormat::Hcl => "hcl",
+            FileFormat::Hocon => "hocon",
         }
     }
 
     /// das gibt die standard-dateierweiterung für das format zurück.
     pub fn extension(&self) -> &'static str {
-        self.as_str()
+        match self {
+            FileFormat::FixedWidth => "fwf",
+            FileFormat::Hocon => "conf",
+            other => other.as_str(),
+        }
     }
 }
 
+// private helper funktionen für FileFormat::sniff
+
+/// Eine logfmt-Zeile beginnt mit `key=`, der Key besteht nur aus Wortzeichen.
+fn looks_like_logfmt(line: &str) -> bool {
+    let first = line.split_whitespace().next().unwrap_or_default();
+    match first.split_once('=') {
+        Some((key, _)) => {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_.-".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// CSV braucht mindestens zwei Zeilen mit gleich vielen (und mindestens zwei) Spalten.
+fn looks_like_csv(text: &str, line_count: usize) -> bool {
+    if line_count < 2 || !text.lines().next().unwrap_or_default().contains(',') {
+        return false;
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(text.as_bytes());
+    let mut width = None;
+    for record in reader.records() {
+        let Ok(record) = record else {
+            return false;
+        };
+        if *width.get_or_insert(record.len()) != record.len() {
+            return false;
+        }
+    }
+    width.is_some_and(|w| w >= 2)
+}
+
 // string in fileformat umwandeln
 
 impl FromStr for FileFormat {
@@ -79,7 +360,15 @@ impl FromStr for FileFormat {
             "toml" => Ok(FileFormat::Toml),
             "yaml" | "yml" => Ok(FileFormat::Yaml),
             "csv" => Ok(FileFormat::Csv),
-            _ => Err(FormatError::ParseError(format!("Unknown format: {}", s))),
+            "sql" => Ok(FileFormat::Sql),
+            "xlsx" => Ok(FileFormat::Xlsx),
+            "plist" => Ok(FileFormat::Plist),
+            "logfmt" => Ok(FileFormat::Logfmt),
+            "jsonl" | "ndjson" => Ok(FileFormat::Jsonl),
+            "fixed-width" | "fixed" | "fwf" => Ok(FileFormat::FixedWidth),
+            "hcl" | "tf" | "tfvars" => Ok(FileFormat::Hcl),
+            "hocon" | "conf" => Ok(FileFormat::Hocon),
+            _ => Err(FormatError::InvalidFormat(format!("Unknown format: {}", s))),
         }
     }
 }
@@ -106,6 +395,17 @@ mod tests {
         assert_eq!("csv".parse::<FileFormat>().unwrap(), FileFormat::Csv);
     }
 
+    #[test]
+    fn test_all_formats_roundtrip() {
+        for format in FileFormat::ALL {
+            assert_eq!(format.as_str().parse::<FileFormat>().unwrap(), format);
+        }
+        let readable = FileFormat::ALL.iter().filter(|f| f.can_read()).count();
+        let writable = FileFormat::ALL.iter().filter(|f| f.can_write()).count();
+        assert_eq!((readable, writable), (11, 10));
+        assert!(!FileFormat::Sql.can_read() && !FileFormat::Hocon.can_write());
+    }
+
     #[test]
     fn test_from_str_uppercase() {
         assert_eq!("JSON".parse::<FileFormat>().unwrap(), FileFormat::Json);
@@ -122,7 +422,7 @@ mod tests {
     #[test]
     fn test_from_str_unknown_format() {
         let err = "xml".parse::<FileFormat>().unwrap_err();
-        assert!(matches!(err, FormatError::ParseError(_)));
+        assert!(matches!(err, FormatError::InvalidFormat(_)));
         assert!(err.to_string().contains("Unknown format"));
     }
 
@@ -210,6 +510,152 @@ mod tests {
         assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
     }
 
+    #[test]
+    fn test_convert_csv_to_sql() {
+        let input = "name,age\nAlice,30";
+        let result = FileFormat::Csv.convert(input, FileFormat::Sql).unwrap();
+        assert!(result.contains("CREATE TABLE"));
+        assert!(result.contains("\"age\" INTEGER"));
+        assert!(result.contains("(30, 'Alice')"));
+    }
+
+    #[test]
+    fn test_convert_from_sql_fails() {
+        let result = FileFormat::Sql.convert("SELECT 1;", FileFormat::Json);
+        assert!(matches!(result.unwrap_err(), FormatError::InvalidFormat(_)));
+    }
+
+    #[test]
+    fn test_convert_xlsx_requires_bytes() {
+        let result = FileFormat::Json.convert("[]", FileFormat::Xlsx);
+        assert!(matches!(result.unwrap_err(), FormatError::InvalidFormat(_)));
+    }
+
+    #[test]
+    fn test_convert_bytes_xlsx_roundtrip() {
+        let options = ConvertOptions::default();
+        let input = br#"[{"name":"Alice","age":30}]"#;
+        let xlsx = FileFormat::Json
+            .convert_bytes(input, FileFormat::Xlsx, &options)
+            .unwrap();
+        let csv = FileFormat::Xlsx
+            .convert_bytes(&xlsx, FileFormat::Csv, &options)
+            .unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "age,name\n30,Alice\n");
+    }
+
+    #[test]
+    fn test_convert_bytes_text_formats() {
+        let result = FileFormat::Yaml
+            .convert_bytes(b"name: test", FileFormat::Json, &ConvertOptions::default())
+            .unwrap();
+        assert!(String::from_utf8(result).unwrap().contains("\"name\""));
+    }
+
+    #[test]
+    fn test_convert_bytes_invalid_utf8_fails() {
+        let result = FileFormat::Json.convert_bytes(&[0xff, 0xfe], FileFormat::Yaml, &ConvertOptions::default());
+        assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
+    }
+
+    #[test]
+    fn test_convert_json_to_plist_xml() {
+        let result = FileFormat::Json
+            .convert(r#"{"name":"Demo"}"#, FileFormat::Plist)
+            .unwrap();
+        assert!(result.contains("<key>name</key>"));
+        assert!(result.contains("<string>Demo</string>"));
+    }
+
+    #[test]
+    fn test_convert_bytes_binary_plist_to_yaml() {
+        let options = ConvertOptions {
+            plist: crate::formats::plist::PlistOptions {
+                binary: true,
+                ..Default::default()
+            },
+            ..ConvertOptions::default()
+        };
+        let plist = FileFormat::Json
+            .convert_bytes(br#"{"build":42}"#, FileFormat::Plist, &options)
+            .unwrap();
+        assert!(plist.starts_with(b"bplist00"));
+        let yaml = FileFormat::Plist
+            .convert_bytes(&plist, FileFormat::Yaml, &options)
+            .unwrap();
+        assert_eq!(String::from_utf8(yaml).unwrap(), "build: 42\n");
+    }
+
+    #[test]
+    fn test_convert_logfmt_to_jsonl() {
+        let input = "level=info status=200\nlevel=error msg=\"boom\"";
+        let result = FileFormat::Logfmt.convert(input, FileFormat::Jsonl).unwrap();
+        assert_eq!(
+            result,
+            "{\"level\":\"info\",\"status\":200}\n{\"level\":\"error\",\"msg\":\"boom\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_logfmt_to_csv_unions_keys() {
+        let input = "a=1 b=2\nc=3";
+        let result = FileFormat::Logfmt.convert(input, FileFormat::Csv).unwrap();
+        assert_eq!(result, "a,b,c\n1,2,\n,,3\n");
+    }
+
+    #[test]
+    fn test_convert_fixed_width_to_csv() {
+        let input = "ID  NAME\n1   Alice\n2   Bob";
+        let result = FileFormat::FixedWidth.convert(input, FileFormat::Csv).unwrap();
+        assert_eq!(result, "ID,NAME\n1,Alice\n2,Bob\n");
+    }
+
+    #[test]
+    fn test_convert_to_fixed_width_fails() {
+        let result = FileFormat::Json.convert("[]", FileFormat::FixedWidth);
+        assert!(matches!(result.unwrap_err(), FormatError::InvalidFormat(_)));
+    }
+
+    #[test]
+    fn test_from_str_hcl_and_hocon() {
+        assert_eq!("tfvars".parse::<FileFormat>().unwrap(), FileFormat::Hcl);
+        assert_eq!("tf".parse::<FileFormat>().unwrap(), FileFormat::Hcl);
+        assert_eq!("conf".parse::<FileFormat>().unwrap(), FileFormat::Hocon);
+        assert_eq!("hocon".parse::<FileFormat>().unwrap(), FileFormat::Hocon);
+        assert_eq!(FileFormat::Hocon.as_str(), "hocon");
+        assert_eq!(FileFormat::Hocon.extension(), "conf");
+    }
+
+    #[test]
+    fn test_fixed_width_name_and_aliases() {
+        assert_eq!(FileFormat::FixedWidth.as_str(), "fixed-width");
+        assert_eq!(FileFormat::FixedWidth.extension(), "fwf");
+        for alias in ["fixed-width", "fixed", "fwf"] {
+            assert_eq!(alias.parse::<FileFormat>().unwrap(), FileFormat::FixedWidth);
+        }
+    }
+
+    #[test]
+    fn test_convert_hocon_to_hcl() {
+        let input = "base = /srv\napp { dir = ${base}/app, workers = 4 }";
+        let result = FileFormat::Hocon.convert(input, FileFormat::Hcl).unwrap();
+        assert!(result.contains("base = \"/srv\""));
+        assert!(result.contains("app {\n  dir = \"/srv/app\"\n  workers = 4\n}"));
+    }
+
+    #[test]
+    fn test_convert_hcl_to_json() {
+        let input = "name = \"web\"\nlimits {\n  cpu = 2\n}\n";
+        let result = FileFormat::Hcl.convert(input, FileFormat::Json).unwrap();
+        assert!(result.contains("\"cpu\": 2"));
+    }
+
+    #[test]
+    fn test_convert_to_hocon_fails() {
+        let result = FileFormat::Json.convert("{}", FileFormat::Hocon);
+        assert!(matches!(result.unwrap_err(), FormatError::InvalidFormat(_)));
+    }
+
     #[test]
     fn test_convert_all_identity() {
         assert!(FileFormat::Json.convert(r#"{"a":1}"#, FileFormat::Json).is_ok());
@@ -217,4 +663,32 @@ mod tests {
         assert!(FileFormat::Yaml.convert("name: test", FileFormat::Yaml).is_ok());
         assert!(FileFormat::Csv.convert("a,b\n1,2", FileFormat::Csv).is_ok());
     }
+
+    #[test]
+    fn test_sniff_text_formats() {
+        let sniff = |s: &str| FileFormat::sniff(s.as_bytes());
+        assert_eq!(sniff("  {\"a\": 1}\n"), Some(FileFormat::Json));
+        assert_eq!(sniff("[1, 2]"), Some(FileFormat::Json));
+        assert_eq!(sniff("{\"a\":1}\n{\"a\":2}\n"), Some(FileFormat::Jsonl));
+        assert_eq!(sniff("[server]\nport = 80\n"), Some(FileFormat::Toml));
+        assert_eq!(
+            sniff("level=info msg=\"started\"\nlevel=warn msg=slow\n"),
+            Some(FileFormat::Logfmt)
+        );
+        assert_eq!(sniff("id,name\n1,ann\n2,bob\n"), Some(FileFormat::Csv));
+        assert_eq!(sniff("name: app\nports:\n  - 80\n"), Some(FileFormat::Yaml));
+        assert_eq!(
+            sniff("<?xml version=\"1.0\"?>\n<plist version=\"1.0\"><dict/></plist>"),
+            Some(FileFormat::Plist)
+        );
+    }
+
+    #[test]
+    fn test_sniff_binary_and_unknown() {
+        assert_eq!(FileFormat::sniff(b"PK\x03\x04rest"), Some(FileFormat::Xlsx));
+        assert_eq!(FileFormat::sniff(b"bplist00rest"), Some(FileFormat::Plist));
+        assert_eq!(FileFormat::sniff(b""), None);
+        assert_eq!(FileFormat::sniff(b"just some words"), None);
+        assert_eq!(FileFormat::sniff(&[0xff, 0xfe]), None);
+    }
 }
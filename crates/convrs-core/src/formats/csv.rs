@@ -1,10 +1,41 @@
 //! CSV-Konvertierungen: String-zu-String Funktionen.
 
 use crate::error::FormatError;
-use crate::formats::utils::json_to_toml_value;
+use crate::formats::utils::{flatten_json_values, json_to_toml_value, json_value_to_string};
 use csv::ReaderBuilder;
 use serde_json::Value as JsonValue;
 
+/// Serialisiert einen `serde_json::Value` zu CSV String.
+///
+/// Verschachtelte Objekte werden wie bei `json_to_csv_string` geflattet.
+pub fn value_to_csv_string(value: &JsonValue) -> Result<String, FormatError> {
+    let (headers, rows) = value_to_table(value)?;
+
+    if rows.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    writer
+        .write_record(&headers)
+        .map_err(|e| FormatError::SerializationError(format!("Error writing CSV header: {}", e)))?;
+
+    for row in &rows {
+        let fields: Vec<String> = row.iter().map(json_value_to_string).collect();
+        writer
+            .write_record(&fields)
+            .map_err(|e| FormatError::SerializationError(format!("Error writing CSV row: {}", e)))?;
+    }
+
+    let data = writer
+        .into_inner()
+        .map_err(|e| FormatError::SerializationError(format!("Error finishing CSV: {}", e)))?;
+
+    String::from_utf8(data)
+        .map_err(|e| FormatError::SerializationError(format!("Error converting to UTF-8: {}", e)))
+}
+
 /// Konvertiert CSV String zu JSON String.
 pub fn csv_to_json_string(input: &str) -> Result<String, FormatError> {
     let records = parse_csv_to_json_values(input)?;
@@ -87,10 +118,8 @@ pub fn csv_to_csv_string(input: &str) -> Result<String, FormatError> {
         .map_err(|e| FormatError::SerializationError(format!("Error converting to UTF-8: {}", e)))
 }
 
-// private helper funktionen
-
 /// Parst CSV String zu einer Liste von JSON-Objekten.
-fn parse_csv_to_json_values(input: &str) -> Result<Vec<JsonValue>, FormatError> {
+pub fn parse_csv_to_json_values(input: &str) -> Result<Vec<JsonValue>, FormatError> {
     let lines: Vec<&str> = input.lines().filter(|l| !l.trim().is_empty()).collect();
 
     if lines.is_empty() {
@@ -137,10 +166,68 @@ fn parse_csv_to_json_values(input: &str) -> Result<Vec<JsonValue>, FormatError>
     Ok(records)
 }
 
+/// Gibt die Records eines geparsten Werts zurück: die Elemente eines Arrays
+/// oder ein einzelnes Objekt. Ein `data`-Wrapper (von CSV → TOML) wird entpackt.
+pub(crate) fn value_as_records(value: &JsonValue) -> Option<&[JsonValue]> {
+    let value = match value {
+        JsonValue::Object(obj)
+            if obj.len() == 1 && obj.get("data").is_some_and(|d| d.is_array()) =>
+        {
+            &obj["data"]
+        }
+        other => other,
+    };
+
+    match value {
+        JsonValue::Array(arr) => Some(arr.as_slice()),
+        JsonValue::Object(_) => Some(std::slice::from_ref(value)),
+        _ => None,
+    }
+}
+
+/// Bringt einen geparsten Wert in Tabellenform: Header + typisierte Zeilen.
+///
+/// Objekte werden wie beim CSV-Export geflattet. Die Zellen behalten den Typ des
+/// geparsten Werts; `infer_type` läuft nur beim Lesen von CSV-Text, sodass ein
+/// String wie `"00123"` ein String bleibt.
+pub(crate) fn value_to_table(
+    value: &JsonValue,
+) -> Result<(Vec<String>, Vec<Vec<JsonValue>>), FormatError> {
+    let records = value_as_records(value).ok_or_else(|| {
+        FormatError::SerializationError(
+            "Data must be an array or object for tabular output".to_string(),
+        )
+    })?;
+
+    let flattened: Vec<_> = records.iter().map(|v| flatten_json_values(v, "")).collect();
+
+    let mut all_headers = std::collections::BTreeSet::new();
+    for obj in &flattened {
+        for key in obj.keys() {
+            all_headers.insert(key.clone());
+        }
+    }
+    let headers: Vec<String> = all_headers.into_iter().collect();
+
+    let rows = flattened
+        .iter()
+        .map(|flat_obj| {
+            headers
+                .iter()
+                .map(|h| flat_obj.get(h).cloned().unwrap_or(JsonValue::Null))
+                .collect()
+        })
+        .collect();
+
+    Ok((headers, rows))
+}
+
+// private helper funktionen
+
 /// Versucht den Typ eines CSV-String-Wertes zu erkennen.
 ///
 /// Reihenfolge: Boolean → Integer → Float → String (Fallback).
-fn infer_type(value: &str) -> JsonValue {
+pub(crate) fn infer_type(value: &str) -> JsonValue {
     if value.is_empty() {
         return JsonValue::Null;
     }
@@ -238,6 +325,30 @@ mod tests {
         assert_eq!(infer_type("hello"), JsonValue::String("hello".to_string()));
     }
 
+    #[test]
+    fn test_value_to_table_unwraps_data_and_keeps_types() {
+        let value = serde_json::json!({"data": [{"name": "Alice", "age": 30}, {"name": "Bob"}]});
+        let (headers, rows) = value_to_table(&value).unwrap();
+        assert_eq!(headers, vec!["age", "name"]);
+        assert_eq!(rows[0], vec![JsonValue::Number(30.into()), JsonValue::String("Alice".to_string())]);
+        assert_eq!(rows[1][0], JsonValue::Null);
+    }
+
+    #[test]
+    fn test_value_to_table_keeps_numeric_looking_strings() {
+        let value = serde_json::json!([{"zip": "00123", "flag": "true", "n": 1}]);
+        let (headers, rows) = value_to_table(&value).unwrap();
+        assert_eq!(headers, vec!["flag", "n", "zip"]);
+        assert_eq!(rows[0], vec![serde_json::json!("true"), serde_json::json!(1), serde_json::json!("00123")]);
+    }
+
+    #[test]
+    fn test_value_to_csv_string_flattens() {
+        let value = serde_json::json!([{"user": {"name": "Alice"}, "age": 30}]);
+        let result = value_to_csv_string(&value).unwrap();
+        assert_eq!(result, "age,user_name\n30,Alice\n");
+    }
+
     #[test]
     fn test_infer_type_empty_is_null() {
         assert_eq!(infer_type(""), JsonValue::Null);
@@ -0,0 +1,311 @@
+//! Fixed-Width-Eingabe: Textdateien mit Spalten fester Breite (z.b. Mainframe-Exporte).
+//!
+//! Die Spalten kommen entweder aus einer Spezifikation (Name, Start, Breite, Typ)
+//! oder werden aus der Kopfzeile und den Leerzeichen-Spalten automatisch erkannt.
+//! Das Ergebnis sind dieselben Record-Objekte wie bei `parse_csv_to_json_values`.
+
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::formats::csv::infer_type;
+
+/// Typ einer Fixed-Width-Spalte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixedWidthType {
+    /// Typ wird wie bei CSV per `infer_type` erkannt.
+    #[default]
+    Auto,
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+impl FromStr for FixedWidthType {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(FixedWidthType::Auto),
+            "string" | "str" | "text" => Ok(FixedWidthType::String),
+            "integer" | "int" => Ok(FixedWidthType::Integer),
+            "float" | "number" => Ok(FixedWidthType::Float),
+            "boolean" | "bool" => Ok(FixedWidthType::Boolean),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown column type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Beschreibung einer Spalte: Name, Startposition (1-basiert), Breite und Typ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthColumn {
+    pub name: String,
+    /// Startposition in Zeichen, 1-basiert wie in üblichen Satzbeschreibungen.
+    pub start: usize,
+    pub width: usize,
+    pub column_type: FixedWidthType,
+}
+
+/// Parst eine Spalte im Format `name:start:width[:type]`, z.b. `amount:26:10:float`.
+impl FromStr for FixedWidthColumn {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        if !(3..=4).contains(&parts.len()) || parts[0].is_empty() {
+            return Err(FormatError::InvalidFormat(format!(
+                "Invalid column spec '{}', expected name:start:width[:type]",
+                s
+            )));
+        }
+
+        let number = |part: &str, what: &str| {
+            part.parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| {
+                    FormatError::InvalidFormat(format!(
+                        "Invalid {} '{}' in column spec '{}'",
+                        what, part, s
+                    ))
+                })
+        };
+
+        Ok(FixedWidthColumn {
+            name: parts[0].to_string(),
+            start: number(parts[1], "start")?,
+            width: number(parts[2], "width")?,
+            column_type: match parts.get(3) {
+                Some(t) => t.parse()?,
+                None => FixedWidthType::Auto,
+            },
+        })
+    }
+}
+
+/// Optionen für das Lesen von Fixed-Width-Dateien.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixedWidthOptions {
+    /// Spalten-Spezifikation. Leer: Spalten werden automatisch erkannt.
+    pub columns: Vec<FixedWidthColumn>,
+    /// Überspringt bei expliziter Spezifikation die erste Zeile (Kopfzeile).
+    pub skip_header: bool,
+}
+
+/// Parst eine Fixed-Width-Tabelle zu einer Liste von Record-Objekten.
+pub fn parse_fixed_width(
+    input: &str,
+    options: &FixedWidthOptions,
+) -> Result<JsonValue, FormatError> {
+    let lines: Vec<(usize, Vec<char>)> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line.chars().collect()))
+        .collect();
+
+    if lines.is_empty() {
+        return Err(FormatError::ParseError(
+            "Fixed-width input is empty".to_string(),
+        ));
+    }
+
+    let (columns, data_lines) = if options.columns.is_empty() {
+        (detect_columns(&lines)?, &lines[1..])
+    } else if options.skip_header {
+        (options.columns.clone(), &lines[1..])
+    } else {
+        (options.columns.clone(), &lines[..])
+    };
+
+    let mut records = Vec::new();
+    for (line_no, chars) in data_lines {
+        let mut obj = serde_json::Map::new();
+        for column in &columns {
+            let field = slice_field(chars, column.start - 1, column.width);
+            let value = convert_field(field.trim(), column.column_type).map_err(|msg| {
+                FormatError::ParseError(format!(
+                    "Invalid fixed-width data: line {}, column '{}': {}",
+                    line_no, column.name, msg
+                ))
+            })?;
+            obj.insert(column.name.clone(), value);
+        }
+        records.push(JsonValue::Object(obj));
+    }
+
+    Ok(JsonValue::Array(records))
+}
+
+// private helper funktionen
+
+/// Erkennt Spaltengrenzen: eine Spalte beginnt dort, wo nach mindestens zwei Positionen,
+/// die in allen Zeilen leer sind, wieder Text folgt. Ein einzelnes Leerzeichen trennt
+/// keine Spalten, damit Namen wie `first name` ganz bleiben. Namen kommen aus der Kopfzeile.
+fn detect_columns(lines: &[(usize, Vec<char>)]) -> Result<Vec<FixedWidthColumn>, FormatError> {
+    let max_len = lines
+        .iter()
+        .map(|(_, chars)| chars.len())
+        .max()
+        .unwrap_or(0);
+
+    let is_blank: Vec<bool> = (0..max_len)
+        .map(|pos| {
+            lines
+                .iter()
+                .all(|(_, chars)| chars.get(pos).is_none_or(|c| c.is_whitespace()))
+        })
+        .collect();
+
+    // nur läufe von mindestens zwei leeren positionen zählen als lücke
+    let mut is_gap = vec![false; max_len];
+    let mut pos = 0;
+    while pos < max_len {
+        let run = is_blank[pos..].iter().take_while(|blank| **blank).count();
+        if run >= 2 {
+            is_gap[pos..pos + run].fill(true);
+        }
+        pos += run.max(1);
+    }
+
+    let mut starts = Vec::new();
+    for pos in 0..max_len {
+        if !is_gap[pos] && (pos == 0 || is_gap[pos - 1]) {
+            starts.push(pos);
+        }
+    }
+
+    let header = &lines[0].1;
+    let mut columns = Vec::new();
+    for (i, start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(max_len);
+        let name = slice_field(header, *start, end - start).trim().to_string();
+        if name.is_empty() {
+            return Err(FormatError::ParseError(format!(
+                "Invalid fixed-width header: column at position {} has no name",
+                start + 1
+            )));
+        }
+        columns.push(FixedWidthColumn {
+            name,
+            start: start + 1,
+            width: end - start,
+            column_type: FixedWidthType::Auto,
+        });
+    }
+
+    Ok(columns)
+}
+
+fn slice_field(chars: &[char], start: usize, width: usize) -> String {
+    chars.iter().skip(start).take(width).collect()
+}
+
+fn convert_field(field: &str, column_type: FixedWidthType) -> Result<JsonValue, String> {
+    if field.is_empty() {
+        return Ok(JsonValue::Null);
+    }
+
+    match column_type {
+        FixedWidthType::Auto => Ok(infer_type(field)),
+        FixedWidthType::String => Ok(JsonValue::String(field.to_string())),
+        FixedWidthType::Integer => field
+            .parse::<i64>()
+            .map(|n| JsonValue::Number(n.into()))
+            .map_err(|_| format!("'{}' is not an integer", field)),
+        FixedWidthType::Float => field
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(JsonValue::Number)
+            .ok_or_else(|| format!("'{}' is not a number", field)),
+        FixedWidthType::Boolean => match infer_type(field) {
+            JsonValue::Bool(b) => Ok(JsonValue::Bool(b)),
+            _ => Err(format!("'{}' is not a boolean", field)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "\
+ID   NAME        AMOUNT
+1    Alice       100.50
+2    Bob Smith    20
+";
+
+    #[test]
+    fn test_fixed_width_auto_detect() {
+        let value = parse_fixed_width(REPORT, &FixedWidthOptions::default()).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                {"ID": 1, "NAME": "Alice", "AMOUNT": 100.5},
+                {"ID": 2, "NAME": "Bob Smith", "AMOUNT": 20}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_auto_detect_header_with_space() {
+        let input = "first name   age\nAnn          30\nBob Lee      41\n";
+        let value = parse_fixed_width(input, &FixedWidthOptions::default()).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                {"first name": "Ann", "age": 30},
+                {"first name": "Bob Lee", "age": 41}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_with_spec() {
+        let options = FixedWidthOptions {
+            columns: vec![
+                "id:1:5:string".parse().unwrap(),
+                "name:6:12".parse().unwrap(),
+                "amount:18:6:float".parse().unwrap(),
+            ],
+            skip_header: true,
+        };
+        let value = parse_fixed_width(REPORT, &options).unwrap();
+        assert_eq!(value[0]["id"], "1");
+        assert_eq!(value[1]["name"], "Bob Smith");
+        assert_eq!(value[1]["amount"], 20.0);
+    }
+
+    #[test]
+    fn test_fixed_width_type_error_reports_line() {
+        let options = FixedWidthOptions {
+            columns: vec!["n:1:3:integer".parse().unwrap()],
+            skip_header: false,
+        };
+        let err = parse_fixed_width("12\nab\n", &options).unwrap_err();
+        assert!(err.to_string().contains("line 2, column 'n'"));
+    }
+
+    #[test]
+    fn test_fixed_width_column_spec_parsing() {
+        let column: FixedWidthColumn = "amount:26:10:float".parse().unwrap();
+        assert_eq!(column.start, 26);
+        assert_eq!(column.width, 10);
+        assert_eq!(column.column_type, FixedWidthType::Float);
+        assert!("amount:0:10".parse::<FixedWidthColumn>().is_err());
+        assert!("amount:1".parse::<FixedWidthColumn>().is_err());
+        assert!("amount:1:2:date".parse::<FixedWidthColumn>().is_err());
+    }
+
+    #[test]
+    fn test_fixed_width_empty_fails() {
+        let result = parse_fixed_width("  \n", &FixedWidthOptions::default());
+        assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
+    }
+}
@@ -0,0 +1,152 @@
+//! HCL-Konvertierungen (z.b. Terraform-Variablen).
+//!
+//! Beim Lesen werden Blöcke zu verschachtelten Objekten, Labels zu Keys.
+//! Beim Schreiben werden Objekte zu Blöcken und alle anderen Werte zu Attributen.
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Parst einen HCL String zu einem `serde_json::Value`.
+pub fn parse_hcl(input: &str) -> Result<JsonValue, FormatError> {
+    hcl::from_str(input).map_err(|e| FormatError::ParseError(format!("Invalid HCL: {}", e)))
+}
+
+/// Serialisiert einen `serde_json::Value` zu HCL String.
+///
+/// HCL braucht wie TOML ein Objekt als Root, Arrays werden darum in `data` gewrappt.
+pub fn value_to_hcl_string(value: &JsonValue) -> Result<String, FormatError> {
+    let body = match value {
+        JsonValue::Object(obj) => object_to_body(obj)?,
+        JsonValue::Array(_) => hcl::Body::builder()
+            .add_attribute(hcl::Attribute::new("data", to_expression(value)?))
+            .build(),
+        _ => {
+            return Err(FormatError::SerializationError(
+                "HCL must be an object or array".to_string(),
+            ));
+        }
+    };
+
+    hcl::to_string(&body)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting HCL: {}", e)))
+}
+
+// private helper funktionen
+
+fn object_to_body(obj: &serde_json::Map<String, JsonValue>) -> Result<hcl::Body, FormatError> {
+    let mut builder = hcl::Body::builder();
+
+    for (key, value) in obj {
+        let ident = hcl::Identifier::new(key.as_str()).map_err(|_| {
+            FormatError::SerializationError(format!("Invalid HCL identifier: '{}'", key))
+        })?;
+
+        match value {
+            // Objekte mit gültigen Keys werden zu Blöcken
+            JsonValue::Object(inner) if has_identifier_keys(inner) => {
+                builder = builder.add_block(object_to_block(ident, inner)?);
+            }
+            // Arrays von Objekten werden zu wiederholten Blöcken
+            JsonValue::Array(items)
+                if !items.is_empty()
+                    && items
+                        .iter()
+                        .all(|item| item.as_object().is_some_and(has_identifier_keys)) =>
+            {
+                for item in items {
+                    if let JsonValue::Object(inner) = item {
+                        builder = builder.add_block(object_to_block(ident.clone(), inner)?);
+                    }
+                }
+            }
+            _ => {
+                builder = builder.add_attribute(hcl::Attribute::new(ident, to_expression(value)?));
+            }
+        }
+    }
+
+    Ok(builder.build())
+}
+
+fn object_to_block(
+    ident: hcl::Identifier,
+    obj: &serde_json::Map<String, JsonValue>,
+) -> Result<hcl::Block, FormatError> {
+    let body = object_to_body(obj)?;
+    Ok(hcl::Block::builder(ident).add_structures(body).build())
+}
+
+fn has_identifier_keys(obj: &serde_json::Map<String, JsonValue>) -> bool {
+    obj.keys()
+        .all(|key| hcl::Identifier::new(key.as_str()).is_ok())
+}
+
+fn to_expression(value: &JsonValue) -> Result<hcl::Expression, FormatError> {
+    hcl::to_expression(value)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting HCL: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hcl_attributes_and_blocks() {
+        let input = r#"
+region = "eu-central-1"
+instance_count = 3
+
+tags {
+  team = "core"
+}
+
+variable "image" {
+  default = "ubuntu"
+}
+"#;
+        let value = parse_hcl(input).unwrap();
+        assert_eq!(value["region"], "eu-central-1");
+        assert_eq!(value["instance_count"], 3);
+        assert_eq!(value["tags"]["team"], "core");
+        assert_eq!(value["variable"]["image"]["default"], "ubuntu");
+    }
+
+    #[test]
+    fn test_value_to_hcl_blocks_and_attributes() {
+        let value = serde_json::json!({
+            "region": "eu-central-1",
+            "zones": ["a", "b"],
+            "server": {"port": 8080}
+        });
+        let result = value_to_hcl_string(&value).unwrap();
+        assert!(result.contains("region = \"eu-central-1\""));
+        assert!(result.contains("zones = [\n  \"a\",\n  \"b\"\n]"));
+        assert!(result.contains("server {\n  port = 8080\n}"));
+    }
+
+    #[test]
+    fn test_hcl_roundtrip() {
+        let value = serde_json::json!({
+            "name": "web",
+            "enabled": true,
+            "settings": {"retries": 3, "labels": {"env": "prod"}}
+        });
+        let hcl = value_to_hcl_string(&value).unwrap();
+        assert_eq!(parse_hcl(&hcl).unwrap(), value);
+    }
+
+    #[test]
+    fn test_value_to_hcl_non_identifier_keys_become_object_attribute() {
+        let value = serde_json::json!({"headers": {"Content-Type": "json", "x y": "z"}});
+        let result = value_to_hcl_string(&value).unwrap();
+        assert!(result.contains("headers = {"));
+        assert_eq!(parse_hcl(&result).unwrap(), value);
+    }
+
+    #[test]
+    fn test_parse_hcl_invalid_fails() {
+        let result = parse_hcl("name = ");
+        assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
+    }
+}
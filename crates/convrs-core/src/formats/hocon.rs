@@ -0,0 +1,959 @@
+//! HOCON-Eingabe (Human-Optimized Config Object Notation, z.b. `application.conf`).
+//!
+//! Der Parser liest zuerst eine Zwischendarstellung, in der doppelte Keys schon
+//! gemergt sind. Danach werden Substitutionen (`${a.b}`, `${?a.b}`) gegen das
+//! Root-Objekt aufgelöst. Includes werden relativ zu `HoconOptions::include_dir` gelesen.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::formats::csv::infer_type;
+use crate::formats::utils::json_value_to_string;
+
+/// Maximale Verschachtelung von Includes, schützt auch vor Include-Zyklen.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Optionen für das Lesen von HOCON.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HoconOptions {
+    /// Verzeichnis, relativ zu dem `include`-Anweisungen aufgelöst werden.
+    /// Ohne Angabe wird relativ zum aktuellen Verzeichnis gesucht.
+    pub include_dir: Option<PathBuf>,
+}
+
+/// Parst einen HOCON String zu einem `serde_json::Value`.
+pub fn parse_hocon(input: &str, options: &HoconOptions) -> Result<JsonValue, FormatError> {
+    let root = parse_document(input, options.include_dir.as_deref(), 0)
+        .map_err(|msg| FormatError::ParseError(format!("Invalid HOCON: {}", msg)))?;
+
+    let mut resolver = Resolver {
+        root: &root,
+        stack: Vec::new(),
+    };
+    let value = resolver
+        .resolve(&root, &mut Vec::new())
+        .map_err(|msg| FormatError::ParseError(format!("Invalid HOCON: {}", msg)))?;
+
+    Ok(value.unwrap_or_else(|| JsonValue::Object(serde_json::Map::new())))
+}
+
+// private helper funktionen
+
+/// Zwischendarstellung vor dem Auflösen der Substitutionen.
+#[derive(Debug, Clone)]
+enum Raw {
+    Value(JsonValue),
+    Subst {
+        path: Vec<String>,
+        optional: bool,
+    },
+    /// Aneinandergereihte Werte, z.b. `${base}/bin` oder `${list} [4]`.
+    Concat(Vec<Raw>),
+    /// Leerraum zwischen Teilen einer Concat, zählt nur bei Strings.
+    Ws(String),
+    Array(Vec<Raw>),
+    /// Felder in Reihenfolge, Keys sind eindeutig.
+    Object(Vec<(String, Raw)>),
+}
+
+fn parse_document(input: &str, base_dir: Option<&Path>, depth: usize) -> Result<Raw, String> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        line: 1,
+        base_dir,
+        depth,
+    };
+
+    parser.skip_blank();
+    let braced = parser.peek() == Some('{');
+    if braced {
+        parser.bump();
+    }
+    let fields = parser.parse_fields(braced, &[])?;
+    parser.skip_blank();
+    if let Some(c) = parser.peek() {
+        return Err(parser.error(format!("unexpected '{}' after root object", c)));
+    }
+    Ok(Raw::Object(fields))
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    base_dir: Option<&'a Path>,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, msg: impl std::fmt::Display) -> String {
+        format!("line {}: {}", self.line, msg)
+    }
+
+    fn at_comment(&self) -> bool {
+        self.peek() == Some('#') || self.starts_with("//")
+    }
+
+    fn skip_comment(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+    }
+
+    /// Überspringt Leerraum inklusive Zeilenumbrüche und Kommentare.
+    fn skip_blank(&mut self) {
+        loop {
+            if self.peek().is_some_and(char::is_whitespace) {
+                self.bump();
+            } else if self.at_comment() {
+                self.skip_comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Überspringt Leerraum innerhalb einer Zeile.
+    fn skip_inline(&mut self) -> String {
+        let mut ws = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace() && *c != '\n') {
+            ws.push(c);
+            self.bump();
+        }
+        ws
+    }
+
+    /// Parst Felder bis `}` (braced) oder Dateiende und mergt doppelte Keys.
+    fn parse_fields(
+        &mut self,
+        braced: bool,
+        prefix: &[String],
+    ) -> Result<Vec<(String, Raw)>, String> {
+        let mut fields = Vec::new();
+
+        loop {
+            self.skip_blank();
+            while self.peek() == Some(',') {
+                self.bump();
+                self.skip_blank();
+            }
+
+            match self.peek() {
+                None if braced => return Err(self.error("unterminated object, expected '}'")),
+                None => return Ok(fields),
+                Some('}') if braced => {
+                    self.bump();
+                    return Ok(fields);
+                }
+                _ => {}
+            }
+
+            if self.at_include() {
+                if let Some(Raw::Object(included)) = self.parse_include(prefix)? {
+                    for (key, value) in included {
+                        set_field(&mut fields, &[key], value, prefix);
+                    }
+                }
+            } else {
+                let path = self.parse_key()?;
+                let mut abs = prefix.to_vec();
+                abs.extend(path.iter().cloned());
+
+                self.skip_inline();
+                let value = match self.peek() {
+                    Some('{') => self.parse_value(&abs)?,
+                    Some(':') | Some('=') => {
+                        self.bump();
+                        self.parse_value(&abs)?
+                    }
+                    Some('+') if self.peek_at(1) == Some('=') => {
+                        self.bump();
+                        self.bump();
+                        // `a += b` entspricht `a = ${?a} [b]`
+                        Raw::Concat(vec![
+                            Raw::Subst {
+                                path: abs.clone(),
+                                optional: true,
+                            },
+                            Raw::Array(vec![self.parse_value(&abs)?]),
+                        ])
+                    }
+                    _ => {
+                        return Err(self.error(format!(
+                            "expected ':', '=' or '{{' after key '{}'",
+                            path.join(".")
+                        )));
+                    }
+                };
+                set_field(&mut fields, &path, value, prefix);
+            }
+
+            self.skip_inline();
+            if self.at_comment() {
+                self.skip_comment();
+            }
+            match self.peek() {
+                None | Some('\n') | Some(',') => {}
+                Some('}') if braced => {}
+                Some(c) => return Err(self.error(format!("unexpected '{}' after value", c))),
+            }
+        }
+    }
+
+    fn at_include(&self) -> bool {
+        if !self.starts_with("include") {
+            return false;
+        }
+        let mut offset = "include".len();
+        let mut has_space = false;
+        while self.peek_at(offset).is_some_and(|c| c == ' ' || c == '\t') {
+            offset += 1;
+            has_space = true;
+        }
+        has_space
+            && matches!(
+                self.peek_at(offset),
+                Some('"') | Some('f') | Some('r') | Some('u') | Some('c')
+            )
+    }
+
+    /// Parst `include "f"`, `include file("f")` und `include required(...)`.
+    fn parse_include(&mut self, prefix: &[String]) -> Result<Option<Raw>, String> {
+        for _ in 0.."include".len() {
+            self.bump();
+        }
+        self.skip_inline();
+
+        let mut required = false;
+        let mut closing = 0;
+        if self.starts_with("required(") {
+            required = true;
+            self.pos += "required(".len();
+            closing += 1;
+            self.skip_inline();
+        }
+        if self.starts_with("file(") {
+            self.pos += "file(".len();
+            closing += 1;
+            self.skip_inline();
+        } else if self.starts_with("url(") || self.starts_with("classpath(") {
+            return Err(self.error("only file includes are supported"));
+        }
+
+        if self.peek() != Some('"') {
+            return Err(self.error("expected quoted file name after include"));
+        }
+        let name = self.parse_quoted()?;
+        for _ in 0..closing {
+            self.skip_inline();
+            if self.bump() != Some(')') {
+                return Err(self.error("expected ')' after include file name"));
+            }
+        }
+
+        if self.depth >= MAX_INCLUDE_DEPTH {
+            return Err(self.error(format!("include depth exceeded at '{}'", name)));
+        }
+
+        let path = match self.base_dir {
+            Some(dir) => dir.join(&name),
+            None => PathBuf::from(&name),
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if required => {
+                return Err(self.error(format!("cannot read required include '{}': {}", name, e)));
+            }
+            // nicht gefundene includes werden ignoriert
+            Err(_) => return Ok(None),
+        };
+
+        let included_dir = path.parent().map(Path::to_path_buf);
+        let mut included = parse_document(&content, included_dir.as_deref(), self.depth + 1)
+            .map_err(|msg| self.error(format!("in include '{}': {}", name, msg)))?;
+        if let Raw::Object(fields) = &mut included {
+            // self-referenzen im include beziehen sich auf den aktuellen pfad
+            let mut merged = Vec::new();
+            for (key, value) in fields.drain(..) {
+                set_field(&mut merged, &[key], value, prefix);
+            }
+            *fields = merged;
+        }
+        Ok(Some(included))
+    }
+
+    /// Parst einen Key-Pfad wie `a.b."c.d"` in seine Segmente.
+    fn parse_key(&mut self) -> Result<Vec<String>, String> {
+        let mut path = Vec::new();
+        let mut segment = String::new();
+        let mut quoted_segment = false;
+
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    segment.push_str(&self.parse_quoted()?);
+                    quoted_segment = true;
+                }
+                Some('.') => {
+                    self.bump();
+                    path.push(finish_segment(&segment, quoted_segment));
+                    segment.clear();
+                    quoted_segment = false;
+                }
+                Some(c) if c.is_whitespace() && c != '\n' => {
+                    let ws = self.skip_inline();
+                    if matches!(
+                        self.peek(),
+                        Some(':') | Some('=') | Some('{') | Some('+') | None
+                    ) || self.peek() == Some('\n')
+                    {
+                        break;
+                    }
+                    segment.push_str(&ws);
+                }
+                Some(c) if is_unquoted_char(c) && !self.starts_with("//") => {
+                    segment.push(c);
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+
+        path.push(finish_segment(&segment, quoted_segment));
+        if path.iter().any(String::is_empty) && !quoted_segment {
+            return Err(self.error("expected key"));
+        }
+        Ok(path)
+    }
+
+    /// Parst einen Wert inklusive Concatenation bis Zeilenende, `,`, `}` oder `]`.
+    fn parse_value(&mut self, path: &[String]) -> Result<Raw, String> {
+        let mut parts = Vec::new();
+        let mut single_unquoted = false;
+
+        loop {
+            let ws = self.skip_inline();
+            if self.at_comment()
+                || matches!(
+                    self.peek(),
+                    None | Some('\n') | Some(',') | Some('}') | Some(']')
+                )
+            {
+                break;
+            }
+            if !parts.is_empty() && !ws.is_empty() {
+                parts.push(Raw::Ws(ws));
+            }
+
+            single_unquoted = false;
+            let part = match self.peek() {
+                Some('{') => {
+                    self.bump();
+                    Raw::Object(self.parse_fields(true, path)?)
+                }
+                Some('[') => {
+                    self.bump();
+                    self.parse_array(path)?
+                }
+                Some('"') => Raw::Value(JsonValue::String(self.parse_quoted()?)),
+                Some('$') if self.peek_at(1) == Some('{') => self.parse_subst()?,
+                Some(c) if is_unquoted_char(c) => {
+                    single_unquoted = true;
+                    let mut token = String::new();
+                    while let Some(c) = self
+                        .peek()
+                        .filter(|c| is_unquoted_char(*c) && !c.is_whitespace())
+                    {
+                        if self.starts_with("//") {
+                            break;
+                        }
+                        token.push(c);
+                        self.bump();
+                    }
+                    Raw::Value(JsonValue::String(token))
+                }
+                Some(c) => return Err(self.error(format!("unexpected '{}' in value", c))),
+                None => unreachable!(),
+            };
+            parts.push(part);
+        }
+
+        match parts.len() {
+            0 => Err(self.error("expected value")),
+            1 => {
+                let part = parts.remove(0);
+                match part {
+                    // ein einzelner unquotierter wert wird typisiert
+                    Raw::Value(JsonValue::String(token)) if single_unquoted => {
+                        Ok(Raw::Value(unquoted_to_value(&token)))
+                    }
+                    other => Ok(other),
+                }
+            }
+            _ => Ok(Raw::Concat(parts)),
+        }
+    }
+
+    fn parse_array(&mut self, path: &[String]) -> Result<Raw, String> {
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => return Err(self.error("unterminated array, expected ']'")),
+                Some(']') => {
+                    self.bump();
+                    return Ok(Raw::Array(items));
+                }
+                _ => {}
+            }
+
+            items.push(self.parse_value(path)?);
+
+            self.skip_inline();
+            if self.at_comment() {
+                self.skip_comment();
+            }
+            match self.peek() {
+                Some(',') | Some('\n') => {
+                    self.bump();
+                }
+                Some(']') => {}
+                None => return Err(self.error("unterminated array, expected ']'")),
+                Some(c) => return Err(self.error(format!("unexpected '{}' in array", c))),
+            }
+        }
+    }
+
+    fn parse_subst(&mut self) -> Result<Raw, String> {
+        self.bump();
+        self.bump();
+        let optional = self.peek() == Some('?');
+        if optional {
+            self.bump();
+        }
+        self.skip_inline();
+        let path = self.parse_key()?;
+        self.skip_inline();
+        if self.bump() != Some('}') {
+            return Err(self.error("unterminated substitution, expected '}'"));
+        }
+        Ok(Raw::Subst { path, optional })
+    }
+
+    /// Parst `"..."` mit JSON-Escapes oder `"""..."""` ohne Escapes.
+    fn parse_quoted(&mut self) -> Result<String, String> {
+        let mut out = String::new();
+
+        if self.starts_with("\"\"\"") {
+            self.pos += 3;
+            while !self.starts_with("\"\"\"") {
+                match self.bump() {
+                    Some(c) => out.push(c),
+                    None => return Err(self.error("unterminated triple-quoted string")),
+                }
+            }
+            self.pos += 3;
+            // zusätzliche quotes am ende gehören zum string
+            while self.peek() == Some('"') {
+                out.push('"');
+                self.bump();
+            }
+            return Ok(out);
+        }
+
+        self.bump();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.bump() {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.bump()).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| {
+                                self.error(format!("invalid unicode escape '\\u{}'", hex))
+                            })?;
+                        out.push(c);
+                    }
+                    Some(c @ ('"' | '\\' | '/')) => out.push(c),
+                    Some(c) => return Err(self.error(format!("invalid escape '\\{}'", c))),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    !matches!(
+        c,
+        '$' | '"'
+            | '{'
+            | '}'
+            | '['
+            | ']'
+            | ':'
+            | '='
+            | ','
+            | '+'
+            | '#'
+            | '`'
+            | '^'
+            | '?'
+            | '!'
+            | '@'
+            | '*'
+            | '&'
+            | '\\'
+            | '\n'
+    )
+}
+
+fn finish_segment(segment: &str, quoted: bool) -> String {
+    if quoted {
+        segment.to_string()
+    } else {
+        segment.trim().to_string()
+    }
+}
+
+fn unquoted_to_value(token: &str) -> JsonValue {
+    if token == "null" {
+        JsonValue::Null
+    } else {
+        infer_type(token)
+    }
+}
+
+/// Setzt einen Wert unter `path` und mergt ihn mit einem bestehenden Wert.
+/// `prefix` ist der absolute Pfad von `fields`, für Self-Referenzen wie `a = ${a}x`.
+fn set_field(fields: &mut Vec<(String, Raw)>, path: &[String], value: Raw, prefix: &[String]) {
+    let key = &path[0];
+    let mut abs = prefix.to_vec();
+    abs.push(key.clone());
+    let index = fields.iter().position(|(k, _)| k == key);
+
+    let new_value = if path.len() > 1 {
+        let mut inner = match index.map(|i| &fields[i].1) {
+            Some(Raw::Object(existing)) => existing.clone(),
+            _ => Vec::new(),
+        };
+        set_field(&mut inner, &path[1..], value, &abs);
+        Raw::Object(inner)
+    } else {
+        let existing = index.map(|i| fields[i].1.clone());
+        merge_value(existing, value, &abs)
+    };
+
+    match index {
+        Some(i) => fields[i].1 = new_value,
+        None => fields.push((key.clone(), new_value)),
+    }
+}
+
+/// Objekte werden rekursiv gemergt, alle anderen Werte ersetzt.
+fn merge_value(existing: Option<Raw>, value: Raw, path: &[String]) -> Raw {
+    match (existing, value) {
+        (Some(Raw::Object(mut fields)), Raw::Object(new_fields)) => {
+            for (key, val) in new_fields {
+                set_field(&mut fields, &[key], val, path);
+            }
+            Raw::Object(fields)
+        }
+        (existing, value) => replace_self_refs(value, path, existing.as_ref()),
+    }
+}
+
+/// Ersetzt Substitutionen auf den eigenen Pfad durch den bisherigen Wert.
+fn replace_self_refs(value: Raw, path: &[String], previous: Option<&Raw>) -> Raw {
+    match value {
+        Raw::Subst {
+            path: ref target, ..
+        } if target == path => match previous {
+            Some(prev) => prev.clone(),
+            None => value,
+        },
+        Raw::Concat(parts) => Raw::Concat(
+            parts
+                .into_iter()
+                .map(|part| replace_self_refs(part, path, previous))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Löst Substitutionen gegen das Root-Objekt auf.
+struct Resolver<'a> {
+    root: &'a Raw,
+    /// Pfade, die gerade aufgelöst werden (Zyklus-Erkennung).
+    stack: Vec<Vec<String>>,
+}
+
+impl Resolver<'_> {
+    /// Gibt `None` zurück, wenn der Wert undefiniert ist (z.b. `${?missing}`).
+    fn resolve(&mut self, raw: &Raw, path: &mut Vec<String>) -> Result<Option<JsonValue>, String> {
+        match raw {
+            Raw::Value(value) => Ok(Some(value.clone())),
+            Raw::Ws(ws) => Ok(Some(JsonValue::String(ws.clone()))),
+            Raw::Subst {
+                path: target,
+                optional,
+            } => self.resolve_subst(target, *optional),
+            Raw::Array(items) => {
+                let mut values = Vec::new();
+                for item in items {
+                    if let Some(value) = self.resolve(item, path)? {
+                        values.push(value);
+                    }
+                }
+                Ok(Some(JsonValue::Array(values)))
+            }
+            Raw::Object(fields) => {
+                let mut obj = serde_json::Map::new();
+                for (key, value) in fields {
+                    path.push(key.clone());
+                    self.stack.push(path.clone());
+                    let resolved = self.resolve(value, path);
+                    self.stack.pop();
+                    path.pop();
+                    if let Some(value) = resolved? {
+                        obj.insert(key.clone(), value);
+                    }
+                }
+                Ok(Some(JsonValue::Object(obj)))
+            }
+            Raw::Concat(parts) => self.resolve_concat(parts, path),
+        }
+    }
+
+    fn resolve_subst(
+        &mut self,
+        target: &[String],
+        optional: bool,
+    ) -> Result<Option<JsonValue>, String> {
+        let root = self.root;
+        let mut node = root;
+
+        for (i, key) in target.iter().enumerate() {
+            match node {
+                Raw::Object(fields) => match fields.iter().find(|(k, _)| k == key) {
+                    Some((_, value)) => node = value,
+                    None => return self.fallback(target, optional),
+                },
+                // pfad führt durch einen noch nicht aufgelösten wert
+                _ => {
+                    let Some(value) = self.resolve_at(&target[..i], node, optional)? else {
+                        return self.fallback(target, optional);
+                    };
+                    let found = target[i..]
+                        .iter()
+                        .try_fold(&value, |current, key| current.get(key.as_str()));
+                    return match found {
+                        Some(found) => Ok(Some(found.clone())),
+                        None => self.fallback(target, optional),
+                    };
+                }
+            }
+        }
+
+        self.resolve_at(target, node, optional)
+    }
+
+    fn resolve_at(
+        &mut self,
+        target: &[String],
+        node: &Raw,
+        optional: bool,
+    ) -> Result<Option<JsonValue>, String> {
+        if self.stack.iter().any(|p| p == target) {
+            if optional {
+                return Ok(None);
+            }
+            return Err(format!("cycle in substitution ${{{}}}", target.join(".")));
+        }
+
+        self.stack.push(target.to_vec());
+        let resolved = self.resolve(node, &mut target.to_vec());
+        self.stack.pop();
+        resolved
+    }
+
+    /// Nicht gefundene Pfade werden wie bei HOCON in den Umgebungsvariablen gesucht.
+    fn fallback(&self, target: &[String], optional: bool) -> Result<Option<JsonValue>, String> {
+        let name = target.join(".");
+        match std::env::var(&name) {
+            Ok(value) => Ok(Some(JsonValue::String(value))),
+            Err(_) if optional => Ok(None),
+            Err(_) => Err(format!("unresolved substitution ${{{}}}", name)),
+        }
+    }
+
+    fn resolve_concat(
+        &mut self,
+        parts: &[Raw],
+        path: &mut Vec<String>,
+    ) -> Result<Option<JsonValue>, String> {
+        let mut resolved = Vec::new();
+        for part in parts {
+            if let Some(value) = self.resolve(part, path)? {
+                resolved.push((matches!(part, Raw::Ws(_)), value));
+            }
+        }
+
+        // leerraum am rand entsteht durch undefinierte optionale substitutionen
+        while resolved.first().is_some_and(|(ws, _)| *ws) {
+            resolved.remove(0);
+        }
+        while resolved.last().is_some_and(|(ws, _)| *ws) {
+            resolved.pop();
+        }
+
+        let values: Vec<&JsonValue> = resolved
+            .iter()
+            .filter(|(ws, _)| !ws)
+            .map(|(_, value)| value)
+            .collect();
+
+        if values.is_empty() {
+            return Ok(None);
+        }
+        if values.len() == 1 && resolved.len() == 1 {
+            return Ok(Some(values[0].clone()));
+        }
+
+        if values.iter().all(|v| v.is_object()) {
+            let mut merged = serde_json::Map::new();
+            for value in values {
+                if let JsonValue::Object(obj) = value {
+                    merge_json(&mut merged, obj);
+                }
+            }
+            return Ok(Some(JsonValue::Object(merged)));
+        }
+
+        if values.iter().all(|v| v.is_array()) {
+            let items = values
+                .into_iter()
+                .filter_map(JsonValue::as_array)
+                .flatten()
+                .cloned()
+                .collect();
+            return Ok(Some(JsonValue::Array(items)));
+        }
+
+        if values.iter().any(|v| v.is_array() || v.is_object()) {
+            return Err(format!(
+                "cannot concatenate objects or arrays with strings at '{}'",
+                path.join(".")
+            ));
+        }
+
+        let text: String = resolved
+            .iter()
+            .map(|(_, value)| match value {
+                JsonValue::Null => "null".to_string(),
+                other => json_value_to_string(other),
+            })
+            .collect();
+        Ok(Some(JsonValue::String(text)))
+    }
+}
+
+fn merge_json(
+    target: &mut serde_json::Map<String, JsonValue>,
+    source: &serde_json::Map<String, JsonValue>,
+) {
+    for (key, value) in source {
+        match (target.get_mut(key), value) {
+            (Some(JsonValue::Object(existing)), JsonValue::Object(inner)) => {
+                merge_json(existing, inner)
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> JsonValue {
+        parse_hocon(input, &HoconOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_hocon_basic_syntax() {
+        let input = r#"
+# kommentar
+app {
+  name = "demo"   // inline kommentar
+  port: 8080
+  debug = false
+  ratio = 0.5
+  tags = [a, "b c", 3]
+}
+app.owner = core
+"#;
+        assert_eq!(
+            parse(input),
+            serde_json::json!({
+                "app": {
+                    "name": "demo",
+                    "port": 8080,
+                    "debug": false,
+                    "ratio": 0.5,
+                    "tags": ["a", "b c", 3],
+                    "owner": "core"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_hocon_json_root_and_commas() {
+        let value = parse(r#"{ "a": 1, "b": { "c": null }, "d": [1, 2,] }"#);
+        assert_eq!(
+            value,
+            serde_json::json!({"a": 1, "b": {"c": null}, "d": [1, 2]})
+        );
+    }
+
+    #[test]
+    fn test_parse_hocon_objects_merge_and_override() {
+        let input =
+            "db { host = localhost, port = 5432 }\ndb { port = 6543 }\nlevel = 1\nlevel = 2";
+        assert_eq!(
+            parse(input),
+            serde_json::json!({"db": {"host": "localhost", "port": 6543}, "level": 2})
+        );
+    }
+
+    #[test]
+    fn test_parse_hocon_substitutions() {
+        let input = r#"
+base = /opt/app
+paths {
+  bin = ${base}/bin
+  logs = ${base}"/log files"
+}
+defaults { timeout = 30, retries = 3 }
+service = ${defaults} { retries = 5 }
+copy = ${paths.bin}
+"#;
+        let value = parse(input);
+        assert_eq!(value["paths"]["bin"], "/opt/app/bin");
+        assert_eq!(value["paths"]["logs"], "/opt/app/log files");
+        assert_eq!(
+            value["service"],
+            serde_json::json!({"timeout": 30, "retries": 5})
+        );
+        assert_eq!(value["copy"], "/opt/app/bin");
+    }
+
+    #[test]
+    fn test_parse_hocon_self_reference_and_append() {
+        let input = "path = /usr/bin\npath = ${path}\":/opt/bin\"\nlist = [1]\nlist += 2\nnew += x";
+        let value = parse(input);
+        assert_eq!(value["path"], "/usr/bin:/opt/bin");
+        assert_eq!(value["list"], serde_json::json!([1, 2]));
+        assert_eq!(value["new"], serde_json::json!(["x"]));
+    }
+
+    #[test]
+    fn test_parse_hocon_optional_substitution() {
+        let value = parse("a = 1\nb = ${?does.not.exist}\nc = ${?does.not.exist} suffix");
+        assert_eq!(value, serde_json::json!({"a": 1, "c": "suffix"}));
+    }
+
+    #[test]
+    fn test_parse_hocon_unresolved_and_cycle_fail() {
+        let err = parse_hocon("a = ${missing.key}", &HoconOptions::default()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unresolved substitution ${missing.key}")
+        );
+
+        let err = parse_hocon("a = ${b}\nb = ${a}", &HoconOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn test_parse_hocon_triple_quoted_string() {
+        let value = parse("text = \"\"\"line one\nline \"two\"\"\"\"");
+        assert_eq!(value["text"], "line one\nline \"two\"");
+    }
+
+    #[test]
+    fn test_parse_hocon_includes() {
+        let dir = std::env::temp_dir().join(format!("convrs-hocon-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("base.conf"),
+            "db { host = localhost, port = 5432 }",
+        )
+        .unwrap();
+
+        let options = HoconOptions {
+            include_dir: Some(dir.clone()),
+        };
+        let input = "include \"base.conf\"\ninclude \"missing.conf\"\ndb.port = 1234\nurl = ${db.host}\":\"${db.port}";
+        let value = parse_hocon(input, &options).unwrap();
+        assert_eq!(
+            value["db"],
+            serde_json::json!({"host": "localhost", "port": 1234})
+        );
+        assert_eq!(value["url"], "localhost:1234");
+
+        let err = parse_hocon("include required(file(\"missing.conf\"))", &options).unwrap_err();
+        assert!(err.to_string().contains("required include"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_hocon_reports_line() {
+        let err = parse_hocon("a = 1\nb = [1, 2\n", &HoconOptions::default()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Parse Error: Invalid HOCON: line")
+        );
+    }
+}
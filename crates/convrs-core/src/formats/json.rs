@@ -3,6 +3,17 @@
 use crate::error::FormatError;
 use crate::formats::utils::{flatten_json, json_to_toml_value};
 
+/// Parst einen JSON String zu einem `serde_json::Value`.
+pub fn parse_json(input: &str) -> Result<serde_json::Value, FormatError> {
+    serde_json::from_str(input).map_err(|e| FormatError::ParseError(format!("Invalid JSON: {}", e)))
+}
+
+/// Serialisiert einen `serde_json::Value` zu formatiertem JSON String.
+pub fn value_to_json_string(value: &serde_json::Value) -> Result<String, FormatError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting JSON: {}", e)))
+}
+
 /// Konvertiert JSON String zu formatiertem JSON String (Pretty-Printing).
 pub fn json_to_json_string(input: &str) -> Result<String, FormatError> {
     let json_value: serde_json::Value = serde_json::from_str(input)
@@ -0,0 +1,67 @@
+//! JSON-Lines-Konvertierungen: ein kompakter JSON-Wert pro Zeile.
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Parst JSON Lines zu einem Array mit einem Eintrag pro Zeile.
+pub fn parse_jsonl(input: &str) -> Result<JsonValue, FormatError> {
+    let mut values = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = serde_json::from_str(line).map_err(|e| {
+            FormatError::ParseError(format!("Invalid JSON Lines: line {}: {}", i + 1, e))
+        })?;
+        values.push(value);
+    }
+
+    Ok(JsonValue::Array(values))
+}
+
+/// Serialisiert ein Array als JSON Lines, andere Werte als einzelne Zeile.
+pub fn value_to_jsonl_string(value: &JsonValue) -> Result<String, FormatError> {
+    let values = match value {
+        JsonValue::Array(arr) => arr.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+
+    let mut out = String::new();
+    for value in values {
+        let line = serde_json::to_string(value).map_err(|e| {
+            FormatError::SerializationError(format!("Error formatting JSON Lines: {}", e))
+        })?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jsonl() {
+        let value = parse_jsonl("{\"a\":1}\n\n{\"a\":2}\n").unwrap();
+        assert_eq!(value, serde_json::json!([{"a": 1}, {"a": 2}]));
+    }
+
+    #[test]
+    fn test_parse_jsonl_reports_line() {
+        let err = parse_jsonl("{\"a\":1}\n{broken").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_value_to_jsonl() {
+        let value = serde_json::json!([{"a": 1}, {"b": "x"}]);
+        assert_eq!(
+            value_to_jsonl_string(&value).unwrap(),
+            "{\"a\":1}\n{\"b\":\"x\"}\n"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! logfmt-Konvertierungen: `key=value key2="quoted value"`, ein Record pro Zeile.
+//!
+//! Unquotierte Werte werden wie bei CSV über `infer_type` typisiert,
+//! quotierte Werte bleiben Strings. Ein Key ohne `=` ergibt `true`.
+//! Keys mit Leerzeichen, `=` oder `"` werden wie Werte quotiert (`"my key"=1`).
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::formats::csv::{infer_type, value_as_records};
+use crate::formats::utils::json_value_to_string;
+
+/// Parst logfmt-Zeilen zu einer Liste von Record-Objekten.
+pub fn parse_logfmt(input: &str) -> Result<JsonValue, FormatError> {
+    let mut records = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = parse_line(line).map_err(|msg| {
+            FormatError::ParseError(format!("Invalid logfmt: line {}: {}", i + 1, msg))
+        })?;
+        records.push(JsonValue::Object(record));
+    }
+
+    Ok(JsonValue::Array(records))
+}
+
+/// Serialisiert Records als logfmt, eine Zeile pro Record.
+///
+/// Verschachtelte Objekte werden wie bei CSV mit Unterstrich geflattet.
+pub fn value_to_logfmt_string(value: &JsonValue) -> Result<String, FormatError> {
+    let records = value_as_records(value).ok_or_else(|| {
+        FormatError::SerializationError("Data must be an array or object for logfmt".to_string())
+    })?;
+
+    let mut out = String::new();
+    for record in records {
+        let mut pairs = Vec::new();
+        flatten_pairs(record, None, &mut pairs);
+
+        let line: Vec<String> = pairs
+            .into_iter()
+            .map(|(key, val)| format!("{}={}", format_key(&key), format_value(val)))
+            .collect();
+        out.push_str(&line.join(" "));
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+// private helper funktionen
+
+fn parse_line(line: &str) -> Result<serde_json::Map<String, JsonValue>, String> {
+    let mut record = serde_json::Map::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let key = if chars.next_if_eq(&'"').is_some() {
+            read_quoted(&mut chars).ok_or_else(|| "unterminated quoted key".to_string())?
+        } else {
+            let mut key = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+                key.push(c);
+            }
+            if key.is_empty() {
+                return Err("missing key before '='".to_string());
+            }
+            key
+        };
+
+        if chars.next_if_eq(&'=').is_none() {
+            // Key ohne Wert gilt als Flag
+            record.insert(key, JsonValue::Bool(true));
+            continue;
+        }
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let quoted = read_quoted(&mut chars)
+                .ok_or_else(|| format!("unterminated quoted value for key '{}'", key))?;
+            JsonValue::String(quoted)
+        } else {
+            let mut raw = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                raw.push(c);
+            }
+            infer_type(&raw)
+        };
+
+        record.insert(key, value);
+    }
+
+    Ok(record)
+}
+
+/// Liest einen quotierten Key oder Wert nach dem öffnenden `"`. `None`: nicht abgeschlossen.
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+    let mut quoted = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(quoted),
+            '\\' => match chars.next()? {
+                'n' => quoted.push('\n'),
+                't' => quoted.push('\t'),
+                c => quoted.push(c),
+            },
+            c => quoted.push(c),
+        }
+    }
+}
+
+/// Flattet ein Objekt zu Key/Wert-Paaren und behält dabei die Typen der Werte.
+///
+/// `prefix` ist `None` nur auf oberster Ebene, damit auch ein leerer Key ein Paar ergibt.
+fn flatten_pairs<'a>(
+    value: &'a JsonValue,
+    prefix: Option<&str>,
+    pairs: &mut Vec<(String, &'a JsonValue)>,
+) {
+    match (value, prefix) {
+        (JsonValue::Object(obj), _) => {
+            for (key, val) in obj {
+                let new_key = match prefix {
+                    Some(prefix) => format!("{}_{}", prefix, key),
+                    None => key.clone(),
+                };
+                flatten_pairs(val, Some(&new_key), pairs);
+            }
+        }
+        (_, Some(prefix)) => pairs.push((prefix.to_string(), value)),
+        (_, None) => {}
+    }
+}
+
+/// Formatiert einen Wert für logfmt und quotiert ihn, wenn nötig.
+///
+/// Strings, die beim Einlesen per `infer_type` einen anderen Typ bekämen
+/// (z.b. "42"), werden ebenfalls quotiert, damit sie Strings bleiben.
+fn format_value(value: &JsonValue) -> String {
+    let raw = json_value_to_string(value);
+
+    let needs_quotes = match value {
+        JsonValue::String(s) => {
+            s.is_empty()
+                || s.chars()
+                    .any(|c| c.is_whitespace() || c == '=' || c == '"' || c == '\\')
+                || infer_type(s) != *value
+        }
+        JsonValue::Array(_) | JsonValue::Object(_) => true,
+        _ => false,
+    };
+
+    if !needs_quotes {
+        return raw;
+    }
+    quote(&raw)
+}
+
+/// Quotiert einen Key, wenn er sonst nicht wieder als ein Key gelesen würde (auch `""`).
+fn format_key(key: &str) -> String {
+    let needs_quotes = key.is_empty()
+        || key
+            .chars()
+            .any(|c| c.is_whitespace() || c == '=' || c == '"' || c == '\\');
+    if needs_quotes {
+        quote(key)
+    } else {
+        key.to_string()
+    }
+}
+
+fn quote(raw: &str) -> String {
+    let escaped = raw
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_logfmt_types_and_quotes() {
+        let input = r#"level=info msg="request done" status=200 duration=1.5 cached"#;
+        let value = parse_logfmt(input).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "level": "info",
+                "msg": "request done",
+                "status": 200,
+                "duration": 1.5,
+                "cached": true
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_logfmt_escapes_and_empty_values() {
+        let value = parse_logfmt(r#"msg="say \"hi\"" user="#).unwrap();
+        assert_eq!(value[0]["msg"], "say \"hi\"");
+        assert_eq!(value[0]["user"], JsonValue::Null);
+    }
+
+    #[test]
+    fn test_parse_logfmt_unterminated_quote_fails() {
+        let err = parse_logfmt("a=1\nmsg=\"oops").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_value_to_logfmt_quotes_when_needed() {
+        let value = serde_json::json!([
+            {"level": "info", "msg": "hello world", "code": "42", "n": 42, "ok": true}
+        ]);
+        let result = value_to_logfmt_string(&value).unwrap();
+        assert_eq!(
+            result,
+            "code=\"42\" level=info msg=\"hello world\" n=42 ok=true\n"
+        );
+    }
+
+    #[test]
+    fn test_logfmt_roundtrip() {
+        let input = "level=warn msg=\"disk \\\"full\\\"\" pct=97\nlevel=info path=/tmp\n";
+        let value = parse_logfmt(input).unwrap();
+        let output = value_to_logfmt_string(&value).unwrap();
+        assert_eq!(parse_logfmt(&output).unwrap(), value);
+    }
+
+    #[test]
+    fn test_logfmt_roundtrip_quotes_special_keys() {
+        let value = serde_json::json!([
+            {"my key": 1, "a=b": "x", "say \"hi\"": true, "plain": "ok"}
+        ]);
+        let output = value_to_logfmt_string(&value).unwrap();
+        assert!(output.contains("\"my key\"=1"));
+        assert!(output.contains("\"a=b\"=x"));
+        assert_eq!(parse_logfmt(&output).unwrap(), value);
+    }
+
+    #[test]
+    fn test_logfmt_roundtrip_keeps_empty_key() {
+        let value = serde_json::json!([{"": 1, "b": 2}]);
+        let output = value_to_logfmt_string(&value).unwrap();
+        assert_eq!(output, "\"\"=1 b=2\n");
+        assert_eq!(parse_logfmt(&output).unwrap(), value);
+    }
+}
@@ -4,4 +4,12 @@ pub mod json;
 pub mod yaml;
 pub mod toml;
 pub mod csv;
+pub mod sql;
+pub mod xlsx;
+pub mod plist;
+pub mod logfmt;
+pub mod jsonl;
+pub mod fixed_width;
+pub mod hcl;
+pub mod hocon;
 pub mod utils;
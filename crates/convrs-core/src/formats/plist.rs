@@ -0,0 +1,225 @@
+//! Property-List-Konvertierungen (Apple plist) im XML- und Binärformat.
+//!
+//! Beim Lesen wird das Format automatisch erkannt. Datumswerte werden zu
+//! RFC-3339-Strings, Data-Blobs zu Base64-Strings.
+//!
+//! Beim Schreiben bleiben Strings `<string>`, auch wenn sie wie ein Datum aussehen;
+//! erst mit `PlistOptions::dates` werden RFC-3339-Strings zu `<date>`. plist kennt
+//! kein `null`, es wird als leerer `<string></string>` geschrieben (wie bei TOML).
+//!
+//! `<data>` geht dabei verloren: die Base64-Strings aus gelesenen Data-Blobs lassen sich
+//! nicht sicher von normalen Strings unterscheiden (`"Demo"` ist auch gültiges Base64)
+//! und werden deshalb als `<string>` zurückgeschrieben.
+
+use std::io::Cursor;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Optionen für die plist-Ausgabe.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlistOptions {
+    /// Schreibt eine binäre plist (`bplist00`) statt XML.
+    pub binary: bool,
+    /// Schreibt RFC-3339-Strings als `<date>` statt als `<string>`.
+    pub dates: bool,
+}
+
+/// Parst eine plist (XML oder binär) zu einem `serde_json::Value`.
+pub fn parse_plist(input: &[u8]) -> Result<JsonValue, FormatError> {
+    let plist_value = plist::Value::from_reader(Cursor::new(input))
+        .map_err(|e| FormatError::ParseError(format!("Invalid plist: {}", e)))?;
+
+    plist_to_json_value(&plist_value)
+}
+
+/// Serialisiert einen `serde_json::Value` als plist (XML oder binär).
+pub fn value_to_plist_bytes(
+    value: &JsonValue,
+    options: &PlistOptions,
+) -> Result<Vec<u8>, FormatError> {
+    let plist_value = json_to_plist_value(value, options);
+    let mut buffer = Vec::new();
+
+    let result = if options.binary {
+        plist_value.to_writer_binary(&mut buffer)
+    } else {
+        plist_value.to_writer_xml(&mut buffer)
+    };
+
+    result
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting plist: {}", e)))?;
+    Ok(buffer)
+}
+
+/// Konvertiert einen `plist::Value` rekursiv in einen `serde_json::Value`.
+fn plist_to_json_value(value: &plist::Value) -> Result<JsonValue, FormatError> {
+    match value {
+        plist::Value::Boolean(b) => Ok(JsonValue::Bool(*b)),
+        plist::Value::Integer(i) => {
+            if let Some(signed) = i.as_signed() {
+                Ok(JsonValue::Number(signed.into()))
+            } else if let Some(unsigned) = i.as_unsigned() {
+                Ok(JsonValue::Number(unsigned.into()))
+            } else {
+                Err(FormatError::ParseError(format!(
+                    "Invalid plist integer: {}",
+                    i
+                )))
+            }
+        }
+        plist::Value::Real(f) => serde_json::Number::from_f64(*f)
+            .map(JsonValue::Number)
+            .ok_or_else(|| FormatError::ParseError(format!("Invalid plist real: {}", f))),
+        plist::Value::String(s) => Ok(JsonValue::String(s.clone())),
+        plist::Value::Date(date) => Ok(JsonValue::String(date.to_xml_format())),
+        plist::Value::Data(data) => Ok(JsonValue::String(BASE64.encode(data))),
+        plist::Value::Uid(uid) => Ok(JsonValue::Number(uid.get().into())),
+        plist::Value::Array(arr) => {
+            let json_arr: Result<Vec<JsonValue>, FormatError> =
+                arr.iter().map(plist_to_json_value).collect();
+            Ok(JsonValue::Array(json_arr?))
+        }
+        plist::Value::Dictionary(dict) => {
+            let mut obj = serde_json::Map::new();
+            for (key, val) in dict {
+                obj.insert(key.clone(), plist_to_json_value(val)?);
+            }
+            Ok(JsonValue::Object(obj))
+        }
+        _ => Err(FormatError::ParseError(
+            "Unsupported plist value type".to_string(),
+        )),
+    }
+}
+
+/// Konvertiert einen `serde_json::Value` rekursiv in einen `plist::Value`.
+fn json_to_plist_value(value: &JsonValue, options: &PlistOptions) -> plist::Value {
+    match value {
+        // plist hat kein "null" — leerer String als Ersatz (wie bei TOML)
+        JsonValue::Null => plist::Value::String(String::new()),
+        JsonValue::Bool(b) => plist::Value::Boolean(*b),
+        JsonValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                plist::Value::Integer(i.into())
+            } else if let Some(u) = n.as_u64() {
+                plist::Value::Integer(u.into())
+            } else {
+                plist::Value::Real(n.as_f64().unwrap_or_default())
+            }
+        }
+        // RFC-3339-Strings werden nur auf Wunsch wieder zu plist-Datumswerten
+        JsonValue::String(s) if options.dates => match plist::Date::from_xml_format(s) {
+            Ok(date) => plist::Value::Date(date),
+            Err(_) => plist::Value::String(s.clone()),
+        },
+        JsonValue::String(s) => plist::Value::String(s.clone()),
+        JsonValue::Array(arr) => plist::Value::Array(
+            arr.iter()
+                .map(|item| json_to_plist_value(item, options))
+                .collect(),
+        ),
+        JsonValue::Object(obj) => {
+            let mut dict = plist::Dictionary::new();
+            for (key, val) in obj {
+                dict.insert(key.clone(), json_to_plist_value(val, options));
+            }
+            plist::Value::Dictionary(dict)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleName</key>
+    <string>Demo</string>
+    <key>Build</key>
+    <integer>42</integer>
+    <key>Released</key>
+    <date>2024-05-01T12:00:00Z</date>
+    <key>Icon</key>
+    <data>aGVsbG8=</data>
+</dict>
+</plist>"#;
+
+    #[test]
+    fn test_parse_xml_plist() {
+        let value = parse_plist(XML_PLIST.as_bytes()).unwrap();
+        assert_eq!(value["CFBundleName"], "Demo");
+        assert_eq!(value["Build"], 42);
+        assert_eq!(value["Released"], "2024-05-01T12:00:00Z");
+        assert_eq!(value["Icon"], "aGVsbG8=");
+    }
+
+    #[test]
+    fn test_xml_plist_output() {
+        let value = serde_json::json!({"name": "Demo", "enabled": true});
+        let bytes = value_to_plist_bytes(&value, &PlistOptions::default()).unwrap();
+        let xml = String::from_utf8(bytes).unwrap();
+        assert!(xml.contains("<key>name</key>"));
+        assert!(xml.contains("<true/>"));
+    }
+
+    #[test]
+    fn test_date_like_strings_stay_strings() {
+        let value = serde_json::json!({"released": "2024-05-01T12:00:00Z", "missing": null});
+        let bytes = value_to_plist_bytes(&value, &PlistOptions::default()).unwrap();
+        let xml = String::from_utf8(bytes).unwrap();
+        assert!(xml.contains("<string>2024-05-01T12:00:00Z</string>"));
+        assert!(!xml.contains("<date>"));
+        assert!(xml.contains("<string></string>"));
+    }
+
+    #[test]
+    fn test_dates_option_writes_date_values() {
+        let value = serde_json::json!({"released": "2024-05-01T12:00:00Z"});
+        let options = PlistOptions {
+            dates: true,
+            ..PlistOptions::default()
+        };
+        let bytes = value_to_plist_bytes(&value, &options).unwrap();
+        let xml = String::from_utf8(bytes).unwrap();
+        assert!(xml.contains("<date>2024-05-01T12:00:00Z</date>"));
+    }
+
+    #[test]
+    fn test_data_is_written_back_as_base64_string() {
+        let value = parse_plist(XML_PLIST.as_bytes()).unwrap();
+        let options = PlistOptions {
+            dates: true,
+            ..PlistOptions::default()
+        };
+        let bytes = value_to_plist_bytes(&value, &options).unwrap();
+        let xml = String::from_utf8(bytes).unwrap();
+        assert!(xml.contains("<string>aGVsbG8=</string>"));
+        assert!(!xml.contains("<data>"));
+        assert_eq!(parse_plist(xml.as_bytes()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_binary_plist_roundtrip_keeps_dates() {
+        let value = serde_json::json!({"released": "2024-05-01T12:00:00Z", "tags": ["a", "b"]});
+        let options = PlistOptions {
+            binary: true,
+            dates: true,
+        };
+        let bytes = value_to_plist_bytes(&value, &options).unwrap();
+        assert!(bytes.starts_with(b"bplist00"));
+        assert_eq!(parse_plist(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_invalid_plist_fails() {
+        let result = parse_plist(b"<plist><dict><key>broken");
+        assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
+    }
+}
@@ -0,0 +1,331 @@
+//! SQL-Ausgabe: `CREATE TABLE` + gebündelte `INSERT`-Statements.
+//!
+//! SQL wird nur als Zielformat unterstützt. Spaltentypen und Literale folgen den
+//! Typen der geparsten Werte: ein String wie `"007"` bleibt `TEXT` und wird gequotet.
+
+use std::str::FromStr;
+
+use crate::error::FormatError;
+use crate::formats::csv::value_to_table;
+use serde_json::Value as JsonValue;
+
+/// Unterstützte SQL-Dialekte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqlDialect {
+    #[default]
+    Sqlite,
+    Postgres,
+    Mysql,
+}
+
+impl SqlDialect {
+    /// gibt den dialekt-namen als string zurück
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SqlDialect::Sqlite => "sqlite",
+            SqlDialect::Postgres => "postgres",
+            SqlDialect::Mysql => "mysql",
+        }
+    }
+
+    /// Setzt einen Bezeichner (Tabelle, Spalte) in dialekt-spezifische Quotes.
+    fn quote_ident(&self, ident: &str) -> String {
+        match self {
+            SqlDialect::Mysql => format!("`{}`", ident.replace('`', "``")),
+            SqlDialect::Sqlite | SqlDialect::Postgres => {
+                format!("\"{}\"", ident.replace('"', "\"\""))
+            }
+        }
+    }
+
+    fn type_name(&self, column_type: ColumnType) -> &'static str {
+        match (self, column_type) {
+            (SqlDialect::Sqlite, ColumnType::Integer) => "INTEGER",
+            (SqlDialect::Sqlite, ColumnType::Float) => "REAL",
+            (SqlDialect::Postgres | SqlDialect::Mysql, ColumnType::Integer) => "BIGINT",
+            (SqlDialect::Postgres, ColumnType::Float) => "DOUBLE PRECISION",
+            (SqlDialect::Mysql, ColumnType::Float) => "DOUBLE",
+            (_, ColumnType::Boolean) => "BOOLEAN",
+            (_, ColumnType::Text) => "TEXT",
+        }
+    }
+
+    fn string_literal(&self, s: &str) -> String {
+        let escaped = s.replace('\'', "''");
+        match self {
+            // MySQL interpretiert Backslashes im Default-Modus als Escape-Zeichen
+            SqlDialect::Mysql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            SqlDialect::Sqlite | SqlDialect::Postgres => format!("'{}'", escaped),
+        }
+    }
+
+    fn bool_literal(&self, b: bool) -> &'static str {
+        match (self, b) {
+            // SQLite kennt erst ab 3.23 TRUE/FALSE, 1/0 funktioniert überall
+            (SqlDialect::Sqlite, true) => "1",
+            (SqlDialect::Sqlite, false) => "0",
+            (_, true) => "TRUE",
+            (_, false) => "FALSE",
+        }
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sqlite" => Ok(SqlDialect::Sqlite),
+            "postgres" | "postgresql" | "pg" => Ok(SqlDialect::Postgres),
+            "mysql" | "mariadb" => Ok(SqlDialect::Mysql),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown SQL dialect: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Optionen für die SQL-Ausgabe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlOptions {
+    pub dialect: SqlDialect,
+    /// Name der erzeugten Tabelle.
+    pub table: String,
+    /// Maximale Anzahl Zeilen pro `INSERT`-Statement.
+    pub batch_size: usize,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        SqlOptions {
+            dialect: SqlDialect::default(),
+            table: "data".to_string(),
+            batch_size: 100,
+        }
+    }
+}
+
+/// Aus den Zellwerten abgeleiteter Spaltentyp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Integer,
+    Float,
+    Boolean,
+    Text,
+}
+
+/// Bestimmt den Spaltentyp aus den JSON-Typen aller nicht-leeren Zellen einer Spalte.
+///
+/// Integer + Float ergibt Float, alle anderen Mischungen fallen auf Text zurück.
+fn column_type<'a>(cells: impl Iterator<Item = &'a JsonValue>) -> ColumnType {
+    let mut current: Option<ColumnType> = None;
+
+    for cell in cells {
+        let cell_type = match cell {
+            JsonValue::Null => continue,
+            JsonValue::Bool(_) => ColumnType::Boolean,
+            JsonValue::Number(n) if n.is_i64() || n.is_u64() => ColumnType::Integer,
+            JsonValue::Number(_) => ColumnType::Float,
+            _ => ColumnType::Text,
+        };
+
+        current = Some(match (current, cell_type) {
+            (None, t) => t,
+            (Some(a), b) if a == b => a,
+            (Some(ColumnType::Integer), ColumnType::Float)
+            | (Some(ColumnType::Float), ColumnType::Integer) => ColumnType::Float,
+            _ => ColumnType::Text,
+        });
+    }
+
+    current.unwrap_or(ColumnType::Text)
+}
+
+fn sql_literal(value: &JsonValue, column_type: ColumnType, dialect: SqlDialect) -> String {
+    match (value, column_type) {
+        (JsonValue::Null, _) => "NULL".to_string(),
+        (JsonValue::Bool(b), ColumnType::Boolean) => dialect.bool_literal(*b).to_string(),
+        (JsonValue::Number(n), ColumnType::Integer | ColumnType::Float) => n.to_string(),
+        (JsonValue::String(s), _) => dialect.string_literal(s),
+        (other, _) => dialect.string_literal(&other.to_string()),
+    }
+}
+
+/// Erzeugt `CREATE TABLE` und gebündelte `INSERT`-Statements aus einem geparsten Wert.
+///
+/// Die Spalten werden aus den Daten abgeleitet. Ein leeres Array oder Records ohne
+/// Felder (z.b. `[1, 2, 3]`) ergeben deshalb einen Fehler statt einer leeren Datei.
+pub fn value_to_sql_string(value: &JsonValue, options: &SqlOptions) -> Result<String, FormatError> {
+    let (headers, rows) = value_to_table(value)?;
+
+    if rows.is_empty() {
+        return Err(FormatError::SerializationError(
+            "SQL output needs at least one record to derive the columns".to_string(),
+        ));
+    }
+    if headers.is_empty() {
+        return Err(FormatError::SerializationError(
+            "SQL output needs an array of objects".to_string(),
+        ));
+    }
+
+    let dialect = options.dialect;
+    let column_types: Vec<ColumnType> = (0..headers.len())
+        .map(|i| column_type(rows.iter().map(|row| &row[i])))
+        .collect();
+
+    let table = dialect.quote_ident(&options.table);
+    let columns: Vec<String> = headers.iter().map(|h| dialect.quote_ident(h)).collect();
+
+    let mut out = format!("CREATE TABLE {} (\n", table);
+    let definitions: Vec<String> = columns
+        .iter()
+        .zip(&column_types)
+        .map(|(column, column_type)| format!("    {} {}", column, dialect.type_name(*column_type)))
+        .collect();
+    out.push_str(&definitions.join(",\n"));
+    out.push_str("\n);\n");
+
+    for batch in rows.chunks(options.batch_size.max(1)) {
+        out.push_str(&format!(
+            "\nINSERT INTO {} ({}) VALUES\n",
+            table,
+            columns.join(", ")
+        ));
+
+        let tuples: Vec<String> = batch
+            .iter()
+            .map(|row| {
+                let values: Vec<String> = row
+                    .iter()
+                    .zip(&column_types)
+                    .map(|(cell, column_type)| sql_literal(cell, *column_type, dialect))
+                    .collect();
+                format!("    ({})", values.join(", "))
+            })
+            .collect();
+        out.push_str(&tuples.join(",\n"));
+        out.push_str(";\n");
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(dialect: SqlDialect) -> SqlOptions {
+        SqlOptions {
+            dialect,
+            ..SqlOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_sql_create_table_infers_types() {
+        let value = serde_json::json!([
+            {"name": "Alice", "age": 30, "score": 1.5, "active": true},
+            {"name": "Bob", "age": 25, "score": 2, "active": false}
+        ]);
+        let result = value_to_sql_string(&value, &options(SqlDialect::Postgres)).unwrap();
+        assert!(result.contains("CREATE TABLE \"data\""));
+        assert!(result.contains("\"age\" BIGINT"));
+        assert!(result.contains("\"score\" DOUBLE PRECISION"));
+        assert!(result.contains("\"active\" BOOLEAN"));
+        assert!(result.contains("\"name\" TEXT"));
+        assert!(result.contains("(TRUE, 30, 'Alice', 1.5)"));
+    }
+
+    #[test]
+    fn test_sql_keeps_string_columns_as_text() {
+        let value = serde_json::json!([{"code": "007", "flag": "true", "n": 1}]);
+        let result = value_to_sql_string(&value, &options(SqlDialect::Sqlite)).unwrap();
+        assert!(result.contains("\"code\" TEXT"));
+        assert!(result.contains("\"flag\" TEXT"));
+        assert!(result.contains("\"n\" INTEGER"));
+        assert!(result.contains("('007', 'true', 1)"));
+    }
+
+    #[test]
+    fn test_sql_keeps_leading_zeros_quoted() {
+        let value = serde_json::json!([{"zip": "00123"}, {"zip": "98765"}]);
+        let result = value_to_sql_string(&value, &options(SqlDialect::Postgres)).unwrap();
+        assert!(result.contains("\"zip\" TEXT"));
+        assert!(result.contains("('00123'),\n    ('98765');"));
+    }
+
+    #[test]
+    fn test_sql_without_columns_fails() {
+        for value in [serde_json::json!([1, 2, 3]), serde_json::json!([]), serde_json::json!([{}])] {
+            let err = value_to_sql_string(&value, &SqlOptions::default()).unwrap_err();
+            assert!(matches!(err, FormatError::SerializationError(_)), "{}", value);
+        }
+        let err = value_to_sql_string(&serde_json::json!([1, 2, 3]), &SqlOptions::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("needs an array of objects"));
+    }
+
+    #[test]
+    fn test_sql_dialect_quoting() {
+        let value = serde_json::json!([{"name": "O'Brien"}]);
+        let sqlite = value_to_sql_string(&value, &options(SqlDialect::Sqlite)).unwrap();
+        assert!(sqlite.contains("\"name\" TEXT"));
+        assert!(sqlite.contains("('O''Brien')"));
+
+        let mysql = value_to_sql_string(&value, &options(SqlDialect::Mysql)).unwrap();
+        assert!(mysql.contains("CREATE TABLE `data`"));
+        assert!(mysql.contains("`name` TEXT"));
+    }
+
+    #[test]
+    fn test_sql_sqlite_booleans_as_integers() {
+        let value = serde_json::json!([{"flag": true}, {"flag": false}]);
+        let result = value_to_sql_string(&value, &options(SqlDialect::Sqlite)).unwrap();
+        assert!(result.contains("(1),\n    (0);"));
+    }
+
+    #[test]
+    fn test_sql_batches_inserts() {
+        let value = serde_json::json!([{"x": 1}, {"x": 2}, {"x": 3}]);
+        let opts = SqlOptions {
+            batch_size: 2,
+            ..SqlOptions::default()
+        };
+        let result = value_to_sql_string(&value, &opts).unwrap();
+        assert_eq!(result.matches("INSERT INTO").count(), 2);
+    }
+
+    #[test]
+    fn test_sql_missing_values_are_null() {
+        let value = serde_json::json!([{"a": 1, "b": "x"}, {"a": 2}]);
+        let result = value_to_sql_string(&value, &SqlOptions::default()).unwrap();
+        assert!(result.contains("(2, NULL)"));
+    }
+
+    #[test]
+    fn test_sql_mixed_column_falls_back_to_text() {
+        let value = serde_json::json!([{"v": 1}, {"v": "abc"}]);
+        let result = value_to_sql_string(&value, &SqlOptions::default()).unwrap();
+        assert!(result.contains("\"v\" TEXT"));
+        assert!(result.contains("('1')"));
+    }
+
+    #[test]
+    fn test_sql_dialect_from_str() {
+        assert_eq!("sqlite".parse::<SqlDialect>().unwrap(), SqlDialect::Sqlite);
+        assert_eq!(
+            "PostgreSQL".parse::<SqlDialect>().unwrap(),
+            SqlDialect::Postgres
+        );
+        assert_eq!("mysql".parse::<SqlDialect>().unwrap(), SqlDialect::Mysql);
+        assert!("oracle".parse::<SqlDialect>().is_err());
+    }
+}
@@ -1,7 +1,29 @@
 //! TOML-Konvertierungen: String-zu-String Funktionen.
 
 use crate::error::FormatError;
-use crate::formats::utils::flatten_json;
+use crate::formats::utils::{flatten_json, json_to_toml_value};
+
+/// Parst einen TOML String zu einem `serde_json::Value`.
+pub fn parse_toml(input: &str) -> Result<serde_json::Value, FormatError> {
+    let toml_value: toml::Value = toml::from_str(input)
+        .map_err(|e| FormatError::ParseError(format!("Invalid TOML: {}", e)))?;
+
+    serde_json::to_value(&toml_value)
+        .map_err(|e| FormatError::SerializationError(format!("Error converting: {}", e)))
+}
+
+/// Serialisiert einen `serde_json::Value` zu TOML String.
+///
+/// TOML braucht ein Objekt als Root, Arrays werden darum in `data` gewrappt.
+pub fn value_to_toml_string(value: &serde_json::Value) -> Result<String, FormatError> {
+    let toml_value = match value {
+        serde_json::Value::Array(_) => json_to_toml_value(&serde_json::json!({ "data": value }))?,
+        other => json_to_toml_value(other)?,
+    };
+
+    toml::to_string_pretty(&toml_value)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting TOML: {}", e)))
+}
 
 /// Konvertiert TOML String zu JSON String.
 pub fn toml_to_json_string(input: &str) -> Result<String, FormatError> {
@@ -91,6 +91,52 @@ pub fn flatten_json(
     result
 }
 
+/// Wie `flatten_json`, behält aber die Typen der Blattwerte bei.
+///
+/// Arrays werden wie beim CSV-Export als JSON-String abgelegt. Wird für typisierte
+/// Tabellen-Exporte (XLSX, SQL) verwendet, damit z.B. `"00123"` ein String bleibt.
+pub fn flatten_json_values(
+    value: &serde_json::Value,
+    prefix: &str,
+) -> std::collections::HashMap<String, serde_json::Value> {
+    use std::collections::HashMap;
+
+    let mut result = HashMap::new();
+
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, val) in obj {
+                let new_key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}_{}", prefix, key)
+                };
+
+                if val.is_object() {
+                    result.extend(flatten_json_values(val, &new_key));
+                } else {
+                    result.insert(new_key, flat_leaf(val));
+                }
+            }
+        }
+        _ => {
+            if !prefix.is_empty() {
+                result.insert(prefix.to_string(), flat_leaf(value));
+            }
+        }
+    }
+
+    result
+}
+
+// arrays passen in keine zelle, sie landen wie beim csv-export als json-string
+fn flat_leaf(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(_) => serde_json::Value::String(json_value_to_string(value)),
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +199,14 @@ mod tests {
         assert_eq!(flat.get("user_name").unwrap(), "Alice");
         assert_eq!(flat.get("user_address_city").unwrap(), "Zürich");
     }
+
+    #[test]
+    fn test_flatten_json_values_keeps_types() {
+        let json = serde_json::json!({"zip": "00123", "n": 1, "user": {"ok": true}, "tags": [1]});
+        let flat = flatten_json_values(&json, "");
+        assert_eq!(flat["zip"], serde_json::json!("00123"));
+        assert_eq!(flat["n"], serde_json::json!(1));
+        assert_eq!(flat["user_ok"], serde_json::json!(true));
+        assert_eq!(flat["tags"], serde_json::json!("[1]"));
+    }
 }
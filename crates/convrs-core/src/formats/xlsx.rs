@@ -0,0 +1,273 @@
+//! XLSX-Konvertierungen: Byte-basierte Funktionen für Excel-Dateien.
+//!
+//! XLSX ist ein Binärformat und läuft deshalb über `FileFormat::convert_bytes`.
+//! Gelesene Sheets ergeben dieselben Record-Objekte wie `parse_csv_to_json_values`.
+
+use std::io::Cursor;
+
+use calamine::{Data, Reader, Xlsx};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+use crate::formats::csv::value_to_table;
+
+/// Optionen für das Lesen von XLSX-Dateien.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XlsxOptions {
+    /// Name des zu lesenden Sheets. Standard: das erste Sheet.
+    pub sheet: Option<String>,
+    /// Liest alle Sheets in ein Objekt mit dem Sheet-Namen als Key.
+    pub all_sheets: bool,
+}
+
+/// Parst eine XLSX-Datei zu einer Liste von Record-Objekten (bzw. einem Objekt pro Sheet).
+pub fn parse_xlsx(input: &[u8], options: &XlsxOptions) -> Result<JsonValue, FormatError> {
+    let mut workbook = Xlsx::new(Cursor::new(input))
+        .map_err(|e| FormatError::ParseError(format!("Invalid XLSX: {}", e)))?;
+
+    let sheet_names = workbook.sheet_names();
+
+    if options.all_sheets {
+        let mut sheets = serde_json::Map::new();
+        for name in sheet_names {
+            let records = read_sheet(&mut workbook, &name)?;
+            sheets.insert(name, JsonValue::Array(records));
+        }
+        return Ok(JsonValue::Object(sheets));
+    }
+
+    let name = match &options.sheet {
+        Some(sheet) => {
+            if !sheet_names.contains(sheet) {
+                return Err(FormatError::ParseError(format!(
+                    "Sheet '{}' not found, available sheets: {}",
+                    sheet,
+                    sheet_names.join(", ")
+                )));
+            }
+            sheet.clone()
+        }
+        None => sheet_names
+            .first()
+            .cloned()
+            .ok_or_else(|| FormatError::ParseError("XLSX file contains no sheets".to_string()))?,
+    };
+
+    Ok(JsonValue::Array(read_sheet(&mut workbook, &name)?))
+}
+
+/// Schreibt einen Wert als XLSX-Datei mit typisierten Zellen.
+///
+/// Ein Objekt, dessen Werte alle Arrays sind, ergibt ein Sheet pro Key
+/// (Gegenstück zu `XlsxOptions::all_sheets`), alles andere ein einzelnes Sheet.
+pub fn value_to_xlsx_bytes(value: &JsonValue) -> Result<Vec<u8>, FormatError> {
+    let mut workbook = Workbook::new();
+
+    match value {
+        JsonValue::Object(obj) if is_sheet_map(obj) => {
+            for (name, sheet_value) in obj {
+                let worksheet = workbook.add_worksheet();
+                worksheet.set_name(name).map_err(xlsx_error)?;
+                write_sheet(worksheet, sheet_value)?;
+            }
+        }
+        _ => write_sheet(workbook.add_worksheet(), value)?,
+    }
+
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+// private helper funktionen
+
+/// Prüft, ob ein Objekt ein Sheet pro Key beschreibt (ein `data`-Wrapper zählt nicht).
+fn is_sheet_map(obj: &serde_json::Map<String, JsonValue>) -> bool {
+    let is_data_wrapper = obj.len() == 1 && obj.contains_key("data");
+    !obj.is_empty() && !is_data_wrapper && obj.values().all(|v| v.is_array())
+}
+
+fn read_sheet(
+    workbook: &mut Xlsx<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Vec<JsonValue>, FormatError> {
+    let range = workbook
+        .worksheet_range(name)
+        .map_err(|e| FormatError::ParseError(format!("Error reading sheet '{}': {}", name, e)))?;
+
+    let mut rows = range
+        .rows()
+        .filter(|row| row.iter().any(|cell| *cell != Data::Empty));
+
+    let headers: Vec<String> = match rows.next() {
+        Some(row) => row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| match cell {
+                Data::Empty => format!("column_{}", i + 1),
+                other => other.to_string(),
+            })
+            .collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let records = rows
+        .map(|row| {
+            let mut obj = serde_json::Map::new();
+            for (header, cell) in headers.iter().zip(row) {
+                obj.insert(header.clone(), cell_to_json(cell));
+            }
+            JsonValue::Object(obj)
+        })
+        .collect();
+
+    Ok(records)
+}
+
+/// Konvertiert eine XLSX-Zelle in einen JSON-Wert.
+fn cell_to_json(cell: &Data) -> JsonValue {
+    match cell {
+        Data::Empty => JsonValue::Null,
+        Data::Bool(b) => JsonValue::Bool(*b),
+        Data::Int(i) => JsonValue::Number((*i).into()),
+        // Excel speichert alle Zahlen als Float, ganze Zahlen bleiben ganze Zahlen
+        Data::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+            JsonValue::Number((*f as i64).into())
+        }
+        Data::Float(f) => serde_json::Number::from_f64(*f)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null),
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => {
+            JsonValue::String(s.clone())
+        }
+        Data::DateTime(dt) => match dt.as_datetime() {
+            Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => {
+                JsonValue::String(datetime.date().to_string())
+            }
+            Some(datetime) => JsonValue::String(datetime.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            None => serde_json::Number::from_f64(dt.as_f64())
+                .map(JsonValue::Number)
+                .unwrap_or(JsonValue::Null),
+        },
+        Data::Error(e) => JsonValue::String(e.to_string()),
+    }
+}
+
+fn write_sheet(worksheet: &mut Worksheet, value: &JsonValue) -> Result<(), FormatError> {
+    let (headers, rows) = value_to_table(value)?;
+    let header_format = Format::new().set_bold();
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, header, &header_format)
+            .map_err(xlsx_error)?;
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        let row_idx = (i + 1) as u32;
+        for (col, cell) in row.iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                JsonValue::Null => {}
+                JsonValue::Bool(b) => {
+                    worksheet
+                        .write_boolean(row_idx, col, *b)
+                        .map_err(xlsx_error)?;
+                }
+                JsonValue::Number(n) => {
+                    let number = n.as_f64().unwrap_or_default();
+                    worksheet
+                        .write_number(row_idx, col, number)
+                        .map_err(xlsx_error)?;
+                }
+                JsonValue::String(s) => {
+                    worksheet
+                        .write_string(row_idx, col, s)
+                        .map_err(xlsx_error)?;
+                }
+                other => {
+                    worksheet
+                        .write_string(row_idx, col, other.to_string())
+                        .map_err(xlsx_error)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn xlsx_error(e: rust_xlsxwriter::XlsxError) -> FormatError {
+    FormatError::SerializationError(format!("Error writing XLSX: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xlsx_roundtrip_keeps_types() {
+        let value = serde_json::json!([
+            {"name": "Alice", "age": 30, "score": 1.5, "active": true},
+            {"name": "Bob", "age": 25, "score": 2.25, "active": false}
+        ]);
+        let bytes = value_to_xlsx_bytes(&value).unwrap();
+        let parsed = parse_xlsx(&bytes, &XlsxOptions::default()).unwrap();
+        assert_eq!(parsed, value);
+    }
+
+    #[test]
+    fn test_xlsx_roundtrip_keeps_numeric_looking_strings() {
+        let value = serde_json::json!([{"zip": "00123", "flag": "true", "n": 1}]);
+        let bytes = value_to_xlsx_bytes(&value).unwrap();
+        let parsed = parse_xlsx(&bytes, &XlsxOptions::default()).unwrap();
+        assert_eq!(parsed, value);
+    }
+
+    #[test]
+    fn test_xlsx_all_sheets() {
+        let value = serde_json::json!({
+            "users": [{"name": "Alice"}],
+            "teams": [{"team": "core"}]
+        });
+        let bytes = value_to_xlsx_bytes(&value).unwrap();
+        let options = XlsxOptions {
+            all_sheets: true,
+            ..XlsxOptions::default()
+        };
+        let parsed = parse_xlsx(&bytes, &options).unwrap();
+        assert_eq!(parsed["users"][0]["name"], "Alice");
+        assert_eq!(parsed["teams"][0]["team"], "core");
+    }
+
+    #[test]
+    fn test_xlsx_select_sheet() {
+        let value = serde_json::json!({
+            "first": [{"a": 1}],
+            "second": [{"b": 2}]
+        });
+        let bytes = value_to_xlsx_bytes(&value).unwrap();
+        let options = XlsxOptions {
+            sheet: Some("second".to_string()),
+            ..XlsxOptions::default()
+        };
+        let parsed = parse_xlsx(&bytes, &options).unwrap();
+        assert_eq!(parsed, serde_json::json!([{"b": 2}]));
+    }
+
+    #[test]
+    fn test_xlsx_unknown_sheet_fails() {
+        let bytes = value_to_xlsx_bytes(&serde_json::json!([{"a": 1}])).unwrap();
+        let options = XlsxOptions {
+            sheet: Some("missing".to_string()),
+            ..XlsxOptions::default()
+        };
+        let err = parse_xlsx(&bytes, &options).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_xlsx_invalid_input_fails() {
+        let result = parse_xlsx(b"not a zip file", &XlsxOptions::default());
+        assert!(matches!(result.unwrap_err(), FormatError::ParseError(_)));
+    }
+}
@@ -3,6 +3,21 @@
 use crate::error::FormatError;
 use crate::formats::utils::{flatten_json, json_to_toml_value};
 
+/// Parst einen YAML String zu einem `serde_json::Value`.
+pub fn parse_yaml(input: &str) -> Result<serde_json::Value, FormatError> {
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(input)
+        .map_err(|e| FormatError::ParseError(format!("Invalid YAML: {}", e)))?;
+
+    serde_json::to_value(&yaml_value)
+        .map_err(|e| FormatError::SerializationError(format!("Error converting: {}", e)))
+}
+
+/// Serialisiert einen `serde_json::Value` zu YAML String.
+pub fn value_to_yaml_string(value: &serde_json::Value) -> Result<String, FormatError> {
+    serde_yaml::to_string(value)
+        .map_err(|e| FormatError::SerializationError(format!("Error formatting YAML: {}", e)))
+}
+
 /// Konvertiert YAML String zu JSON String.
 pub fn yaml_to_json_string(input: &str) -> Result<String, FormatError> {
     let yaml_value: serde_yaml::Value = serde_yaml::from_str(input)
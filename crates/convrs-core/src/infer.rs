@@ -0,0 +1,381 @@
+//! Leitet ein JSON Schema (Draft 2020-12) aus Beispieldaten ab.
+//!
+//! Alle Beispiele werden in eine gemeinsame Struktur (`Shape`) gemergt: beobachtete
+//! Typen, Keys, die in jedem Objekt vorkommen (`required`), wiederkehrende Strings
+//! (`enum`) und gemeinsame String-Formate (`date`, `date-time`, `email`, `uri`).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{Map, Value as JsonValue, json};
+
+use crate::ConvertOptions;
+use crate::error::FormatError;
+use crate::format::FileFormat;
+use crate::formats::csv::value_to_table;
+
+pub const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Optionen für `infer_schema`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferOptions {
+    /// Maximale Anzahl verschiedener Werte, für die noch ein `enum` erzeugt wird (0: nie).
+    pub max_enum_values: usize,
+}
+
+impl Default for InferOptions {
+    fn default() -> Self {
+        InferOptions {
+            max_enum_values: 10,
+        }
+    }
+}
+
+/// Leitet ein Schema ab, das alle `samples` erfüllen.
+pub fn infer_schema<'a, I>(samples: I, options: &InferOptions) -> JsonValue
+where
+    I: IntoIterator<Item = &'a JsonValue>,
+{
+    let mut shape = Shape::default();
+    for sample in samples {
+        shape.observe(sample);
+    }
+
+    let mut schema = shape.to_schema(options);
+    if let JsonValue::Object(obj) = &mut schema {
+        obj.insert("$schema".to_string(), json!(SCHEMA_DRAFT));
+    }
+    schema
+}
+
+/// Parst ein Beispiel. Tabellarische Formate (CSV, XLSX, Fixed-Width) werden wie beim
+/// SQL-Export in Tabellenform gebracht, verschachtelte Spalten also geflattet.
+pub fn sample_from_bytes(
+    format: FileFormat,
+    input: &[u8],
+    options: &ConvertOptions,
+) -> Result<JsonValue, FormatError> {
+    let value = format.parse_bytes(input, options)?;
+
+    match format {
+        FileFormat::Csv | FileFormat::Xlsx | FileFormat::FixedWidth => {
+            let (headers, rows) = value_to_table(&value)?;
+            let records = rows
+                .into_iter()
+                .map(|row| JsonValue::Object(headers.iter().cloned().zip(row).collect()))
+                .collect();
+            Ok(JsonValue::Array(records))
+        }
+        _ => Ok(value),
+    }
+}
+
+// private helper funktionen
+
+/// Gemergte Beobachtungen für eine Stelle im Dokument.
+#[derive(Default)]
+struct Shape {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    strings: Option<StringShape>,
+    /// Elemente aller beobachteten Arrays (`None`: kein Array gesehen).
+    items: Option<Box<Shape>>,
+    object: Option<ObjectShape>,
+    /// wie oft die stelle vorkam, für `required`
+    count: usize,
+}
+
+struct StringShape {
+    count: usize,
+    values: BTreeSet<String>,
+    /// `None`: noch kein String gesehen, `Some(None)`: keine gemeinsame Format-Angabe.
+    format: Option<Option<&'static str>>,
+}
+
+#[derive(Default)]
+struct ObjectShape {
+    count: usize,
+    properties: BTreeMap<String, Shape>,
+}
+
+impl Shape {
+    fn observe(&mut self, value: &JsonValue) {
+        self.count += 1;
+        match value {
+            JsonValue::Null => self.null = true,
+            JsonValue::Bool(_) => self.boolean = true,
+            JsonValue::Number(n) if n.is_i64() || n.is_u64() => self.integer = true,
+            JsonValue::Number(_) => self.number = true,
+            JsonValue::String(s) => {
+                let strings = self.strings.get_or_insert_with(|| StringShape {
+                    count: 0,
+                    values: BTreeSet::new(),
+                    format: None,
+                });
+                strings.count += 1;
+                strings.values.insert(s.clone());
+                let format = string_format(s);
+                strings.format = match strings.format {
+                    None => Some(format),
+                    Some(current) if current == format => Some(current),
+                    Some(_) => Some(None),
+                };
+            }
+            JsonValue::Array(arr) => {
+                let items = self.items.get_or_insert_with(Box::default);
+                for item in arr {
+                    items.observe(item);
+                }
+            }
+            JsonValue::Object(obj) => {
+                let object = self.object.get_or_insert_with(ObjectShape::default);
+                object.count += 1;
+                for (key, value) in obj {
+                    object
+                        .properties
+                        .entry(key.clone())
+                        .or_default()
+                        .observe(value);
+                }
+            }
+        }
+    }
+
+    fn to_schema(&self, options: &InferOptions) -> JsonValue {
+        let mut schema = Map::new();
+        let mut types = Vec::new();
+
+        if let Some(object) = &self.object {
+            types.push("object");
+            let properties: Map<String, JsonValue> = object
+                .properties
+                .iter()
+                .map(|(key, shape)| (key.clone(), shape.to_schema(options)))
+                .collect();
+            let required: Vec<JsonValue> = object
+                .properties
+                .iter()
+                .filter(|(_, shape)| shape.count == object.count)
+                .map(|(key, _)| json!(key))
+                .collect();
+
+            schema.insert("properties".to_string(), JsonValue::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), JsonValue::Array(required));
+            }
+        }
+        if let Some(items) = &self.items {
+            types.push("array");
+            // ohne beobachtete elemente (nur leere arrays) bleibt `items` offen
+            if items.count > 0 {
+                schema.insert("items".to_string(), items.to_schema(options));
+            }
+        }
+        let mut enum_values = None;
+        if let Some(strings) = &self.strings {
+            types.push("string");
+            match strings.format {
+                Some(Some(format)) => {
+                    schema.insert("format".to_string(), json!(format));
+                }
+                // enum nur, wenn sich werte wiederholen und es wenige sind
+                _ if strings.values.len() <= options.max_enum_values
+                    && strings.count > strings.values.len() =>
+                {
+                    enum_values = Some(&strings.values);
+                }
+                _ => {}
+            }
+        }
+        if self.number {
+            types.push("number");
+        } else if self.integer {
+            types.push("integer");
+        }
+        if self.boolean {
+            types.push("boolean");
+        }
+        if self.null {
+            types.push("null");
+        }
+
+        // ein enum würde alle anderen typen ausschliessen, null bleibt erlaubt
+        if let Some(values) = enum_values
+            && types.iter().all(|t| matches!(*t, "string" | "null"))
+        {
+            let mut values: Vec<JsonValue> = values.iter().map(|v| json!(v)).collect();
+            if self.null {
+                values.push(JsonValue::Null);
+            }
+            schema.insert("enum".to_string(), JsonValue::Array(values));
+        }
+
+        match types.as_slice() {
+            [] => {}
+            [single] => {
+                schema.insert("type".to_string(), json!(single));
+            }
+            _ => {
+                schema.insert("type".to_string(), json!(types));
+            }
+        }
+
+        JsonValue::Object(schema)
+    }
+}
+
+/// Erkennt die gängigen String-Formate aus JSON Schema.
+fn string_format(s: &str) -> Option<&'static str> {
+    if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        return Some("date");
+    }
+    if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+        return Some("date-time");
+    }
+    if is_email(s) {
+        return Some("email");
+    }
+    if is_uri(s) {
+        return Some("uri");
+    }
+    None
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !s.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn is_uri(s: &str) -> bool {
+    match s.split_once("://") {
+        Some((scheme, rest)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !rest.is_empty()
+                && !s.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn infer(samples: &[JsonValue]) -> JsonValue {
+        infer_schema(samples, &InferOptions::default())
+    }
+
+    #[test]
+    fn test_infer_object_types_and_required() {
+        let schema = infer(&[
+            json!({"name": "web", "port": 80, "ratio": 1, "tags": ["a"]}),
+            json!({"name": "db", "port": 5432, "ratio": 0.5, "debug": true}),
+        ]);
+
+        assert_eq!(schema["$schema"], json!(SCHEMA_DRAFT));
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["required"], json!(["name", "port", "ratio"]));
+        assert_eq!(schema["properties"]["port"], json!({"type": "integer"}));
+        assert_eq!(schema["properties"]["ratio"], json!({"type": "number"}));
+        assert_eq!(schema["properties"]["debug"], json!({"type": "boolean"}));
+        assert_eq!(
+            schema["properties"]["tags"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+    }
+
+    #[test]
+    fn test_infer_enum_and_nullable() {
+        let schema = infer(&[json!([
+            {"level": "info", "user": "ann", "code": null},
+            {"level": "warn", "user": "bob", "code": 1},
+            {"level": "info", "user": "cid", "code": 2}
+        ])]);
+
+        let item = &schema["items"];
+        assert_eq!(
+            item["properties"]["level"],
+            json!({"type": "string", "enum": ["info", "warn"]})
+        );
+        // keine wiederholung, also kein enum
+        assert_eq!(item["properties"]["user"], json!({"type": "string"}));
+        assert_eq!(
+            item["properties"]["code"],
+            json!({"type": ["integer", "null"]})
+        );
+    }
+
+    #[test]
+    fn test_infer_enum_only_for_pure_strings() {
+        let schema = infer(&[json!(["a", "a", 1, null]), json!(["b", "b", null])]);
+        assert_eq!(
+            schema["items"],
+            json!({"type": ["string", "integer", "null"]})
+        );
+
+        let schema = infer(&[json!(["a", "a", null])]);
+        assert_eq!(
+            schema["items"],
+            json!({"type": ["string", "null"], "enum": ["a", null]})
+        );
+    }
+
+    #[test]
+    fn test_infer_string_formats() {
+        let schema = infer(&[json!({
+            "born": ["2024-01-31", "1999-12-01"],
+            "seen": "2024-01-31T10:00:00Z",
+            "mail": "ann@example.com",
+            "home": "https://example.com/ann",
+            "mixed": ["2024-01-31", "soon"]
+        })]);
+
+        let props = &schema["properties"];
+        assert_eq!(props["born"]["items"]["format"], json!("date"));
+        assert_eq!(props["seen"]["format"], json!("date-time"));
+        assert_eq!(props["mail"]["format"], json!("email"));
+        assert_eq!(props["home"]["format"], json!("uri"));
+        assert!(props["mixed"]["items"].get("format").is_none());
+    }
+
+    #[test]
+    fn test_infer_from_csv_uses_type_inference() {
+        let sample = sample_from_bytes(
+            FileFormat::Csv,
+            b"id,name,active\n1,ann,true\n2,bob,false\n",
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+        let schema = infer(&[sample]);
+
+        assert_eq!(schema["type"], json!("array"));
+        let props = &schema["items"]["properties"];
+        assert_eq!(props["id"]["type"], json!("integer"));
+        assert_eq!(props["active"]["type"], json!("boolean"));
+    }
+
+    #[test]
+    fn test_inferred_schema_validates_samples() {
+        let samples = [
+            json!({"a": 1, "b": ["x", "y", "x"], "c": {"d": null}}),
+            json!({"a": 2.5, "b": [], "c": {"d": "2024-01-01"}}),
+        ];
+        let schema = crate::validate::Schema::new(&infer(&samples)).unwrap();
+        for sample in &samples {
+            assert!(schema.validate(sample).is_empty());
+        }
+    }
+}
@@ -1,9 +1,25 @@
 //! hier befindet sich die core-bibliothek für die konvertierungslogik.
 
+pub mod codegen;
+pub mod diagnostic;
+pub mod diff;
+pub mod edit;
 pub mod error;
 pub mod format;
+pub mod fmt;
 pub mod formats;
+pub mod infer;
+pub mod lint;
+pub mod location;
+pub mod merge;
+pub mod options;
+pub mod patch;
+pub mod query;
+pub mod repair;
+pub mod validate;
 
 // re-exports für einfachen zugang
 pub use error::FormatError;
 pub use format::FileFormat;
+pub use options::ConvertOptions;
+pub use query::{KeyPath, Query};
@@ -0,0 +1,292 @@
+//! Lint-Regeln für Datendateien (`convrs fmt --lint`).
+//!
+//! - `duplicate-key`: ein Key kommt im selben Objekt mehrfach vor (JSON, YAML)
+//! - `yaml-tab`: Tabs in der Einrückung (YAML)
+//! - `yaml-ambiguous-bool`: unquotierte Werte wie `yes` oder `on`, die in YAML 1.1 Booleans
+//!   und in YAML 1.2 Strings sind
+//! - `mixed-type-array`: Arrays mit Elementen verschiedener Typen (alle Formate, `null` zählt nicht)
+//! - `syntax`: das Dokument kann nicht gelesen werden
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+use yaml_rust2::Event;
+use yaml_rust2::parser::Parser as YamlParser;
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+use crate::ConvertOptions;
+use crate::format::FileFormat;
+use crate::location::{Location, locate};
+use crate::query::{KeyPath, PathSegment, type_name};
+
+/// Werte, die YAML 1.1 als Boolean liest (`true`/`false` sind in beiden Versionen gleich).
+const AMBIGUOUS_BOOLS: &[&str] = &[
+    "y", "Y", "yes", "Yes", "YES", "n", "N", "no", "No", "NO", "on", "On", "ON", "off", "Off",
+    "OFF",
+];
+
+/// Ein Fund einer Lint-Regel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    pub rule: &'static str,
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// Prüft ein Dokument auf alle Regeln. Ungültige Dokumente ergeben ein `syntax`-Issue,
+/// die Funden sind nach Position sortiert.
+pub fn lint_bytes(format: FileFormat, input: &[u8], options: &ConvertOptions) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    if let Ok(text) = std::str::from_utf8(input) {
+        if format == FileFormat::Yaml {
+            lint_yaml_tabs(text, &mut issues);
+        }
+        if matches!(format, FileFormat::Json | FileFormat::Yaml) {
+            lint_yaml_events(text, format == FileFormat::Yaml, &mut issues);
+        }
+    }
+
+    match format.parse_bytes(input, options) {
+        Ok(value) => lint_arrays(&value, &mut Vec::new(), format, input, &mut issues),
+        // syntaxfehler und doppelte keys (serde_yaml lehnt sie ab) sind für JSON und YAML
+        // schon mit position gemeldet
+        Err(e)
+            if !issues
+                .iter()
+                .any(|issue| matches!(issue.rule, "syntax" | "duplicate-key")) =>
+        {
+            issues.push(LintIssue {
+                rule: "syntax",
+                message: e.to_string(),
+                location: None,
+            })
+        }
+        Err(_) => {}
+    }
+
+    issues.sort_by_key(|issue| issue.location.map(|l| (l.line, l.column)));
+    issues
+}
+
+// private helper funktionen
+
+fn lint_yaml_tabs(text: &str, issues: &mut Vec<LintIssue>) {
+    for (i, line) in text.lines().enumerate() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        if let Some(column) = indent.find('\t') {
+            issues.push(LintIssue {
+                rule: "yaml-tab",
+                message: "tab in indentation, YAML only allows spaces".to_string(),
+                location: Some(Location {
+                    line: i + 1,
+                    column: column + 1,
+                }),
+            });
+        }
+    }
+}
+
+enum Frame {
+    Mapping {
+        keys: HashSet<String>,
+        expect_key: bool,
+    },
+    Sequence,
+}
+
+/// Doppelte Keys und mehrdeutige Booleans über den YAML-Event-Parser (JSON ist fast
+/// eine Teilmenge von YAML).
+fn lint_yaml_events(text: &str, is_yaml: bool, issues: &mut Vec<LintIssue>) {
+    let mut parser = YamlParser::new_from_str(text);
+    let mut stack: Vec<Frame> = Vec::new();
+
+    loop {
+        let (event, marker) = match parser.next_token() {
+            Ok(token) => token,
+            Err(e) => {
+                issues.push(LintIssue {
+                    rule: "syntax",
+                    message: e.info().to_string(),
+                    location: Some(to_location(e.marker())),
+                });
+                return;
+            }
+        };
+
+        // jeder knoten ist abwechselnd key und wert der umgebenden map
+        let is_key = match stack.last_mut() {
+            Some(Frame::Mapping { expect_key, .. })
+                if matches!(
+                    event,
+                    Event::Scalar(..)
+                        | Event::Alias(..)
+                        | Event::MappingStart(..)
+                        | Event::SequenceStart(..)
+                ) =>
+            {
+                *expect_key = !*expect_key;
+                !*expect_key
+            }
+            _ => false,
+        };
+
+        match event {
+            Event::StreamEnd => return,
+            Event::Scalar(value, style, ..) => {
+                if is_key
+                    && let Some(Frame::Mapping { keys, .. }) = stack.last_mut()
+                    && !keys.insert(value.clone())
+                {
+                    issues.push(LintIssue {
+                        rule: "duplicate-key",
+                        message: format!("duplicate key '{}'", value),
+                        location: Some(to_location(&marker)),
+                    });
+                }
+                if is_yaml && style == TScalarStyle::Plain && AMBIGUOUS_BOOLS.contains(&&*value) {
+                    issues.push(LintIssue {
+                        rule: "yaml-ambiguous-bool",
+                        message: format!(
+                            "'{}' is a boolean in YAML 1.1 but a string in YAML 1.2, quote it or use true/false",
+                            value
+                        ),
+                        location: Some(to_location(&marker)),
+                    });
+                }
+            }
+            Event::MappingStart(..) => stack.push(Frame::Mapping {
+                keys: HashSet::new(),
+                expect_key: true,
+            }),
+            Event::SequenceStart(..) => stack.push(Frame::Sequence),
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lint_arrays(
+    value: &JsonValue,
+    path: &mut Vec<PathSegment>,
+    format: FileFormat,
+    input: &[u8],
+    issues: &mut Vec<LintIssue>,
+) {
+    match value {
+        JsonValue::Array(items) => {
+            let types: BTreeSet<&str> = items
+                .iter()
+                .filter(|item| !item.is_null())
+                .map(type_name)
+                .collect();
+            if types.len() > 1 {
+                let key_path = KeyPath::from_segments(path.clone());
+                issues.push(LintIssue {
+                    rule: "mixed-type-array",
+                    message: format!(
+                        "array {} mixes types: {}",
+                        key_path,
+                        types.into_iter().collect::<Vec<_>>().join(", ")
+                    ),
+                    location: locate(format, input, &key_path),
+                });
+            }
+            for (i, item) in items.iter().enumerate() {
+                path.push(PathSegment::Index(i as i64));
+                lint_arrays(item, path, format, input, issues);
+                path.pop();
+            }
+        }
+        JsonValue::Object(obj) => {
+            for (key, item) in obj {
+                path.push(PathSegment::Key(key.clone()));
+                lint_arrays(item, path, format, input, issues);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn to_location(marker: &Marker) -> Location {
+    Location {
+        line: marker.line(),
+        // yaml-rust2 zählt spalten ab 0
+        column: marker.col() + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(format: FileFormat, input: &str) -> Vec<(&'static str, Option<(usize, usize)>)> {
+        lint_bytes(format, input.as_bytes(), &ConvertOptions::default())
+            .into_iter()
+            .map(|issue| (issue.rule, issue.location.map(|l| (l.line, l.column))))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_yaml() {
+        let input =
+            "on: push\nname: ci\nflags:\n  debug: yes\n  quoted: \"no\"\nports: [80, \"443\"]\n";
+        assert_eq!(
+            lint(FileFormat::Yaml, input),
+            vec![
+                ("yaml-ambiguous-bool", Some((1, 1))),
+                ("yaml-ambiguous-bool", Some((4, 10))),
+                ("mixed-type-array", Some((6, 1))),
+            ]
+        );
+
+        // doppelte keys ohne zusätzliches syntax-issue von serde_yaml
+        assert_eq!(
+            lint(FileFormat::Yaml, "name: ci\nname: again\n"),
+            vec![("duplicate-key", Some((2, 1)))]
+        );
+    }
+
+    #[test]
+    fn test_lint_json_duplicate_keys_per_object() {
+        let input = "{\n  \"a\": {\"x\": 1},\n  \"b\": {\"x\": 2, \"x\": 3}\n}";
+        assert_eq!(
+            lint(FileFormat::Json, input),
+            vec![("duplicate-key", Some((3, 17)))]
+        );
+        // null mischt keine typen, ganzzahlen und kommazahlen sind beide zahlen
+        assert!(lint(FileFormat::Json, "[1, 2.5, null]").is_empty());
+    }
+
+    #[test]
+    fn test_lint_yaml_tabs_and_syntax() {
+        let issues = lint(FileFormat::Yaml, "a:\n\tb: 1\n");
+        assert_eq!(issues[0], ("yaml-tab", Some((2, 1))));
+        assert!(issues.iter().any(|(rule, _)| *rule == "syntax"));
+    }
+
+    #[test]
+    fn test_lint_toml_arrays() {
+        let issues = lint_bytes(
+            FileFormat::Toml,
+            b"[server]\nhosts = [\"a\", 1]\n",
+            &ConvertOptions::default(),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].to_string(),
+            "[mixed-type-array] array .server.hosts mixes types: number, string"
+        );
+        assert_eq!(issues[0].location, Some(Location { line: 2, column: 1 }));
+    }
+}
@@ -0,0 +1,241 @@
+//! Findet die Quellposition (Zeile und Spalte) eines Pfads im Originaltext.
+//!
+//! Das gemeinsame Datenmodell kennt keine Positionen mehr, deshalb wird der Text
+//! für Meldungen (z.b. Schema-Validierung) ein zweites Mal mit Positionen gelesen.
+//! Unterstützt werden JSON und YAML (über den YAML-Event-Parser) sowie TOML.
+
+use std::fmt;
+use std::ops::Range;
+
+use toml_edit::{ImDocument, Item, Table, Value as TomlValue};
+use yaml_rust2::Event;
+use yaml_rust2::parser::Parser as YamlParser;
+
+use crate::format::FileFormat;
+use crate::query::{KeyPath, PathSegment, resolve_index};
+
+/// Position im Quelltext, Zeile und Spalte beginnen bei 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Sucht die Position von `path` in `input`.
+///
+/// Bei Keys wird die Position des Keys geliefert, bei Array-Elementen die des Elements.
+/// Existiert der Pfad nicht vollständig, wird die Position des tiefsten gefundenen
+/// Vorfahren geliefert. `None` für Formate ohne Positionsinformation oder ungültigen Text.
+pub fn locate(format: FileFormat, input: &[u8], path: &KeyPath) -> Option<Location> {
+    let text = std::str::from_utf8(input).ok()?;
+    match format {
+        FileFormat::Json | FileFormat::Yaml => locate_yaml(text, path.segments()),
+        FileFormat::Toml => {
+            let offset = locate_toml(text, path.segments())?;
+            Some(offset_to_location(text, offset))
+        }
+        _ => None,
+    }
+}
+
+// private helper funktionen
+
+/// JSON ist (fast) eine Teilmenge von YAML, deshalb reicht ein Event-Parser für beide.
+fn locate_yaml(text: &str, segments: &[PathSegment]) -> Option<Location> {
+    let mut parser = YamlParser::new_from_str(text);
+    let mut events = Vec::new();
+    loop {
+        let (event, marker) = parser.next_token().ok()?;
+        if event == Event::StreamEnd {
+            break;
+        }
+        events.push((
+            event,
+            Location {
+                line: marker.line(),
+                // yaml-rust2 zählt spalten ab 0
+                column: marker.col() + 1,
+            },
+        ));
+    }
+
+    // erster knoten nach StreamStart/DocumentStart
+    let mut pos = events
+        .iter()
+        .position(|(event, _)| !matches!(event, Event::StreamStart | Event::DocumentStart))?;
+    let mut found = events[pos].1;
+
+    for segment in segments {
+        match (&events[pos].0, segment) {
+            (Event::MappingStart(..), PathSegment::Key(key)) => {
+                pos += 1;
+                loop {
+                    match &events.get(pos)?.0 {
+                        Event::MappingEnd => return Some(found),
+                        Event::Scalar(name, ..) if name == key => {
+                            found = events[pos].1;
+                            pos += 1;
+                            break;
+                        }
+                        _ => {
+                            // key und wert überspringen
+                            pos = skip_node(&events, pos)?;
+                            pos = skip_node(&events, pos)?;
+                        }
+                    }
+                }
+            }
+            (Event::SequenceStart(..), PathSegment::Index(index)) => {
+                let mut starts = Vec::new();
+                let mut item = pos + 1;
+                while !matches!(events.get(item)?.0, Event::SequenceEnd) {
+                    starts.push(item);
+                    item = skip_node(&events, item)?;
+                }
+                match resolve_index(*index, starts.len()).and_then(|i| starts.get(i)) {
+                    Some(&start) => {
+                        pos = start;
+                        found = events[pos].1;
+                    }
+                    None => return Some(found),
+                }
+            }
+            _ => return Some(found),
+        }
+    }
+
+    Some(found)
+}
+
+/// Liefert den Index des ersten Events nach dem Knoten, der bei `pos` beginnt.
+fn skip_node(events: &[(Event, Location)], pos: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut pos = pos;
+    loop {
+        match events.get(pos)?.0 {
+            Event::MappingStart(..) | Event::SequenceStart(..) => depth += 1,
+            Event::MappingEnd | Event::SequenceEnd => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+        pos += 1;
+        if depth == 0 {
+            return Some(pos);
+        }
+    }
+}
+
+enum TomlNode<'a> {
+    Item(&'a Item),
+    Table(&'a Table),
+    Value(&'a TomlValue),
+}
+
+fn locate_toml(text: &str, segments: &[PathSegment]) -> Option<usize> {
+    let document = ImDocument::parse(text).ok()?;
+    let mut node = TomlNode::Table(document.as_table());
+    let mut found = 0;
+
+    for segment in segments {
+        let (child, span): (TomlNode, Option<Range<usize>>) = match (node, segment) {
+            (TomlNode::Table(table), PathSegment::Key(key))
+            | (TomlNode::Item(Item::Table(table)), PathSegment::Key(key)) => {
+                let Some((key, item)) = table.get_key_value(key) else {
+                    break;
+                };
+                (TomlNode::Item(item), key.span())
+            }
+            (TomlNode::Item(Item::Value(TomlValue::InlineTable(table))), PathSegment::Key(key))
+            | (TomlNode::Value(TomlValue::InlineTable(table)), PathSegment::Key(key)) => {
+                let Some((key, item)) = table.get_key_value(key) else {
+                    break;
+                };
+                (TomlNode::Item(item), key.span())
+            }
+            (TomlNode::Item(Item::Value(TomlValue::Array(array))), PathSegment::Index(index))
+            | (TomlNode::Value(TomlValue::Array(array)), PathSegment::Index(index)) => {
+                let Some(value) = resolve_index(*index, array.len()).and_then(|i| array.get(i))
+                else {
+                    break;
+                };
+                (TomlNode::Value(value), value.span())
+            }
+            (TomlNode::Item(Item::ArrayOfTables(tables)), PathSegment::Index(index)) => {
+                let Some(table) = resolve_index(*index, tables.len()).and_then(|i| tables.get(i))
+                else {
+                    break;
+                };
+                (TomlNode::Table(table), table.span())
+            }
+            _ => break,
+        };
+
+        // implizite tabellen haben keine position, dann bleibt die des vorfahren
+        if let Some(span) = span {
+            found = span.start;
+        }
+        node = child;
+    }
+
+    Some(found)
+}
+
+pub(crate) fn offset_to_location(text: &str, offset: usize) -> Location {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(format: FileFormat, input: &str, path: &str) -> Option<(usize, usize)> {
+        let path: KeyPath = path.parse().unwrap();
+        locate(format, input.as_bytes(), &path).map(|l| (l.line, l.column))
+    }
+
+    #[test]
+    fn test_locate_yaml() {
+        let input = "name: app\ndb:\n  host: localhost\n  ports:\n    - 80\n    - 443\n";
+        assert_eq!(at(FileFormat::Yaml, input, ".name"), Some((1, 1)));
+        assert_eq!(at(FileFormat::Yaml, input, ".db.host"), Some((3, 3)));
+        assert_eq!(at(FileFormat::Yaml, input, ".db.ports[1]"), Some((6, 7)));
+        assert_eq!(at(FileFormat::Yaml, input, ".db.ports[-1]"), Some((6, 7)));
+        // fehlender key: position des vorfahren
+        assert_eq!(at(FileFormat::Yaml, input, ".db.user"), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_locate_json() {
+        let input = "{\n  \"a\": {\"b\": [1, {\"c\": true}]},\n  \"d\": null\n}";
+        assert_eq!(at(FileFormat::Json, input, ".a.b[1].c"), Some((2, 19)));
+        assert_eq!(at(FileFormat::Json, input, ".d"), Some((3, 3)));
+    }
+
+    #[test]
+    fn test_locate_toml() {
+        let input = "title = \"x\"\n\n[server]\nport = 8080\nhosts = [\"a\", \"b\"]\n\n[[users]]\nname = \"ann\"\n\n[[users]]\nname = \"bob\"\n";
+        assert_eq!(at(FileFormat::Toml, input, ".title"), Some((1, 1)));
+        assert_eq!(at(FileFormat::Toml, input, ".server.port"), Some((4, 1)));
+        assert_eq!(
+            at(FileFormat::Toml, input, ".server.hosts[1]"),
+            Some((5, 15))
+        );
+        assert_eq!(at(FileFormat::Toml, input, ".users[1].name"), Some((11, 1)));
+    }
+
+    #[test]
+    fn test_locate_unsupported() {
+        assert_eq!(at(FileFormat::Csv, "a,b\n1,2\n", ".[0].a"), None);
+        assert_eq!(at(FileFormat::Yaml, "a: [", ".a"), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Deep-Merge mehrerer Dokumente auf dem gemeinsamen Datenmodell.
+//!
+//! Spätere Dokumente überschreiben frühere. Objekte werden rekursiv gemergt,
+//! für Arrays und `null` gibt es eigene Strategien (`MergeOptions`).
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Wie Arrays aus einem späteren Dokument mit bestehenden Arrays kombiniert werden.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ArrayStrategy {
+    /// Das spätere Array ersetzt das frühere.
+    #[default]
+    Replace,
+    /// Die Elemente des späteren Arrays werden angehängt.
+    Append,
+    /// Objekte mit gleichem Wert im angegebenen Feld werden gemergt, alle anderen angehängt.
+    MergeByKey(String),
+}
+
+/// Parst `replace`, `append` oder `key:<feld>`, z.b. `key:name`.
+impl FromStr for ArrayStrategy {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("key", field)) if !field.is_empty() => {
+                Ok(ArrayStrategy::MergeByKey(field.to_string()))
+            }
+            None if s.eq_ignore_ascii_case("replace") => Ok(ArrayStrategy::Replace),
+            None if s.eq_ignore_ascii_case("append") => Ok(ArrayStrategy::Append),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown array strategy '{}', expected replace, append or key:<field>",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ArrayStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayStrategy::Replace => write!(f, "replace"),
+            ArrayStrategy::Append => write!(f, "append"),
+            ArrayStrategy::MergeByKey(field) => write!(f, "key:{}", field),
+        }
+    }
+}
+
+/// Bedeutung von `null` in einem späteren Dokument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullStrategy {
+    /// `null` ist ein normaler Wert und überschreibt den bisherigen.
+    #[default]
+    Set,
+    /// `null` löscht den Key (wie bei JSON Merge Patch).
+    Delete,
+}
+
+/// Optionen für `merge_values`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeOptions {
+    pub arrays: ArrayStrategy,
+    pub nulls: NullStrategy,
+}
+
+/// Mergt `overlay` rekursiv in `base`.
+pub fn merge_values(base: &mut JsonValue, overlay: &JsonValue, options: &MergeOptions) {
+    match (base, overlay) {
+        (JsonValue::Object(base_obj), JsonValue::Object(overlay_obj)) => {
+            for (key, value) in overlay_obj {
+                if value.is_null() && options.nulls == NullStrategy::Delete {
+                    base_obj.remove(key);
+                    continue;
+                }
+                match base_obj.get_mut(key) {
+                    Some(existing) => merge_values(existing, value, options),
+                    None => {
+                        base_obj.insert(key.clone(), without_nulls(value, options));
+                    }
+                }
+            }
+        }
+        (JsonValue::Array(base_arr), JsonValue::Array(overlay_arr)) => match &options.arrays {
+            ArrayStrategy::Replace => *base_arr = overlay_arr.clone(),
+            ArrayStrategy::Append => base_arr.extend(overlay_arr.iter().cloned()),
+            ArrayStrategy::MergeByKey(field) => {
+                for item in overlay_arr {
+                    let existing = item.get(field).and_then(|id| {
+                        base_arr
+                            .iter_mut()
+                            .find(|candidate| candidate.get(field) == Some(id))
+                    });
+                    match existing {
+                        Some(existing) => merge_values(existing, item, options),
+                        None => base_arr.push(item.clone()),
+                    }
+                }
+            }
+        },
+        (base, overlay) => *base = without_nulls(overlay, options),
+    }
+}
+
+/// Mergt alle Dokumente der Reihe nach, das erste ist die Basis.
+pub fn merge_all<I>(values: I, options: &MergeOptions) -> JsonValue
+where
+    I: IntoIterator<Item = JsonValue>,
+{
+    let mut values = values.into_iter();
+    let mut result = values.next().unwrap_or(JsonValue::Null);
+    for value in values {
+        merge_values(&mut result, &value, options);
+    }
+    result
+}
+
+// private helper funktionen
+
+/// Neue Objekte enthalten bei `NullStrategy::Delete` keine `null`-Keys.
+fn without_nulls(value: &JsonValue, options: &MergeOptions) -> JsonValue {
+    match value {
+        JsonValue::Object(obj) if options.nulls == NullStrategy::Delete => JsonValue::Object(
+            obj.iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), without_nulls(v, options)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn merge(base: JsonValue, overlay: JsonValue, options: &MergeOptions) -> JsonValue {
+        merge_all([base, overlay], options)
+    }
+
+    #[test]
+    fn test_merge_objects_deep() {
+        let result = merge(
+            json!({"db": {"host": "localhost", "port": 5432}, "debug": true}),
+            json!({"db": {"port": 6543}, "debug": false, "name": "prod"}),
+            &MergeOptions::default(),
+        );
+        assert_eq!(
+            result,
+            json!({"db": {"host": "localhost", "port": 6543}, "debug": false, "name": "prod"})
+        );
+    }
+
+    #[test]
+    fn test_merge_arrays_replace_and_append() {
+        let base = json!({"tags": ["a", "b"]});
+        let overlay = json!({"tags": ["c"]});
+
+        let replaced = merge(base.clone(), overlay.clone(), &MergeOptions::default());
+        assert_eq!(replaced, json!({"tags": ["c"]}));
+
+        let options = MergeOptions {
+            arrays: ArrayStrategy::Append,
+            ..Default::default()
+        };
+        assert_eq!(
+            merge(base, overlay, &options),
+            json!({"tags": ["a", "b", "c"]})
+        );
+    }
+
+    #[test]
+    fn test_merge_arrays_by_key() {
+        let options = MergeOptions {
+            arrays: "key:name".parse().unwrap(),
+            ..Default::default()
+        };
+        let result = merge(
+            json!({"services": [{"name": "web", "port": 80}, {"name": "db", "port": 5432}]}),
+            json!({"services": [{"name": "web", "port": 8080}, {"name": "cache", "port": 6379}]}),
+            &options,
+        );
+        assert_eq!(
+            result,
+            json!({"services": [
+                {"name": "web", "port": 8080},
+                {"name": "db", "port": 5432},
+                {"name": "cache", "port": 6379}
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_merge_null_strategies() {
+        let base = json!({"a": 1, "b": {"c": 2, "d": 3}});
+        let overlay = json!({"a": null, "b": {"c": null}, "e": {"f": null, "g": 4}});
+
+        let set = merge(base.clone(), overlay.clone(), &MergeOptions::default());
+        assert_eq!(
+            set,
+            json!({"a": null, "b": {"c": null, "d": 3}, "e": {"f": null, "g": 4}})
+        );
+
+        let options = MergeOptions {
+            nulls: NullStrategy::Delete,
+            ..Default::default()
+        };
+        assert_eq!(
+            merge(base, overlay, &options),
+            json!({"b": {"d": 3}, "e": {"g": 4}})
+        );
+    }
+
+    #[test]
+    fn test_array_strategy_parsing() {
+        assert_eq!(
+            "append".parse::<ArrayStrategy>().unwrap(),
+            ArrayStrategy::Append
+        );
+        assert_eq!(
+            "key:id".parse::<ArrayStrategy>().unwrap(),
+            ArrayStrategy::MergeByKey("id".to_string())
+        );
+        assert!("key:".parse::<ArrayStrategy>().is_err());
+        assert!("union".parse::<ArrayStrategy>().is_err());
+        assert_eq!(
+            ArrayStrategy::MergeByKey("id".to_string()).to_string(),
+            "key:id"
+        );
+    }
+}
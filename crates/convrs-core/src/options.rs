@@ -0,0 +1,28 @@
+//! Format-spezifische Optionen für Konvertierungen.
+//!
+//! `FileFormat::convert` arbeitet immer mit den Standardwerten,
+//! `FileFormat::convert_bytes` nimmt die Optionen explizit entgegen.
+
+use crate::formats::fixed_width::FixedWidthOptions;
+use crate::formats::hocon::HoconOptions;
+use crate::formats::plist::PlistOptions;
+use crate::formats::sql::SqlOptions;
+use crate::formats::xlsx::XlsxOptions;
+use crate::query::Query;
+
+/// Sammelt die Optionen aller Formate, die konfigurierbar sind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConvertOptions {
+    /// Optionen für SQL-Ausgabe (Dialekt, Tabelle, Batch-Grösse).
+    pub sql: SqlOptions,
+    /// Optionen für XLSX-Eingabe (Sheet-Auswahl).
+    pub xlsx: XlsxOptions,
+    /// Optionen für plist-Ausgabe (XML oder binär).
+    pub plist: PlistOptions,
+    /// Optionen für Fixed-Width-Eingabe (Spalten-Spezifikation).
+    pub fixed_width: FixedWidthOptions,
+    /// Optionen für HOCON-Eingabe (Verzeichnis für Includes).
+    pub hocon: HoconOptions,
+    /// Query-Ausdruck, der zwischen Parsen und Serialisieren angewendet wird.
+    pub select: Option<Query>,
+}
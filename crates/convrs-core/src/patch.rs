@@ -0,0 +1,174 @@
+//! Anwenden von JSON Patch (RFC 6902) und JSON Merge Patch (RFC 7386).
+//!
+//! Beide Patch-Arten arbeiten auf dem gemeinsamen Datenmodell und funktionieren
+//! deshalb für jedes Format, das convrs lesen und schreiben kann.
+
+use std::fmt;
+use std::str::FromStr;
+
+use json_patch::{Patch, PatchOperation};
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Art eines Patch-Dokuments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchKind {
+    /// RFC 6902: Array von Operationen (`add`, `remove`, `replace`, `move`, `copy`, `test`).
+    JsonPatch,
+    /// RFC 7386: Objekt, das in das Dokument gemergt wird, `null` löscht Keys.
+    MergePatch,
+}
+
+impl PatchKind {
+    /// Ein Array ist ein JSON Patch, alles andere ein Merge Patch.
+    pub fn detect(patch: &JsonValue) -> Self {
+        if patch.is_array() {
+            PatchKind::JsonPatch
+        } else {
+            PatchKind::MergePatch
+        }
+    }
+}
+
+impl FromStr for PatchKind {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json-patch" | "json" | "6902" => Ok(PatchKind::JsonPatch),
+            "merge-patch" | "merge" | "7386" => Ok(PatchKind::MergePatch),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "Unknown patch kind '{}', expected json-patch or merge-patch",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for PatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchKind::JsonPatch => write!(f, "json-patch"),
+            PatchKind::MergePatch => write!(f, "merge-patch"),
+        }
+    }
+}
+
+/// Wendet `patch` auf `document` an. Ohne `kind` wird die Art anhand des Patches erkannt.
+///
+/// Ein JSON Patch wird ganz oder gar nicht angewendet: schlägt eine Operation fehl,
+/// bleibt das Dokument unverändert.
+pub fn apply_patch(
+    document: &mut JsonValue,
+    patch: &JsonValue,
+    kind: Option<PatchKind>,
+) -> Result<(), FormatError> {
+    match kind.unwrap_or_else(|| PatchKind::detect(patch)) {
+        PatchKind::MergePatch => {
+            json_patch::merge(document, patch);
+            Ok(())
+        }
+        PatchKind::JsonPatch => {
+            let Patch(operations) = serde_json::from_value(patch.clone())
+                .map_err(|e| FormatError::ParseError(format!("Invalid JSON Patch: {}", e)))?;
+
+            json_patch::patch(document, &operations).map_err(|e| {
+                let name = operations
+                    .get(e.operation)
+                    .map_or("unknown", operation_name);
+                FormatError::PathError(format!(
+                    "JSON Patch operation {} ({}) failed at '{}': {}",
+                    e.operation + 1,
+                    name,
+                    e.path,
+                    e.kind
+                ))
+            })
+        }
+    }
+}
+
+// private helper funktionen
+
+fn operation_name(operation: &PatchOperation) -> &'static str {
+    match operation {
+        PatchOperation::Add(_) => "add",
+        PatchOperation::Remove(_) => "remove",
+        PatchOperation::Replace(_) => "replace",
+        PatchOperation::Move(_) => "move",
+        PatchOperation::Copy(_) => "copy",
+        PatchOperation::Test(_) => "test",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_json_patch() {
+        let mut doc = json!({"db": {"port": 5432}, "tags": ["a"]});
+        let patch = json!([
+            {"op": "replace", "path": "/db/port", "value": 6543},
+            {"op": "add", "path": "/tags/-", "value": "b"},
+            {"op": "copy", "from": "/db", "path": "/backup"},
+            {"op": "test", "path": "/tags/0", "value": "a"}
+        ]);
+
+        apply_patch(&mut doc, &patch, None).unwrap();
+        assert_eq!(
+            doc,
+            json!({"db": {"port": 6543}, "tags": ["a", "b"], "backup": {"port": 6543}})
+        );
+    }
+
+    #[test]
+    fn test_apply_merge_patch() {
+        let mut doc = json!({"db": {"host": "localhost", "port": 5432}, "debug": true});
+        let patch = json!({"db": {"port": 6543}, "debug": null});
+
+        apply_patch(&mut doc, &patch, None).unwrap();
+        assert_eq!(doc, json!({"db": {"host": "localhost", "port": 6543}}));
+    }
+
+    #[test]
+    fn test_json_patch_error_names_operation_and_path() {
+        let mut doc = json!({"a": 1});
+        let original = doc.clone();
+        let patch = json!([
+            {"op": "replace", "path": "/a", "value": 2},
+            {"op": "remove", "path": "/missing/key"}
+        ]);
+
+        let err = apply_patch(&mut doc, &patch, None).unwrap_err();
+        assert!(matches!(err, FormatError::PathError(_)));
+        assert!(
+            err.to_string()
+                .contains("operation 2 (remove) failed at '/missing/key'")
+        );
+        // fehlgeschlagene patches werden komplett zurückgerollt
+        assert_eq!(doc, original);
+    }
+
+    #[test]
+    fn test_invalid_json_patch_document() {
+        let err = apply_patch(&mut json!({}), &json!([{"op": "explode"}]), None).unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON Patch"));
+
+        // ein objekt kann explizit nicht als JSON Patch angewendet werden
+        let err = apply_patch(&mut json!({}), &json!({}), Some(PatchKind::JsonPatch)).unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON Patch"));
+    }
+
+    #[test]
+    fn test_patch_kind_parsing() {
+        assert_eq!(
+            "merge-patch".parse::<PatchKind>().unwrap(),
+            PatchKind::MergePatch
+        );
+        assert_eq!("6902".parse::<PatchKind>().unwrap(), PatchKind::JsonPatch);
+        assert!("diff".parse::<PatchKind>().is_err());
+    }
+}
@@ -0,0 +1,687 @@
+//! Query-Ausdrücke (jq-Subset) zum Auswählen von Teilen eines Dokuments.
+//!
+//! Unterstützt werden:
+//! - `.` (ganzes Dokument), `.key`, `."key mit leerzeichen"`, `.["key"]`
+//! - `[0]`, `[-1]` (Index), `[1:3]` (Slice), `[]` (alle Elemente/Werte)
+//! - `|` (Pipe) und `select(<pfad> <op> <wert>)` mit `==`, `!=`, `<`, `<=`, `>`, `>=`
+//!
+//! Die Abfrage läuft auf dem gemeinsamen Datenmodell (`serde_json::Value`) zwischen
+//! Parsen und Serialisieren. Ein einfacher Pfad ohne `[]` und `select(...)` ergibt
+//! genau einen Wert, der direkt ausgegeben wird. Mit `[]` oder `select(...)` entsteht
+//! immer ein Array, auch bei nur einem oder keinem Treffer.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::error::FormatError;
+
+/// Ein geparster Query-Ausdruck, z.b. `.services[] | select(.port > 1024) | .name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    source: String,
+    stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stage {
+    Path(Vec<Step>),
+    Select(Condition),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+    Iterate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    path: Vec<Step>,
+    comparison: Option<(CompareOp, JsonValue)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Query {
+    /// Wendet die Abfrage an und gibt alle Ergebnisse zurück.
+    pub fn apply(&self, value: &JsonValue) -> Result<Vec<JsonValue>, FormatError> {
+        let mut current = vec![value.clone()];
+
+        for stage in &self.stages {
+            let mut next = Vec::new();
+            for item in current {
+                match stage {
+                    Stage::Path(steps) => next.extend(apply_steps(item, steps)?),
+                    Stage::Select(condition) => {
+                        if condition.matches(&item)? {
+                            next.push(item);
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+
+        Ok(current)
+    }
+
+    /// Wendet die Abfrage an und fasst das Ergebnis zu einem Dokument zusammen:
+    /// ein einfacher Pfad ergibt seinen Wert, `[]` und `select(...)` immer ein Array.
+    ///
+    /// So hängt die Form der Ausgabe nur von der Abfrage ab, nicht von der Anzahl Treffer.
+    pub fn select(&self, value: &JsonValue) -> Result<JsonValue, FormatError> {
+        let mut results = self.apply(value)?;
+        if self.is_single() && results.len() == 1 {
+            Ok(results.remove(0))
+        } else {
+            Ok(JsonValue::Array(results))
+        }
+    }
+
+    /// Ob die Abfrage ein einfacher Pfad ist (kein `[]`, kein `select(...)`).
+    fn is_single(&self) -> bool {
+        self.stages.iter().all(|stage| match stage {
+            Stage::Path(steps) => !steps.contains(&Step::Iterate),
+            Stage::Select(_) => false,
+        })
+    }
+}
+
+impl FromStr for Query {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = QueryParser {
+            chars: s.chars().collect(),
+            pos: 0,
+        };
+
+        let stages = parser
+            .parse_pipeline()
+            .map_err(|msg| FormatError::ParseError(format!("Invalid query '{}': {}", s, msg)))?;
+
+        Ok(Query {
+            source: s.to_string(),
+            stages,
+        })
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Ein einfacher Pfad ohne Iteration und Filter, z.b. `.server.ports[0]`.
+///
+/// Wird für `get`/`set`/`delete` verwendet. Der führende Punkt ist optional (`server.port`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPath {
+    source: String,
+    segments: Vec<PathSegment>,
+}
+
+/// Ein Segment eines `KeyPath`: Objekt-Key oder Array-Index (negativ: vom Ende).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(i64),
+}
+
+impl KeyPath {
+    /// Baut einen Pfad aus Segmenten, z.b. für die Pfade im Diff. Der leere Pfad ist `.`.
+    pub fn from_segments(segments: Vec<PathSegment>) -> Self {
+        let mut source = String::new();
+        for segment in &segments {
+            match segment {
+                PathSegment::Key(key) if !key.is_empty() && key.chars().all(is_ident_char) => {
+                    source.push('.');
+                    source.push_str(key);
+                }
+                PathSegment::Key(key) => {
+                    source.push('.');
+                    source.push_str(&JsonValue::String(key.clone()).to_string());
+                }
+                // wie bei jq: `.[0]` für einen index direkt an der wurzel
+                PathSegment::Index(index) if source.is_empty() => {
+                    source.push_str(&format!(".[{}]", index))
+                }
+                PathSegment::Index(index) => source.push_str(&format!("[{}]", index)),
+            }
+        }
+        if source.is_empty() {
+            source.push('.');
+        }
+        KeyPath { source, segments }
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Der Pfad als JSON Pointer (RFC 6901), z.b. `/server/ports/0`.
+    pub fn to_json_pointer(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+                PathSegment::Index(index) => format!("/{}", index),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for KeyPath {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = if s.starts_with('.') {
+            s.to_string()
+        } else {
+            format!(".{}", s)
+        };
+        let invalid = |msg: &str| FormatError::ParseError(format!("Invalid path '{}': {}", s, msg));
+
+        let mut parser = QueryParser {
+            chars: normalized.chars().collect(),
+            pos: 0,
+        };
+        let steps = parser.parse_path().map_err(|msg| invalid(&msg))?;
+        parser.skip_ws();
+        if let Some(c) = parser.peek() {
+            return Err(invalid(&format!("unexpected '{}'", c)));
+        }
+
+        let segments = steps
+            .into_iter()
+            .map(|step| match step {
+                Step::Key(key) => Ok(PathSegment::Key(key)),
+                Step::Index(index) => Ok(PathSegment::Index(index)),
+                Step::Slice(..) | Step::Iterate => {
+                    Err(invalid("iterations and slices are not allowed"))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(KeyPath {
+            source: normalized,
+            segments,
+        })
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+// private helper funktionen
+
+struct QueryParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let matches = s
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c));
+        if matches {
+            self.pos += s.chars().count();
+        }
+        matches
+    }
+
+    fn parse_pipeline(&mut self) -> Result<Vec<Stage>, String> {
+        let mut stages = Vec::new();
+
+        loop {
+            self.skip_ws();
+            let stage = if self.eat("select") {
+                self.skip_ws();
+                if !self.eat("(") {
+                    return Err("expected '(' after select".to_string());
+                }
+                let condition = self.parse_condition()?;
+                self.skip_ws();
+                if !self.eat(")") {
+                    return Err("expected ')' after select condition".to_string());
+                }
+                Stage::Select(condition)
+            } else {
+                Stage::Path(self.parse_path()?)
+            };
+            stages.push(stage);
+
+            self.skip_ws();
+            match self.peek() {
+                None => return Ok(stages),
+                Some('|') => self.pos += 1,
+                Some(c) => return Err(format!("unexpected '{}' at position {}", c, self.pos + 1)),
+            }
+        }
+    }
+
+    /// Parst einen Pfad wie `.a.b[0][]`. Ein einzelner `.` ergibt einen leeren Pfad.
+    fn parse_path(&mut self) -> Result<Vec<Step>, String> {
+        self.skip_ws();
+        if self.peek() != Some('.') {
+            return Err(format!("expected '.' at position {}", self.pos + 1));
+        }
+
+        let mut steps = Vec::new();
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('"') => steps.push(Step::Key(self.parse_string()?)),
+                        Some(c) if is_ident_char(c) => steps.push(Step::Key(self.parse_ident())),
+                        // `.[0]` oder einzelner `.`
+                        _ => {}
+                    }
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    steps.push(self.parse_bracket()?);
+                }
+                _ => return Ok(steps),
+            }
+        }
+    }
+
+    fn parse_bracket(&mut self) -> Result<Step, String> {
+        self.skip_ws();
+        if self.eat("]") {
+            return Ok(Step::Iterate);
+        }
+        if self.peek() == Some('"') {
+            let key = self.parse_string()?;
+            self.skip_ws();
+            if !self.eat("]") {
+                return Err("expected ']' after key".to_string());
+            }
+            return Ok(Step::Key(key));
+        }
+
+        let start = self.parse_int()?;
+        self.skip_ws();
+        if self.eat(":") {
+            let end = self.parse_int()?;
+            self.skip_ws();
+            if !self.eat("]") {
+                return Err("expected ']' after slice".to_string());
+            }
+            return Ok(Step::Slice(start, end));
+        }
+        if !self.eat("]") {
+            return Err("expected ']' after index".to_string());
+        }
+        start
+            .map(Step::Index)
+            .ok_or_else(|| "expected index, slice or ']' inside brackets".to_string())
+    }
+
+    fn parse_int(&mut self) -> Result<Option<i64>, String> {
+        self.skip_ws();
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| format!("invalid number '{}'", text))
+    }
+
+    fn parse_ident(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek().filter(|c| is_ident_char(*c)) {
+            ident.push(c);
+            self.pos += 1;
+        }
+        ident
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+        let literal: String = self.chars[start..self.pos].iter().collect();
+        serde_json::from_str(&literal).map_err(|e| format!("invalid string {}: {}", literal, e))
+    }
+
+    fn parse_condition(&mut self) -> Result<Condition, String> {
+        let path = self.parse_path()?;
+        self.skip_ws();
+
+        let op = if self.eat("==") {
+            CompareOp::Eq
+        } else if self.eat("!=") {
+            CompareOp::Ne
+        } else if self.eat("<=") {
+            CompareOp::Le
+        } else if self.eat(">=") {
+            CompareOp::Ge
+        } else if self.eat("<") {
+            CompareOp::Lt
+        } else if self.eat(">") {
+            CompareOp::Gt
+        } else {
+            return Ok(Condition {
+                path,
+                comparison: None,
+            });
+        };
+
+        self.skip_ws();
+        let literal = self.parse_literal()?;
+        Ok(Condition {
+            path,
+            comparison: Some((op, literal)),
+        })
+    }
+
+    /// Parst einen JSON-Literal (String, Zahl, true, false, null).
+    fn parse_literal(&mut self) -> Result<JsonValue, String> {
+        if self.peek() == Some('"') {
+            return self.parse_string().map(JsonValue::String);
+        }
+
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match serde_json::from_str::<JsonValue>(&text) {
+            Ok(value) if !value.is_array() && !value.is_object() => Ok(value),
+            _ => Err(format!("invalid literal '{}'", text)),
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn apply_steps(value: JsonValue, steps: &[Step]) -> Result<Vec<JsonValue>, FormatError> {
+    let mut current = vec![value];
+
+    for step in steps {
+        let mut next = Vec::new();
+        for item in current {
+            match (step, item) {
+                (_, JsonValue::Null) if !matches!(step, Step::Iterate) => {
+                    next.push(JsonValue::Null)
+                }
+                (Step::Key(key), JsonValue::Object(mut obj)) => {
+                    next.push(obj.remove(key).unwrap_or(JsonValue::Null));
+                }
+                (Step::Index(index), JsonValue::Array(arr)) => {
+                    let resolved = resolve_index(*index, arr.len());
+                    next.push(
+                        resolved
+                            .and_then(|i| arr.get(i).cloned())
+                            .unwrap_or(JsonValue::Null),
+                    );
+                }
+                (Step::Slice(start, end), JsonValue::Array(arr)) => {
+                    let len = arr.len();
+                    let start = start.map_or(0, |s| clamp_index(s, len));
+                    let end = end.map_or(len, |e| clamp_index(e, len));
+                    let items = if start < end {
+                        arr[start..end].to_vec()
+                    } else {
+                        Vec::new()
+                    };
+                    next.push(JsonValue::Array(items));
+                }
+                (Step::Iterate, JsonValue::Array(arr)) => next.extend(arr),
+                (Step::Iterate, JsonValue::Object(obj)) => {
+                    next.extend(obj.into_iter().map(|(_, v)| v))
+                }
+                (step, item) => {
+                    return Err(FormatError::PathError(format!(
+                        "Query error: cannot apply {} to {}",
+                        step.describe(),
+                        type_name(&item)
+                    )));
+                }
+            }
+        }
+        current = next;
+    }
+
+    Ok(current)
+}
+
+impl Step {
+    fn describe(&self) -> String {
+        match self {
+            Step::Key(key) => format!("key \"{}\"", key),
+            Step::Index(index) => format!("index [{}]", index),
+            Step::Slice(..) => "slice".to_string(),
+            Step::Iterate => "iteration []".to_string(),
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, item: &JsonValue) -> Result<bool, FormatError> {
+        let values = apply_steps(item.clone(), &self.path)?;
+
+        Ok(values.iter().any(|value| match &self.comparison {
+            None => !matches!(value, JsonValue::Null | JsonValue::Bool(false)),
+            Some((op, literal)) => compare(value, *op, literal),
+        }))
+    }
+}
+
+fn compare(value: &JsonValue, op: CompareOp, literal: &JsonValue) -> bool {
+    let ordering = match (value, literal) {
+        (JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (JsonValue::String(a), JsonValue::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+
+    match op {
+        CompareOp::Eq => ordering.map_or(value == literal, |o| o.is_eq()),
+        CompareOp::Ne => !ordering.map_or(value == literal, |o| o.is_eq()),
+        CompareOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+        CompareOp::Le => ordering.is_some_and(|o| o.is_le()),
+        CompareOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+        CompareOp::Ge => ordering.is_some_and(|o| o.is_ge()),
+    }
+}
+
+/// Negative Indizes zählen wie bei jq vom Ende.
+pub(crate) fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize)
+    }
+}
+
+fn clamp_index(index: i64, len: usize) -> usize {
+    resolve_index(index, len).unwrap_or(0).min(len)
+}
+
+pub(crate) fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> JsonValue {
+        serde_json::json!({
+            "name": "stack",
+            "services": [
+                {"name": "web", "port": 8080, "public": true},
+                {"name": "db", "port": 5432},
+                {"name": "cache", "port": 6379, "public": false}
+            ],
+            "meta data": {"owner": "core"}
+        })
+    }
+
+    fn select(expr: &str) -> JsonValue {
+        expr.parse::<Query>().unwrap().select(&doc()).unwrap()
+    }
+
+    #[test]
+    fn test_query_identity_and_keys() {
+        assert_eq!(select("."), doc());
+        assert_eq!(select(".name"), "stack");
+        assert_eq!(select(".\"meta data\".owner"), "core");
+        assert_eq!(select(".[\"meta data\"][\"owner\"]"), "core");
+        assert_eq!(select(".missing.deeper"), JsonValue::Null);
+    }
+
+    #[test]
+    fn test_query_index_slice_iterate() {
+        assert_eq!(select(".services[0].name"), "web");
+        assert_eq!(select(".services[-1].name"), "cache");
+        assert_eq!(
+            select(".services[].name"),
+            serde_json::json!(["web", "db", "cache"])
+        );
+        assert_eq!(
+            select(".services[1:][].name"),
+            serde_json::json!(["db", "cache"])
+        );
+        assert_eq!(select(".services[:1] | .[0].port"), 8080);
+    }
+
+    #[test]
+    fn test_query_pipe_and_select() {
+        assert_eq!(
+            select(".services[] | select(.port > 6000) | .name"),
+            serde_json::json!(["web", "cache"])
+        );
+        assert_eq!(
+            select(".services[] | select(.public) | .name"),
+            serde_json::json!(["web"])
+        );
+        assert_eq!(
+            select(".services[] | select(.name != \"db\") | .port"),
+            serde_json::json!([8080, 6379])
+        );
+        assert_eq!(
+            select(".services[] | select(.port == 1)"),
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn test_query_wildcard_always_returns_array() {
+        let doc = serde_json::json!({"items": [{"name": "only"}]});
+        let query: Query = ".items[].name".parse().unwrap();
+        assert_eq!(query.select(&doc).unwrap(), serde_json::json!(["only"]));
+        let query: Query = ".items[0].name".parse().unwrap();
+        assert_eq!(query.select(&doc).unwrap(), "only");
+        let query: Query = ".items[1:]".parse().unwrap();
+        assert_eq!(query.select(&doc).unwrap(), serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_query_type_error() {
+        let query: Query = ".name[0]".parse().unwrap();
+        let err = query.apply(&doc()).unwrap_err();
+        assert!(matches!(err, FormatError::PathError(_)));
+        assert!(err.to_string().contains("cannot apply index [0] to string"));
+    }
+
+    #[test]
+    fn test_key_path_parsing() {
+        let path: KeyPath = "server.ports[-1]".parse().unwrap();
+        assert_eq!(
+            path.segments(),
+            &[
+                PathSegment::Key("server".to_string()),
+                PathSegment::Key("ports".to_string()),
+                PathSegment::Index(-1)
+            ]
+        );
+        assert_eq!(path.to_string(), ".server.ports[-1]");
+        assert!(".".parse::<KeyPath>().unwrap().segments().is_empty());
+        assert!(".a[]".parse::<KeyPath>().is_err());
+        assert!(".a | .b".parse::<KeyPath>().is_err());
+    }
+
+    #[test]
+    fn test_key_path_from_segments() {
+        let path = KeyPath::from_segments(vec![
+            PathSegment::Key("meta data".to_string()),
+            PathSegment::Key("a/b".to_string()),
+            PathSegment::Index(0),
+        ]);
+        assert_eq!(path.to_string(), ".\"meta data\".\"a/b\"[0]");
+        assert_eq!(path.to_json_pointer(), "/meta data/a~1b/0");
+        assert_eq!(path.to_string().parse::<KeyPath>().unwrap(), path);
+        assert_eq!(KeyPath::from_segments(Vec::new()).to_string(), ".");
+    }
+
+    #[test]
+    fn test_query_syntax_errors() {
+        assert!("services".parse::<Query>().is_err());
+        assert!(".a[".parse::<Query>().is_err());
+        assert!(".a | select(.b >)".parse::<Query>().is_err());
+        let err = ".a b".parse::<Query>().unwrap_err();
+        assert!(matches!(err, FormatError::ParseError(_)));
+    }
+}
@@ -0,0 +1,379 @@
+//! Reparatur häufiger Syntaxfehler (`convrs convert --repair`).
+//!
+//! - `trailing-comma`: Komma vor `}` oder `]` (JSON)
+//! - `single-quotes`: Strings und Keys in einfachen Anführungszeichen (JSON)
+//! - `unquoted-key`: Keys ohne Anführungszeichen (JSON)
+//! - `smart-quotes`: typografische Anführungszeichen (`“ ”`, `‘ ’`) statt `"` und `'`
+//!   außerhalb von Strings (JSON, TOML)
+//! - `yaml-tab`: Tabs in der Einrückung (YAML)
+//!
+//! Repariert wird nur, wenn das Dokument nicht gelesen werden kann, und nur, wenn es
+//! danach gelesen werden kann. Inhalte von Strings bleiben unverändert.
+
+use std::fmt;
+
+use crate::ConvertOptions;
+use crate::error::FormatError;
+use crate::format::FileFormat;
+use crate::location::{Location, offset_to_location};
+
+/// Eine angewendete Reparatur, die Position bezieht sich auf den Originaltext.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub rule: &'static str,
+    pub message: String,
+    pub location: Location,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// Ergebnis von `repair_bytes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repaired {
+    pub content: Vec<u8>,
+    /// Leer, wenn das Dokument schon gültig war.
+    pub repairs: Vec<Repair>,
+}
+
+/// Repariert ein Dokument, das nicht gelesen werden kann. Gültige Dokumente kommen
+/// unverändert zurück. Hilft die Reparatur nicht, gibt es den ursprünglichen Fehler.
+pub fn repair_bytes(
+    format: FileFormat,
+    input: &[u8],
+    options: &ConvertOptions,
+) -> Result<Repaired, FormatError> {
+    let error = match format.parse_bytes(input, options) {
+        Ok(_) => {
+            return Ok(Repaired {
+                content: input.to_vec(),
+                repairs: Vec::new(),
+            });
+        }
+        Err(e) => e,
+    };
+    let Ok(text) = std::str::from_utf8(input) else {
+        return Err(error);
+    };
+
+    let (content, repairs) = repair_text(format, text);
+    if repairs.is_empty() || format.parse_bytes(content.as_bytes(), options).is_err() {
+        return Err(error);
+    }
+    Ok(Repaired {
+        content: content.into_bytes(),
+        repairs,
+    })
+}
+
+/// Wendet alle Reparaturen für das Format an, ohne zu prüfen, ob das Ergebnis gültig ist.
+pub fn repair_text(format: FileFormat, text: &str) -> (String, Vec<Repair>) {
+    let mut repairs = Vec::new();
+    let content = match format {
+        FileFormat::Json | FileFormat::Jsonl => repair_json(text, &mut repairs),
+        FileFormat::Toml => repair_toml(text, &mut repairs),
+        FileFormat::Yaml => repair_yaml(text, &mut repairs),
+        _ => text.to_string(),
+    };
+    (content, repairs)
+}
+
+// private helper funktionen
+
+const DOUBLE_SMART: [char; 3] = ['“', '”', '„'];
+const SINGLE_SMART: [char; 2] = ['‘', '’'];
+
+fn repair_json(text: &str, repairs: &mut Vec<Repair>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut push = |rule: &'static str, message: String, offset: usize| {
+        repairs.push(Repair {
+            rule,
+            message,
+            location: offset_to_location(text, offset),
+        })
+    };
+
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let start = i + c.len_utf8();
+        match c {
+            '"' => {
+                if let Some(end) = string_end(text, start, &['"'], true) {
+                    out.push_str(&text[i..=end]);
+                    i = end + 1;
+                    continue;
+                }
+            }
+            c if DOUBLE_SMART.contains(&c) => {
+                if let Some(end) = string_end(text, start, &['"', '“', '”'], true) {
+                    push(
+                        "smart-quotes",
+                        "replaced typographic quotes with \"".to_string(),
+                        i,
+                    );
+                    out.push('"');
+                    out.push_str(&text[start..end]);
+                    out.push('"');
+                    i = end + text[end..].chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+            }
+            '\'' | '‘' | '’' => {
+                let closers: &[char] = if c == '\'' {
+                    &['\'']
+                } else {
+                    &['\'', '‘', '’']
+                };
+                if let Some(end) = string_end(text, start, closers, true) {
+                    if c == '\'' {
+                        push(
+                            "single-quotes",
+                            "replaced single quotes with double quotes".to_string(),
+                            i,
+                        );
+                    } else {
+                        push(
+                            "smart-quotes",
+                            "replaced typographic quotes with \"".to_string(),
+                            i,
+                        );
+                    }
+                    out.push('"');
+                    out.push_str(&double_quoted(&text[start..end]));
+                    out.push('"');
+                    i = end + text[end..].chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+            }
+            ',' if text[start..].trim_start().starts_with(['}', ']']) => {
+                push("trailing-comma", "removed trailing comma".to_string(), i);
+                i = start;
+                continue;
+            }
+            c if is_key_start(c) && out.trim_end().ends_with(['{', ',']) => {
+                let end = text[i..]
+                    .find(|c: char| !is_key_char(c))
+                    .map_or(text.len(), |n| i + n);
+                let key = &text[i..end];
+                if text[end..].trim_start().starts_with(':') {
+                    push("unquoted-key", format!("quoted key '{}'", key), i);
+                    out.push('"');
+                    out.push_str(key);
+                    out.push('"');
+                } else {
+                    out.push_str(key);
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+        i = start;
+    }
+    out
+}
+
+/// Nur typografische Anführungszeichen, Strings und Kommentare bleiben unverändert.
+fn repair_toml(text: &str, repairs: &mut Vec<Repair>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let start = i + c.len_utf8();
+        let end = match c {
+            '#' => Some(text[i..].find('\n').map_or(text.len(), |n| i + n)),
+            '"' if text[i..].starts_with("\"\"\"") => {
+                text[i + 3..].find("\"\"\"").map(|n| i + 3 + n + 3)
+            }
+            '\'' if text[i..].starts_with("'''") => {
+                text[i + 3..].find("'''").map(|n| i + 3 + n + 3)
+            }
+            '"' => string_end(text, start, &['"'], true).map(|end| end + 1),
+            '\'' => string_end(text, start, &['\''], false).map(|end| end + 1),
+            _ => None,
+        };
+        if let Some(end) = end {
+            out.push_str(&text[i..end]);
+            i = end;
+            continue;
+        }
+
+        let replacement = if DOUBLE_SMART.contains(&c) {
+            Some('"')
+        } else if SINGLE_SMART.contains(&c) {
+            Some('\'')
+        } else {
+            None
+        };
+        match replacement {
+            Some(quote) => {
+                repairs.push(Repair {
+                    rule: "smart-quotes",
+                    message: format!("replaced typographic quote with {}", quote),
+                    location: offset_to_location(text, i),
+                });
+                out.push(quote);
+            }
+            None => out.push(c),
+        }
+        i = start;
+    }
+    out
+}
+
+fn repair_yaml(text: &str, repairs: &mut Vec<Repair>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let indent = &line[..indent_len];
+        if let Some(tab) = indent.find('\t') {
+            repairs.push(Repair {
+                rule: "yaml-tab",
+                message: "replaced tabs in indentation with two spaces each".to_string(),
+                location: offset_to_location(text, offset + tab),
+            });
+            out.push_str(&indent.replace('\t', "  "));
+            out.push_str(&line[indent_len..]);
+        } else {
+            out.push_str(line);
+        }
+        offset += line.len();
+    }
+    out
+}
+
+/// Offset des schließenden Zeichens eines Strings, der bei `start` beginnt. `None`, wenn
+/// der String nicht in derselben Zeile geschlossen wird.
+fn string_end(text: &str, start: usize, closers: &[char], escapes: bool) -> Option<usize> {
+    let mut chars = text[start..].char_indices();
+    while let Some((n, c)) = chars.next() {
+        match c {
+            '\\' if escapes => {
+                chars.next();
+            }
+            '\n' => return None,
+            c if closers.contains(&c) => return Some(start + n),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Inhalt eines String in einfachen Anführungszeichen für doppelte: `\'` wird zu `'`,
+/// `"` wird escaped.
+fn double_quoted(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\'') => out.push('\''),
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => out.push('\\'),
+            },
+            '"' => out.push_str("\\\""),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn is_key_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repair(format: FileFormat, input: &str) -> (String, Vec<(&'static str, usize, usize)>) {
+        let repaired = repair_bytes(format, input.as_bytes(), &ConvertOptions::default()).unwrap();
+        (
+            String::from_utf8(repaired.content).unwrap(),
+            repaired
+                .repairs
+                .iter()
+                .map(|r| (r.rule, r.location.line, r.location.column))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_repair_json() {
+        let input = "{\n  name: 'it\\'s \"x\"',\n  \"tags\": ['a', \"b\",],\n}\n";
+        let (content, repairs) = repair(FileFormat::Json, input);
+        assert_eq!(
+            content,
+            "{\n  \"name\": \"it's \\\"x\\\"\",\n  \"tags\": [\"a\", \"b\"]\n}\n"
+        );
+        assert_eq!(
+            repairs,
+            vec![
+                ("unquoted-key", 2, 3),
+                ("single-quotes", 2, 9),
+                ("single-quotes", 3, 12),
+                ("trailing-comma", 3, 20),
+                ("trailing-comma", 3, 22),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repair_smart_quotes_outside_strings() {
+        let (content, repairs) = repair(
+            FileFormat::Json,
+            "{“name”: “app”, \"quote\": \"“x”\", “tag”: ‘y’}",
+        );
+        assert_eq!(
+            content,
+            "{\"name\": \"app\", \"quote\": \"“x”\", \"tag\": \"y\"}"
+        );
+        assert_eq!(repairs.len(), 4);
+        assert!(repairs.iter().all(|(rule, ..)| *rule == "smart-quotes"));
+
+        let (content, repairs) = repair(
+            FileFormat::Toml,
+            "# it’s fine\nname = “app”\nquote = \"“x”\"\n",
+        );
+        assert_eq!(content, "# it’s fine\nname = \"app\"\nquote = \"“x”\"\n");
+        assert_eq!(
+            repairs,
+            vec![("smart-quotes", 2, 8), ("smart-quotes", 2, 12)]
+        );
+    }
+
+    #[test]
+    fn test_repair_yaml_tabs() {
+        let (content, repairs) = repair(FileFormat::Yaml, "server:\n\thost: a\n\tport: 1\n");
+        assert_eq!(content, "server:\n  host: a\n  port: 1\n");
+        assert_eq!(repairs, vec![("yaml-tab", 2, 1), ("yaml-tab", 3, 1)]);
+    }
+
+    #[test]
+    fn test_repair_only_when_needed_and_helpful() {
+        // gültige dokumente bleiben unverändert, auch wenn eine regel greifen würde
+        let (content, repairs) = repair(FileFormat::Yaml, "quote: “x”\n");
+        assert_eq!(content, "quote: “x”\n");
+        assert!(repairs.is_empty());
+
+        // hilft die reparatur nicht, bleibt der ursprüngliche fehler
+        let err = repair_bytes(
+            FileFormat::Json,
+            b"{\"a\": [1, 2,}",
+            &ConvertOptions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, FormatError::ParseError(_)));
+    }
+}
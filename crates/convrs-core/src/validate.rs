@@ -0,0 +1,193 @@
+//! Validierung gegen JSON Schema für alle lesbaren Formate.
+//!
+//! Das Dokument wird ins gemeinsame Datenmodell geparst und dort validiert.
+//! Für Meldungen wird die Position im Originaltext über `location::locate` ergänzt.
+
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+
+use crate::ConvertOptions;
+use crate::error::FormatError;
+use crate::format::FileFormat;
+use crate::location::{Location, locate};
+use crate::query::{KeyPath, PathSegment};
+
+/// Ein kompiliertes JSON Schema.
+pub struct Schema {
+    validator: jsonschema::Validator,
+}
+
+/// Eine Verletzung des Schemas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Pfad im Dokument, z.b. `.server.port`.
+    pub path: KeyPath,
+    pub message: String,
+    /// Position im Quelltext, falls das Format sie liefern kann.
+    pub location: Option<Location>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl Schema {
+    /// Kompiliert ein Schema. Die Draft-Version wird anhand von `$schema` erkannt.
+    pub fn new(schema: &JsonValue) -> Result<Self, FormatError> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| FormatError::InvalidFormat(format!("Invalid schema: {}", e)))?;
+        Ok(Schema { validator })
+    }
+
+    /// Gibt alle Verletzungen zurück (leer, wenn `value` gültig ist). Ohne Positionen.
+    pub fn validate(&self, value: &JsonValue) -> Vec<Violation> {
+        self.validator
+            .iter_errors(value)
+            .map(|error| Violation {
+                path: pointer_to_path(error.instance_path().as_str(), value),
+                message: error.to_string(),
+                location: None,
+            })
+            .collect()
+    }
+}
+
+/// Parst `input` und validiert es. Die Verletzungen enthalten, wo möglich, Zeile und Spalte.
+pub fn validate_bytes(
+    schema: &Schema,
+    format: FileFormat,
+    input: &[u8],
+    options: &ConvertOptions,
+) -> Result<Vec<Violation>, FormatError> {
+    let value = format.parse_bytes(input, options)?;
+    let mut violations = schema.validate(&value);
+    for violation in &mut violations {
+        violation.location = locate(format, input, &violation.path);
+    }
+    Ok(violations)
+}
+
+// private helper funktionen
+
+/// Wandelt einen JSON Pointer in einen `KeyPath` um. Ob ein Segment wie `0` ein Index
+/// oder ein Key ist, entscheidet der Wert an dieser Stelle.
+fn pointer_to_path(pointer: &str, value: &JsonValue) -> KeyPath {
+    let mut current = Some(value);
+    let mut segments = Vec::new();
+
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        match (current, token.parse::<usize>()) {
+            (Some(JsonValue::Array(items)), Ok(index)) => {
+                current = items.get(index);
+                segments.push(PathSegment::Index(index as i64));
+            }
+            _ => {
+                current = current.and_then(|v| v.get(&token));
+                segments.push(PathSegment::Key(token));
+            }
+        }
+    }
+
+    KeyPath::from_segments(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Schema {
+        Schema::new(&json!({
+            "type": "object",
+            "required": ["name", "server"],
+            "properties": {
+                "name": {"type": "string"},
+                "server": {
+                    "type": "object",
+                    "properties": {
+                        "port": {"type": "integer", "maximum": 65535},
+                        "hosts": {"type": "array", "items": {"type": "string"}}
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_reports_all_violations() {
+        let violations = schema().validate(&json!({
+            "server": {"port": 70000, "hosts": ["a", 1]}
+        }));
+
+        let mut paths: Vec<String> = violations.iter().map(|v| v.path.to_string()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![".", ".server.hosts[1]", ".server.port"]);
+        assert!(
+            violations
+                .iter()
+                .any(|v| v.message.contains("\"name\" is a required property"))
+        );
+    }
+
+    #[test]
+    fn test_validate_valid_document() {
+        let violations = schema().validate(&json!({"name": "app", "server": {"port": 80}}));
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_validate_toml_with_locations() {
+        let input = "name = \"app\"\n\n[server]\nport = \"http\"\nhosts = [\"a\", 2]\n";
+        let violations = validate_bytes(
+            &schema(),
+            FileFormat::Toml,
+            input.as_bytes(),
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+
+        let located: Vec<(String, Option<Location>)> = violations
+            .iter()
+            .map(|v| (v.path.to_string(), v.location))
+            .collect();
+        assert!(located.contains(&(
+            ".server.port".to_string(),
+            Some(Location { line: 4, column: 1 })
+        )));
+        assert!(located.contains(&(
+            ".server.hosts[1]".to_string(),
+            Some(Location {
+                line: 5,
+                column: 15
+            })
+        )));
+    }
+
+    #[test]
+    fn test_pointer_with_numeric_key() {
+        let value = json!({"ports": {"8080": {"tls": 1}}, "list": [{"a": 1}]});
+        assert_eq!(
+            pointer_to_path("/ports/8080/tls", &value).segments(),
+            &[
+                PathSegment::Key("ports".to_string()),
+                PathSegment::Key("8080".to_string()),
+                PathSegment::Key("tls".to_string())
+            ]
+        );
+        assert_eq!(
+            pointer_to_path("/list/0/a", &value).to_string(),
+            ".list[0].a"
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let err = Schema::new(&json!({"type": "banana"})).err().unwrap();
+        assert!(err.to_string().contains("Invalid schema"));
+    }
+}
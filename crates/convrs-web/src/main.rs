@@ -5,9 +5,9 @@
 //! 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{console, HtmlButtonElement, HtmlSelectElement, HtmlTextAreaElement};
+use web_sys::{console, HtmlButtonElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 
-use convrs_core::FileFormat;
+use convrs_core::{ConvertOptions, FileFormat, Query};
 use std::str::FromStr;
 
 fn main() {
@@ -56,10 +56,12 @@ fn extract_error_line(error_msg: &str) -> Option<usize> {
 }
 
 /// Führt die Konvertierung über die Core-Bibliothek (convrs-core) für die web-version durch.
+/// Ein nicht-leerer `select_expr` wird als Query zwischen Parsen und Ausgabe angewendet.
 fn perform_conversion(
     input_text: &str,
     input_format: &str,
     output_format: &str,
+    select_expr: &str,
 ) -> Result<String, String> {
     let input_fmt =
         FileFormat::from_str(input_format).map_err(|e| format!("Invalid input format: {}", e))?;
@@ -67,9 +69,20 @@ fn perform_conversion(
     let output_fmt = FileFormat::from_str(output_format)
         .map_err(|e| format!("Invalid output format: {}", e))?;
 
-    input_fmt
-        .convert(input_text, output_fmt)
-        .map_err(|e| e.to_string())
+    if select_expr.trim().is_empty() {
+        return input_fmt
+            .convert(input_text, output_fmt)
+            .map_err(|e| e.to_string());
+    }
+
+    let options = ConvertOptions {
+        select: Some(Query::from_str(select_expr.trim()).map_err(|e| e.to_string())?),
+        ..Default::default()
+    };
+    let output = input_fmt
+        .convert_bytes(input_text.as_bytes(), output_fmt, &options)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(output).map_err(|e| format!("Output is not valid UTF-8: {}", e))
 }
 
 /// wasm entry-point: initialisiert das web-interface.
@@ -102,6 +115,11 @@ pub fn start() -> Result<(), JsValue> {
         .expect("output-format select not found")
         .dyn_into::<HtmlSelectElement>()?;
 
+    let select_input = document
+        .get_element_by_id("select-expr")
+        .expect("select-expr input not found")
+        .dyn_into::<HtmlInputElement>()?;
+
     let convert_button = document
         .get_element_by_id("convert-btn")
         .expect("convert button not found")
@@ -121,19 +139,21 @@ pub fn start() -> Result<(), JsValue> {
     let output_ta = output_textarea.clone();
     let input_fmt = input_format.clone();
     let output_fmt = output_format.clone();
+    let select_in = select_input.clone();
     let status = status_div.clone();
 
     let convert_closure = Closure::wrap(Box::new(move || {
         let input_text = input_ta.value();
         let input_format_val = input_fmt.value();
         let output_format_val = output_fmt.value();
+        let select_val = select_in.value();
 
         if input_text.is_empty() {
             status.set_inner_html("error: no input provided");
             return;
         }
 
-        match perform_conversion(&input_text, &input_format_val, &output_format_val) {
+        match perform_conversion(&input_text, &input_format_val, &output_format_val, &select_val) {
             Ok(output_text) => {
                 output_ta.set_value(&output_text);
                 status.set_inner_html(&format!(
@@ -193,18 +213,31 @@ mod tests {
 
     #[test]
     fn test_perform_conversion_json_to_yaml() {
-        let result = perform_conversion(r#"{"name":"Test"}"#, "json", "yaml");
+        let result = perform_conversion(r#"{"name":"Test"}"#, "json", "yaml", "");
         assert!(result.is_ok());
         assert!(result.unwrap().contains("name"));
     }
 
     #[test]
     fn test_perform_conversion_invalid_format() {
-        let result = perform_conversion("{}", "xml", "json");
+        let result = perform_conversion("{}", "xml", "json", "");
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid input format"));
     }
 
+    #[test]
+    fn test_perform_conversion_with_select() {
+        let input = r#"{"services":[{"name":"web"},{"name":"db"}]}"#;
+        let result = perform_conversion(input, "json", "yaml", ".services[].name").unwrap();
+        assert_eq!(result, "- web\n- db\n");
+    }
+
+    #[test]
+    fn test_perform_conversion_invalid_select() {
+        let result = perform_conversion("{}", "json", "yaml", "services");
+        assert!(result.unwrap_err().contains("Invalid query"));
+    }
+
     #[test]
     fn test_is_syntax_error_real_error() {
         assert!(is_syntax_error("Parse Error: Invalid JSON: line 31 column 5"));